@@ -72,7 +72,7 @@ impl Path {
 
     pub fn with(step: Step) -> Path { Path(small_vec!(step)) }
 
-    pub fn iter(&self) -> std::slice::Iter<Step> { self.0.iter() }
+    pub fn iter(&self) -> std::slice::Iter<'_, Step> { self.0.iter() }
 }
 
 impl<'path> IntoIterator for &'path Path {
@@ -103,7 +103,7 @@ impl<'ty, Ref: TypeRef> PathError<'ty, Ref> {
 }
 
 impl<Ref: TypeRef> Ty<Ref> {
-    pub fn at_path(&self, path: &Path) -> Result<&Self, PathError<Ref>> {
+    pub fn at_path(&self, path: &Path) -> Result<&Self, PathError<'_, Ref>> {
         let mut ty = self;
         let mut path = path.clone();
         let mut path_so_far = Path::new();
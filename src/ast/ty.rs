@@ -39,7 +39,7 @@ pub trait TypeRef:
     SemCommit + Clone + StrictEncode + StrictDecode + StrictDumb + Eq + Debug + Sized
 {
     fn as_ty(&self) -> Option<&Ty<Self>> { None }
-    fn type_refs(&self) -> Iter<Self> { Iter::from(self) }
+    fn type_refs(&self) -> Iter<'_, Self> { Iter::from(self) }
 
     fn is_compound(&self) -> bool { false }
     fn is_byte(&self) -> bool { false }
@@ -207,6 +207,7 @@ impl<Ref: TypeRef> Ty<Ref> {
         match self {
             Ty::Tuple(fields) if fields.len() > 1 => true,
             Ty::Struct(fields) if fields.len() > 1 => true,
+            Ty::Union(_) if self.is_option() => false,
             Ty::Enum(_) | Ty::Union(_) => true,
             _ => false,
         }
@@ -270,7 +271,12 @@ where Ref: Display
             Ty::List(ty, sizing) => {
                 f.write_str("[")?;
                 Display::fmt(ty, f)?;
-                write!(f, "{sizing}]")
+                if sizing.min == sizing.max {
+                    // Distinguishes fixed-size lists from arrays
+                    write!(f, " ^ {}..{:#x}]", sizing.min, sizing.max)
+                } else {
+                    write!(f, "{sizing}]")
+                }
             }
             Ty::Set(ty, sizing) => {
                 f.write_str("{")?;
@@ -358,7 +364,19 @@ where Ref: Display
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         Display::fmt(&self.name, f)?;
         f.write_str(" ")?;
-        Display::fmt(&self.ty, f)
+        fmt_field_ty(&self.ty, f)
+    }
+}
+
+/// Wraps compound field types into parenthesis, such that they can't be confused with the sibling
+/// fields.
+fn fmt_field_ty<Ref: TypeRef + Display>(ty: &Ref, f: &mut Formatter<'_>) -> fmt::Result {
+    if ty.is_compound() {
+        f.write_str("(")?;
+        Display::fmt(ty, f)?;
+        f.write_str(")")
+    } else {
+        Display::fmt(ty, f)
     }
 }
 
@@ -494,11 +512,11 @@ where Ref: Display
         let mut iter = self.iter();
         let last = iter.next_back();
         for ty in iter {
-            Display::fmt(ty, f)?;
+            fmt_field_ty(ty, f)?;
             f.write_str(", ")?;
         }
         if let Some(ty) = last {
-            Display::fmt(ty, f)?;
+            fmt_field_ty(ty, f)?;
         }
         Ok(())
     }
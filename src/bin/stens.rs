@@ -0,0 +1,496 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `stens`: command-line tool for compiling and inspecting strict type libraries and for
+//! converting values to and from strict encoding.
+
+#[macro_use]
+extern crate amplify;
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::{env, fs};
+
+use amplify::confinement::{Confined, U24 as U24MAX, U32 as U32MAX};
+use amplify::hex::{FromHex, ToHex};
+use strict_encoding::{LibName, StrictDeserialize, StrictSerialize};
+use strict_types::decode::DecodeLimits;
+use strict_types::stl::std_stl;
+use strict_types::typify::{TypeSpec, TypedVal};
+use strict_types::{StlFormat, SymbolicLib, SymbolicSys, SystemBuilder, TypeLib, TypeLibId, Urn};
+
+const USAGE: &str = "\
+Usage: stens <COMMAND> [OPTIONS] [ARGS]
+
+Commands:
+  compile <LIB>           compile Vesper source (or convert a library) into the output format
+  inspect [LIB]           print a type library or, with `--system`, the whole type system
+  id <LIB> [ID]           print the library id, verifying it against the expected one
+  decode <TYPE> <DATA>    decode strict encoded data given as a hex string or a file
+  dump <TYPE> <DATA>      print hex dump of strict encoded data annotated with the type
+  encode <TYPE> <VALUE>   strict encode a value given as a string or a file
+  layout <TYPE>           print memory layout of a type in Vesper
+
+Options:
+  -l, --lib <FILE>        load a type library from `.sty`, `.stl` or `.sta` file; may be repeated
+  -o, --output <FILE>     write the result into a file instead of the standard output
+  -f, --format <FORMAT>   output library format: `stl` (default), `sta` or `sty`
+      --system            print the type system composed of all loaded libraries
+      --json              use JSON instead of STON for the values
+  -h, --help              print this help message
+  -V, --version           print the version
+
+Types are given by their fully qualified names (`Lib.Type`), optionally followed by the
+checkword of their semantic id (`Lib.Type#word-word-word`), or by semantic ids, their unique
+prefixes (`semid:9SJxLn7R`) or URNs (`urn:sten:id:...`). The `Std` library is always loaded. \
+                     Libraries are loaded in the order they are given, so Vesper sources can
+import only the libraries given before them.
+
+Exit status is 0 on success, 1 on failure and 2 on invalid command-line arguments.
+";
+
+#[derive(Clone, Eq, PartialEq, Debug, Display)]
+#[display(doc_comments)]
+enum Error {
+    /// {0}
+    ///
+    /// Try `stens --help` for more information.
+    Usage(String),
+
+    /// {0}
+    Failure(String),
+}
+
+impl Error {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Error::Usage(_) => ExitCode::from(2),
+            Error::Failure(_) => ExitCode::FAILURE,
+        }
+    }
+}
+
+macro_rules! usage {
+    ($($arg:tt)*) => { Error::Usage(format!($($arg)*)) };
+}
+
+macro_rules! failure {
+    ($($arg:tt)*) => { Error::Failure(format!($($arg)*)) };
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+struct Args {
+    command: String,
+    args: Vec<String>,
+    libs: Vec<PathBuf>,
+    output: Option<PathBuf>,
+    format: Option<StlFormat>,
+    system: bool,
+    json: bool,
+}
+
+impl Args {
+    /// Parses command-line arguments, returning `None` if help or version was requested.
+    fn parse(mut iter: impl Iterator<Item = String>) -> Result<Option<Args>, Error> {
+        let mut args = Args::default();
+        while let Some(arg) = iter.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, val)) if arg.starts_with("--") => {
+                    (name.to_owned(), Some(val.to_owned()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| iter.next())
+                    .ok_or_else(|| usage!("option `{name}` requires a value"))
+            };
+            match name.as_str() {
+                "-h" | "--help" => {
+                    print!("{USAGE}");
+                    return Ok(None);
+                }
+                "-V" | "--version" => {
+                    println!("stens {}", env!("CARGO_PKG_VERSION"));
+                    return Ok(None);
+                }
+                "-l" | "--lib" => args.libs.push(PathBuf::from(value()?)),
+                "-o" | "--output" => args.output = Some(PathBuf::from(value()?)),
+                "-f" | "--format" => {
+                    let format = value()?;
+                    args.format = Some(
+                        StlFormat::from_str(&format)
+                            .map_err(|_| usage!("unknown library format `{format}`"))?,
+                    );
+                }
+                "--system" => args.system = true,
+                "--json" => args.json = true,
+                "-" => args.args.push(arg),
+                _ if arg.starts_with('-') => return Err(usage!("unknown option `{arg}`")),
+                _ if args.command.is_empty() => args.command = arg,
+                _ => args.args.push(arg),
+            }
+        }
+        if args.command.is_empty() {
+            return Err(usage!("no command given"));
+        }
+        Ok(Some(args))
+    }
+
+    fn expect_args(&self, names: &[&str], optional: usize) -> Result<(), Error> {
+        let required = names.len() - optional;
+        match self.args.len() {
+            len if len < required => {
+                Err(usage!("command `{}` requires argument `{}`", self.command, names[len]))
+            }
+            len if len > names.len() => {
+                Err(usage!("unexpected argument `{}`", self.args[names.len()]))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn arg(&self, pos: usize) -> Option<&str> { self.args.get(pos).map(String::as_str) }
+
+    /// Loads the standard library and the libraries provided with `--lib` options.
+    fn load_libs(&self) -> Result<BTreeMap<LibName, TypeLib>, Error> {
+        let std = std_stl();
+        let mut libs = bmap! { std.name.clone() => std };
+        for path in &self.libs {
+            let lib = load_lib(path, &libs)?;
+            libs.insert(lib.name.clone(), lib);
+        }
+        Ok(libs)
+    }
+
+    fn load_sys(&self) -> Result<SymbolicSys, Error> { compose(self.load_libs()?.into_values()) }
+
+    fn write(&self, data: impl AsRef<[u8]>) -> Result<(), Error> {
+        match &self.output {
+            Some(path) => fs::write(path, data)
+                .map_err(|err| failure!("unable to write to '{}': {err}", path.display())),
+            None => io::stdout()
+                .write_all(data.as_ref())
+                .map_err(|err| failure!("unable to write to the standard output: {err}")),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, Error> {
+    fs::read(path).map_err(|err| failure!("unable to read '{}': {err}", path.display()))
+}
+
+fn read_text(path: &Path) -> Result<String, Error> {
+    String::from_utf8(read_file(path)?)
+        .map_err(|_| failure!("file '{}' is not a valid UTF-8 text", path.display()))
+}
+
+/// Loads type library, detecting its format from the file extension. Vesper sources are
+/// compiled using the already loaded libraries as dependencies.
+fn load_lib(path: &Path, libs: &BTreeMap<LibName, TypeLib>) -> Result<TypeLib, Error> {
+    let fail = |err: &dyn Display| failure!("invalid library '{}': {err}", path.display());
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("stl") => {
+            let data = Confined::try_from(read_file(path)?).map_err(|err| fail(&err))?;
+            TypeLib::from_strict_serialized::<U24MAX>(data).map_err(|err| fail(&err))
+        }
+        #[cfg(feature = "armor")]
+        Some("sta") => {
+            use armor::AsciiArmor;
+            TypeLib::from_ascii_armored_str(&read_text(path)?).map_err(|err| fail(&err))
+        }
+        #[cfg(not(feature = "armor"))]
+        Some("sta") => Err(failure!(
+            "reading armored library '{}' requires `stens` to be compiled with `armor` feature",
+            path.display()
+        )),
+        _ => {
+            let src = read_text(path)?;
+            let lib = SymbolicLib::from_source(&src, libs.values()).map_err(|err| fail(&err))?;
+            lib.compile().map_err(|err| fail(&err))
+        }
+    }
+}
+
+/// Composes type system out of the libraries.
+fn compose(libs: impl IntoIterator<Item = TypeLib>) -> Result<SymbolicSys, Error> {
+    let mut builder = SystemBuilder::new();
+    for lib in libs {
+        builder = builder.import(lib).map_err(|err| failure!("invalid type system: {err}"))?;
+    }
+    builder.finalize().map_err(|errors| {
+        let errors = errors.iter().map(|err| format!("\n- {err}")).collect::<String>();
+        failure!("invalid type system:{errors}")
+    })
+}
+
+/// Parses type specification given as a fully qualified type name with an optional checkword,
+/// a semantic id, its unique prefix or URN.
+fn parse_spec(s: &str) -> Result<TypeSpec, Error> {
+    TypeSpec::from_str(s).map_err(|err| usage!("{err}"))
+}
+
+/// Reads argument which may be either a path to an existing file or an inline value.
+fn file_or_value(arg: &str) -> Result<Option<Vec<u8>>, Error> {
+    let path = Path::new(arg);
+    if arg == "-" {
+        let mut data = vec![];
+        io::Read::read_to_end(&mut io::stdin(), &mut data)
+            .map_err(|err| failure!("unable to read the standard input: {err}"))?;
+        return Ok(Some(data));
+    }
+    if path.is_file() {
+        return read_file(path).map(Some);
+    }
+    Ok(None)
+}
+
+fn lib_source(lib: &TypeLib) -> Result<String, Error> {
+    let symbolic = lib
+        .to_symbolic()
+        .map_err(|err| failure!("library `{}` is inconsistent: {err}", lib.name))?;
+    Ok(format!("{{-\n  Id: {:+}\n  Name: {}\n-}}\n\n{symbolic}", lib.id(), lib.name))
+}
+
+/// Extracts library id from the header comment of a Vesper source.
+fn header_id(src: &str) -> Option<&str> {
+    let header = src.trim_start().strip_prefix("{-")?;
+    let (header, _) = header.split_once("-}")?;
+    header.lines().find_map(|line| line.trim().strip_prefix("Id:")).map(str::trim)
+}
+
+fn compile(args: &Args) -> Result<(), Error> {
+    args.expect_args(&["LIB"], 0)?;
+    let libs = args.load_libs()?;
+    let lib = load_lib(Path::new(&args.args[0]), &libs)?;
+    let ext = args.output.as_ref().and_then(|path| path.extension()).and_then(|ext| ext.to_str());
+    let format = match (args.format, ext) {
+        (Some(format), _) => format,
+        (None, Some(ext)) => StlFormat::from_str(ext).unwrap_or(StlFormat::Binary),
+        (None, None) => StlFormat::Binary,
+    };
+    let data = match format {
+        StlFormat::Binary => lib
+            .to_strict_serialized::<U24MAX>()
+            .map_err(|err| failure!("library `{}` is too large: {err}", lib.name))?
+            .release(),
+        #[cfg(feature = "armor")]
+        StlFormat::Armored => {
+            use armor::AsciiArmor;
+            lib.to_ascii_armored_string().into_bytes()
+        }
+        StlFormat::Source => lib_source(&lib)?.into_bytes(),
+    };
+    args.write(data)?;
+    if let Some(path) = &args.output {
+        eprintln!("Library `{}` with id {:+} written to '{}'", lib.name, lib.id(), path.display());
+    }
+    Ok(())
+}
+
+fn inspect(args: &Args) -> Result<(), Error> {
+    args.expect_args(&["LIB"], if args.system { 1 } else { 0 })?;
+    let mut libs = args.load_libs()?;
+    let lib = match args.arg(0) {
+        Some(path) => Some(load_lib(Path::new(path), &libs)?),
+        None => None,
+    };
+    if !args.system {
+        let lib = lib.expect("checked argument count");
+        return args.write(lib_source(&lib)?);
+    }
+    if let Some(lib) = lib {
+        libs.insert(lib.name.clone(), lib);
+    }
+    args.write(compose(libs.into_values())?.to_string())
+}
+
+fn id(args: &Args) -> Result<(), Error> {
+    args.expect_args(&["LIB", "ID"], 1)?;
+    let path = Path::new(&args.args[0]);
+    let lib = load_lib(path, &args.load_libs()?)?;
+    let id = lib.id();
+    args.write(format!("{id:+}\n"))?;
+
+    // Sources written by the compiler record the library id in their header
+    let src = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("stl" | "sta") => None,
+        _ => Some(read_text(path)?),
+    };
+    let expected = match (args.arg(1), src.as_deref().and_then(header_id)) {
+        (Some(expected), _) => Some(parse_lib_id(expected)?),
+        (None, Some(expected)) => Some(TypeLibId::from_str(expected).map_err(|err| {
+            failure!("invalid library id `{expected}` in '{}' header: {err}", path.display())
+        })?),
+        (None, None) => None,
+    };
+    match expected {
+        Some(expected) if expected != id => {
+            Err(failure!("library id {id:+} doesn't match the expected id {expected:+}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Parses library id given in Baid64, hex or as a URN.
+fn parse_lib_id(s: &str) -> Result<TypeLibId, Error> {
+    if let Ok(id) = TypeLibId::from_str(s).or_else(|_| TypeLibId::from_hex(s)) {
+        return Ok(id);
+    }
+    match Urn::from_str(s) {
+        Ok(Urn::Lib(id)) => Ok(id),
+        Ok(Urn::Type(_)) => Err(usage!("`{s}` is a type and not a library id")),
+        Err(err) => Err(usage!("invalid library id `{s}`: {err}")),
+    }
+}
+
+/// Reads strict encoded data given either as a file or as a hex string.
+fn file_or_hex(arg: &str) -> Result<Vec<u8>, Error> {
+    if let Some(data) = file_or_value(arg)? {
+        return Ok(data);
+    }
+    let hex = arg.trim();
+    Vec::<u8>::from_hex(hex.strip_prefix("0x").unwrap_or(hex))
+        .map_err(|_| usage!("`{arg}` is neither an existing file nor a hex string"))
+}
+
+fn decode(args: &Args) -> Result<(), Error> {
+    args.expect_args(&["TYPE", "DATA"], 0)?;
+    let spec = parse_spec(&args.args[0])?;
+    let data = file_or_hex(&args.args[1])?;
+    let sys = args.load_sys()?;
+    let val = sys
+        .strict_deserialize_type_limited(spec.clone(), &data, DecodeLimits::default())
+        .map_err(|err| failure!("unable to decode `{spec}`: {err}"))?;
+    args.write(format_val(args, &sys, &val)? + "\n")
+}
+
+fn dump(args: &Args) -> Result<(), Error> {
+    args.expect_args(&["TYPE", "DATA"], 0)?;
+    let spec = parse_spec(&args.args[0])?;
+    let data = file_or_hex(&args.args[1])?;
+    let sys = args.load_sys()?;
+    let dump = sys.hex_dump(spec.clone(), &data);
+    args.write(dump.to_string())?;
+    match dump.is_complete() {
+        true => Ok(()),
+        false => Err(failure!("data don't match `{spec}`")),
+    }
+}
+
+fn encode(args: &Args) -> Result<(), Error> {
+    args.expect_args(&["TYPE", "VALUE"], 0)?;
+    let spec = parse_spec(&args.args[0])?;
+    let src = match file_or_value(&args.args[1])? {
+        Some(data) => String::from_utf8(data)
+            .map_err(|_| failure!("value `{}` is not a valid UTF-8 text", args.args[1]))?,
+        None => args.args[1].clone(),
+    };
+    let sys = args.load_sys()?;
+    let val = parse_val(args, &sys, &src, spec.clone())?;
+    let data = sys
+        .as_types()
+        .strict_serialize_value::<U32MAX>(&val)
+        .map_err(|err| failure!("unable to encode `{spec}`: {err}"))?
+        .release();
+    match args.output {
+        Some(_) => args.write(data),
+        None => args.write(data.to_hex() + "\n"),
+    }
+}
+
+fn layout(args: &Args) -> Result<(), Error> {
+    args.expect_args(&["TYPE"], 0)?;
+    let spec = parse_spec(&args.args[0])?;
+    let sys = args.load_sys()?;
+    let sem_id = sys.resolve_spec(spec).map_err(|err| failure!("{err}"))?;
+    let tree = sys.type_tree(sem_id).ok_or_else(|| failure!("unknown type `{sem_id}`"))?;
+    args.write(tree.to_sized_vesper().display().to_string())
+}
+
+#[cfg(feature = "serde")]
+fn format_val(args: &Args, sys: &SymbolicSys, val: &TypedVal) -> Result<String, Error> {
+    if !args.json {
+        return Ok(val.as_val().to_string());
+    }
+    let json = sys.to_json(val).map_err(|err| failure!("{err}"))?;
+    Ok(serde_json::to_string_pretty(&json).expect("JSON values are always serializable"))
+}
+
+#[cfg(not(feature = "serde"))]
+fn format_val(args: &Args, _: &SymbolicSys, val: &TypedVal) -> Result<String, Error> {
+    if args.json {
+        return Err(usage!("JSON support requires `stens` to be compiled with `serde` feature"));
+    }
+    Ok(val.as_val().to_string())
+}
+
+#[cfg(feature = "serde")]
+fn parse_val(args: &Args, sys: &SymbolicSys, src: &str, spec: TypeSpec) -> Result<TypedVal, Error> {
+    if !args.json {
+        return sys.parse_ston(src, spec).map_err(|err| failure!("invalid value: {err}"));
+    }
+    let json = serde_json::from_str(src).map_err(|err| failure!("invalid JSON: {err}"))?;
+    sys.from_json(spec, &json).map_err(|err| failure!("invalid value: {err}"))
+}
+
+#[cfg(not(feature = "serde"))]
+fn parse_val(args: &Args, sys: &SymbolicSys, src: &str, spec: TypeSpec) -> Result<TypedVal, Error> {
+    if args.json {
+        return Err(usage!("JSON support requires `stens` to be compiled with `serde` feature"));
+    }
+    sys.parse_ston(src, spec).map_err(|err| failure!("invalid value: {err}"))
+}
+
+fn run() -> Result<(), Error> {
+    let Some(args) = Args::parse(env::args().skip(1))? else {
+        return Ok(());
+    };
+    if args.system && args.command != "inspect" {
+        return Err(usage!("option `--system` is supported only by `inspect` command"));
+    }
+    match args.command.as_str() {
+        "compile" => compile(&args),
+        "inspect" => inspect(&args),
+        "id" => id(&args),
+        "decode" => decode(&args),
+        "dump" => dump(&args),
+        "encode" => encode(&args),
+        "layout" => layout(&args),
+        cmd => Err(usage!("unknown command `{cmd}`")),
+    }
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            err.exit_code()
+        }
+    }
+}
@@ -0,0 +1,214 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of source code in other programming languages from strict type libraries.
+
+mod rust;
+mod typescript;
+
+use encoding::{LibName, TypeName};
+pub use rust::RustGen;
+pub use typescript::TsGen;
+
+use crate::typelib::{SymbolError, TranspileRef};
+use crate::typesys::TypeFqn;
+use crate::{SemId, SymbolicLib, SymbolicSys, Ty, TypeRef};
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum CodegenError {
+    /// library `{0}` is not a part of the type system.
+    UnknownLib(LibName),
+
+    /// type with semantic id `{0}` is not a part of the type system.
+    UnknownType(SemId),
+
+    /// type `{ty}` can't be represented in the generated code: {reason}.
+    Unsupported { ty: TypeName, reason: String },
+
+    #[from]
+    #[display(inner)]
+    Symbol(SymbolError),
+}
+
+impl CodegenError {
+    pub(crate) fn unsupported(ty: &TypeName, reason: impl ToString) -> Self {
+        CodegenError::Unsupported {
+            ty: ty.clone(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Type reference resolved in the context of the library for which the code is generated.
+pub(crate) enum Resolved<'a, Ref: TypeRef> {
+    Inline(&'a Ty<Ref>),
+    Local(&'a TypeName),
+    Extern(&'a LibName, &'a TypeName),
+}
+
+/// Source of the named types and their references for the code generators.
+pub(crate) trait TypeSource {
+    type Ref: TypeRef;
+
+    fn lib_name(&self) -> &LibName;
+    fn types(&self) -> Vec<(&TypeName, &Ty<Self::Ref>)>;
+    fn local(&self, name: &TypeName) -> Option<&Ty<Self::Ref>>;
+    fn resolve<'a>(&'a self, r: &'a Self::Ref) -> Result<Resolved<'a, Self::Ref>, CodegenError>;
+}
+
+impl TypeSource for SymbolicLib {
+    type Ref = TranspileRef;
+
+    fn lib_name(&self) -> &LibName { self.name() }
+
+    fn types(&self) -> Vec<(&TypeName, &Ty<Self::Ref>)> { self.types().iter().collect() }
+
+    fn local(&self, name: &TypeName) -> Option<&Ty<Self::Ref>> { self.types().get(name) }
+
+    fn resolve<'a>(&'a self, r: &'a Self::Ref) -> Result<Resolved<'a, Self::Ref>, CodegenError> {
+        Ok(match r {
+            TranspileRef::Embedded(ty) => Resolved::Inline(ty),
+            TranspileRef::Named(name) => Resolved::Local(name),
+            TranspileRef::Extern(ext) => Resolved::Extern(&ext.lib_name, &ext.ty_name),
+        })
+    }
+}
+
+/// Types of a single library inside a type system.
+pub(crate) struct SysLib<'sys> {
+    sys: &'sys SymbolicSys,
+    lib: LibName,
+}
+
+impl<'sys> SysLib<'sys> {
+    pub(crate) fn with(sys: &'sys SymbolicSys, lib: &LibName) -> Result<Self, CodegenError> {
+        let lib = lib.clone();
+        if !sys.as_symbols().iter().any(|sym| sym.fqn.as_ref().map(|fqn| &fqn.lib) == Some(&lib)) {
+            return Err(CodegenError::UnknownLib(lib));
+        }
+        Ok(SysLib { sys, lib })
+    }
+}
+
+impl TypeSource for SysLib<'_> {
+    type Ref = SemId;
+
+    fn lib_name(&self) -> &LibName { &self.lib }
+
+    fn types(&self) -> Vec<(&TypeName, &Ty<Self::Ref>)> {
+        let mut types = self
+            .sys
+            .as_symbols()
+            .iter()
+            .filter_map(|sym| {
+                let fqn = sym.fqn.as_ref()?;
+                if fqn.lib != self.lib {
+                    return None;
+                }
+                Some((&fqn.name, self.sys.as_types().get(sym.id)?))
+            })
+            .collect::<Vec<_>>();
+        types.sort_by_key(|(name, _)| *name);
+        types
+    }
+
+    fn local(&self, name: &TypeName) -> Option<&Ty<Self::Ref>> {
+        let id = self.sys.resolve(TypeFqn::with(self.lib.clone(), name.clone()))?;
+        self.sys.as_types().get(*id)
+    }
+
+    fn resolve<'a>(&'a self, r: &'a Self::Ref) -> Result<Resolved<'a, Self::Ref>, CodegenError> {
+        match self.sys.lookup(*r) {
+            Some(fqn) if fqn.lib == self.lib => Ok(Resolved::Local(&fqn.name)),
+            Some(fqn) => Ok(Resolved::Extern(&fqn.lib, &fqn.name)),
+            None => self
+                .sys
+                .as_types()
+                .get(*r)
+                .map(Resolved::Inline)
+                .ok_or(CodegenError::UnknownType(*r)),
+        }
+    }
+}
+
+/// Converts `camelCase` or `PascalCase` identifier into `snake_case`.
+pub(crate) fn snake_case(ident: &str) -> String {
+    let chars = ident.chars().collect::<Vec<_>>();
+    let mut s = String::with_capacity(ident.len() + 4);
+    for (pos, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && pos > 0 {
+            let prev = chars[pos - 1];
+            let next_lower = chars.get(pos + 1).map(char::is_ascii_lowercase).unwrap_or_default();
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                s.push('_');
+            }
+        }
+        s.push(c.to_ascii_lowercase());
+    }
+    s
+}
+
+/// Detects whether an identifier is a `camelCase` name which can be unambiguously restored from
+/// its `snake_case` or `PascalCase` form.
+pub(crate) fn is_plain_camel_case(ident: &str) -> bool {
+    let mut chars = ident.chars();
+    if !chars.next().map(|c| c.is_ascii_lowercase()).unwrap_or_default() {
+        return false;
+    }
+    let mut prev_upper = false;
+    for c in chars {
+        if c.is_ascii_uppercase() {
+            if prev_upper {
+                return false;
+            }
+            prev_upper = true;
+        } else if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            prev_upper = false;
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn case_conversion() {
+        assert_eq!(snake_case("semId"), "sem_id");
+        assert_eq!(snake_case("libId2Hash"), "lib_id2_hash");
+        assert_eq!(snake_case("RGBCommit"), "rgb_commit");
+        assert_eq!(snake_case("StrictTypes"), "strict_types");
+
+        assert!(is_plain_camel_case("semId"));
+        assert!(is_plain_camel_case("a1B2"));
+        assert!(!is_plain_camel_case("htlcID"));
+        assert!(!is_plain_camel_case("sem_id"));
+        assert!(!is_plain_camel_case("Sem"));
+    }
+}
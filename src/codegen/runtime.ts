@@ -0,0 +1,355 @@
+export class StrictError extends Error {}
+
+export type Encoder<T> = (w: StrictWriter, v: T) => void;
+export type Decoder<T> = (r: StrictReader) => T;
+export type Comparator<T> = (a: T, b: T) => number;
+
+const utf8Encoder = new TextEncoder();
+const utf8Decoder = new TextDecoder("utf-8", { fatal: true, ignoreBOM: true });
+
+function fail(msg: string): never {
+  throw new StrictError(msg);
+}
+
+function hasKey(obj: object, key: PropertyKey): boolean {
+  return Object.prototype.hasOwnProperty.call(obj, key);
+}
+
+function latin1(data: Uint8Array): string {
+  return Array.from(data, (byte) => String.fromCharCode(byte)).join("");
+}
+
+function checkLen(len: number, min: number, max: number): void {
+  if (len < min || len > max) {
+    fail(`collection length ${len} is out of bounds ${min}..=${max}`);
+  }
+}
+
+function checkChars(s: string, first: string, rest: string): void {
+  [...s].forEach((c, pos) => {
+    if (!(pos === 0 ? first : rest).includes(c)) {
+      fail(`character '${c}' is not allowed at position ${pos}`);
+    }
+  });
+}
+
+export class StrictWriter {
+  private buf: number[] = [];
+
+  toBytes(): Uint8Array {
+    return Uint8Array.from(this.buf);
+  }
+
+  raw(data: Uint8Array): void {
+    data.forEach((byte) => this.buf.push(byte));
+  }
+
+  unit(v: null): void {
+    if (v !== null) fail("unit value must be null");
+  }
+
+  int(v: number | bigint, len: number, signed: boolean, nonZero = false): void {
+    if (typeof v === "number" && !Number.isSafeInteger(v)) fail(`${v} is not an integer`);
+    const bits = BigInt(len * 8);
+    const min = signed ? -(1n << (bits - 1n)) : 0n;
+    const max = (signed ? 1n << (bits - 1n) : 1n << bits) - 1n;
+    let n = BigInt(v);
+    if (n < min || n > max) fail(`integer ${v} doesn't fit into ${len} byte(s)`);
+    if (nonZero && n === 0n) fail("zero value for a non-zero integer");
+    if (n < 0n) n += 1n << bits;
+    for (let i = 0; i < len; i++) {
+      this.buf.push(Number(n & 0xffn));
+      n >>= 8n;
+    }
+  }
+
+  f32(v: number): void {
+    const view = new DataView(new ArrayBuffer(4));
+    view.setFloat32(0, v, true);
+    this.raw(new Uint8Array(view.buffer));
+  }
+
+  f64(v: number): void {
+    const view = new DataView(new ArrayBuffer(8));
+    view.setFloat64(0, v, true);
+    this.raw(new Uint8Array(view.buffer));
+  }
+
+  char(v: string): void {
+    if ([...v].length !== 1) fail(`'${v}' is not a single character`);
+    this.raw(utf8Encoder.encode(v));
+  }
+
+  len(len: number, min: number, max: number, width: number): void {
+    checkLen(len, min, max);
+    this.int(len, width, false);
+  }
+
+  enum<T extends string>(v: T, tags: Record<T, number>): void {
+    if (!hasKey(tags, v)) fail(`unknown variant '${v}'`);
+    this.int(tags[v], 1, false);
+  }
+
+  option<T>(v: T | null, enc: Encoder<T>): void {
+    this.int(v === null ? 0 : 1, 1, false);
+    if (v !== null) enc(this, v);
+  }
+
+  bytes(v: Uint8Array, len: number): void {
+    if (v.length !== len) fail(`byte array must have length ${len} and not ${v.length}`);
+    this.raw(v);
+  }
+
+  blob(v: Uint8Array, min: number, max: number, width: number): void {
+    this.len(v.length, min, max, width);
+    this.raw(v);
+  }
+
+  string(v: string, min: number, max: number, width: number): void {
+    this.blob(utf8Encoder.encode(v), min, max, width);
+  }
+
+  ascii(v: string, min: number, max: number, width: number, chars: string): void {
+    checkChars(v, chars, chars);
+    this.blob(utf8Encoder.encode(v), min, max, width);
+  }
+
+  rstring(v: string, min: number, max: number, width: number, first: string, rest: string): void {
+    checkChars(v, first, rest);
+    this.blob(utf8Encoder.encode(v), min, max, width);
+  }
+
+  array<T>(v: T[], len: number, enc: Encoder<T>): void {
+    if (v.length !== len) fail(`array must have length ${len} and not ${v.length}`);
+    v.forEach((item) => enc(this, item));
+  }
+
+  list<T>(v: T[], min: number, max: number, width: number, enc: Encoder<T>): void {
+    this.len(v.length, min, max, width);
+    v.forEach((item) => enc(this, item));
+  }
+
+  set<T>(
+    v: T[],
+    min: number,
+    max: number,
+    width: number,
+    enc: Encoder<T>,
+    cmp: Comparator<T>,
+  ): void {
+    const sorted = [...v].sort(cmp);
+    sorted.forEach((item, pos) => {
+      if (pos > 0 && cmp(sorted[pos - 1], item) === 0) fail("repeated set element");
+    });
+    this.list(sorted, min, max, width, enc);
+  }
+
+  map<K, V>(
+    v: Array<[K, V]>,
+    min: number,
+    max: number,
+    width: number,
+    encKey: Encoder<K>,
+    encVal: Encoder<V>,
+    cmpKey: Comparator<K>,
+  ): void {
+    const sorted = [...v].sort((a, b) => cmpKey(a[0], b[0]));
+    sorted.forEach(([key], pos) => {
+      if (pos > 0 && cmpKey(sorted[pos - 1][0], key) === 0) fail("repeated map key");
+    });
+    this.len(sorted.length, min, max, width);
+    sorted.forEach(([key, val]) => {
+      encKey(this, key);
+      encVal(this, val);
+    });
+  }
+}
+
+export class StrictReader {
+  private readonly data: Uint8Array;
+  private pos = 0;
+
+  constructor(data: Uint8Array) {
+    this.data = data;
+  }
+
+  raw(len: number): Uint8Array {
+    if (this.pos + len > this.data.length) fail("unexpected end of data");
+    const slice = this.data.slice(this.pos, this.pos + len);
+    this.pos += len;
+    return slice;
+  }
+
+  end(): void {
+    if (this.pos !== this.data.length) fail("data are not entirely consumed");
+  }
+
+  unit(): null {
+    return null;
+  }
+
+  big(len: number, signed: boolean, nonZero = false): bigint {
+    const bytes = this.raw(len);
+    let n = 0n;
+    for (let i = len - 1; i >= 0; i--) n = (n << 8n) | BigInt(bytes[i]);
+    if (signed && bytes[len - 1] & 0x80) n -= 1n << BigInt(len * 8);
+    if (nonZero && n === 0n) fail("zero value for a non-zero integer");
+    return n;
+  }
+
+  int(len: number, signed: boolean, nonZero = false): number {
+    return Number(this.big(len, signed, nonZero));
+  }
+
+  f32(): number {
+    return new DataView(this.raw(4).buffer).getFloat32(0, true);
+  }
+
+  f64(): number {
+    return new DataView(this.raw(8).buffer).getFloat64(0, true);
+  }
+
+  char(): string {
+    const [first] = this.raw(1);
+    const ones = Math.clz32(~(first << 24));
+    const rest = this.raw(ones >= 2 && ones <= 4 ? ones - 1 : 0);
+    const s = this.utf8(Uint8Array.of(first, ...rest));
+    if ([...s].length !== 1) fail("invalid unicode character");
+    return s;
+  }
+
+  len(min: number, max: number, width: number): number {
+    const len = this.int(width, false);
+    checkLen(len, min, max);
+    return len;
+  }
+
+  enum<T extends string>(tags: Record<T, number>): T {
+    const tag = this.int(1, false);
+    const entry = (Object.entries(tags) as Array<[T, number]>).find(([, t]) => t === tag);
+    return entry ? entry[0] : fail(`unknown tag ${tag}`);
+  }
+
+  option<T>(dec: Decoder<T>): T | null {
+    const tag = this.int(1, false);
+    if (tag > 1) fail(`unknown option tag ${tag}`);
+    return tag === 0 ? null : dec(this);
+  }
+
+  bytes(len: number): Uint8Array {
+    return this.raw(len);
+  }
+
+  blob(min: number, max: number, width: number): Uint8Array {
+    return this.raw(this.len(min, max, width));
+  }
+
+  string(min: number, max: number, width: number): string {
+    return this.utf8(this.blob(min, max, width));
+  }
+
+  ascii(min: number, max: number, width: number, chars: string): string {
+    const s = latin1(this.blob(min, max, width));
+    checkChars(s, chars, chars);
+    return s;
+  }
+
+  rstring(min: number, max: number, width: number, first: string, rest: string): string {
+    const s = latin1(this.blob(min, max, width));
+    checkChars(s, first, rest);
+    return s;
+  }
+
+  array<T>(len: number, dec: Decoder<T>): T[] {
+    return Array.from({ length: len }, () => dec(this));
+  }
+
+  list<T>(min: number, max: number, width: number, dec: Decoder<T>): T[] {
+    return this.array(this.len(min, max, width), dec);
+  }
+
+  set<T>(min: number, max: number, width: number, dec: Decoder<T>, cmp: Comparator<T>): T[] {
+    const set = this.list(min, max, width, dec);
+    set.forEach((item, pos) => {
+      if (pos > 0 && cmp(set[pos - 1], item) >= 0) fail("set elements are repeated or unordered");
+    });
+    return set;
+  }
+
+  map<K, V>(
+    min: number,
+    max: number,
+    width: number,
+    decKey: Decoder<K>,
+    decVal: Decoder<V>,
+    cmpKey: Comparator<K>,
+  ): Array<[K, V]> {
+    const map = this.array(this.len(min, max, width), (r): [K, V] => [decKey(r), decVal(r)]);
+    map.forEach(([key], pos) => {
+      if (pos > 0 && cmpKey(map[pos - 1][0], key) >= 0) fail("map keys are repeated or unordered");
+    });
+    return map;
+  }
+
+  private utf8(data: Uint8Array): string {
+    try {
+      return utf8Decoder.decode(data);
+    } catch {
+      return fail("invalid UTF-8 string");
+    }
+  }
+}
+
+export function cmpNum(a: number | bigint, b: number | bigint): number {
+  return a < b ? -1 : a > b ? 1 : 0;
+}
+
+export function cmpBytes(a: Uint8Array, b: Uint8Array): number {
+  for (let i = 0; i < Math.min(a.length, b.length); i++) {
+    if (a[i] !== b[i]) return a[i] - b[i];
+  }
+  return a.length - b.length;
+}
+
+export function cmpStr(a: string, b: string): number {
+  return cmpBytes(utf8Encoder.encode(a), utf8Encoder.encode(b));
+}
+
+export function cmpTags<T extends string>(a: T, b: T, tags: Record<T, number>): number {
+  return tags[a] - tags[b];
+}
+
+export function cmpOption<T>(a: T | null, b: T | null, cmp: Comparator<T>): number {
+  if (a === null || b === null) return (a === null ? 0 : 1) - (b === null ? 0 : 1);
+  return cmp(a, b);
+}
+
+export function cmpList<T>(a: T[], b: T[], cmp: Comparator<T>): number {
+  for (let i = 0; i < Math.min(a.length, b.length); i++) {
+    const ord = cmp(a[i], b[i]);
+    if (ord !== 0) return ord;
+  }
+  return a.length - b.length;
+}
+
+export function cmpMap<K, V>(
+  a: Array<[K, V]>,
+  b: Array<[K, V]>,
+  cmpKey: Comparator<K>,
+  cmpVal: Comparator<V>,
+): number {
+  return cmpList(a, b, (x, y) => cmpKey(x[0], y[0]) || cmpVal(x[1], y[1]));
+}
+
+export function strictSerialize<T>(enc: Encoder<T>, v: T): Uint8Array {
+  const w = new StrictWriter();
+  enc(w, v);
+  return w.toBytes();
+}
+
+export function strictDeserialize<T>(dec: Decoder<T>, data: Uint8Array): T {
+  const r = new StrictReader(data);
+  const v = dec(r);
+  r.end();
+  return v;
+}
@@ -0,0 +1,842 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of Rust type definitions deriving strict encoding for a type library.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use encoding::{LibName, Primitive, Sizing, TypeName, LIB_NAME_STD};
+
+use super::{is_plain_camel_case, snake_case, CodegenError, Resolved, SysLib, TypeSource};
+use crate::ast::{NamedFields, UnnamedFields};
+use crate::{SymbolicLib, SymbolicSys, Ty, TypeLib};
+
+const KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+const MAX_WIDTH: usize = 100;
+
+const FLOATS: [Primitive; 6] = [
+    Primitive::F16,
+    Primitive::F32,
+    Primitive::F64,
+    Primitive::F80,
+    Primitive::F128,
+    Primitive::F256,
+];
+
+/// Generator of Rust source code for the types of a strict type library.
+///
+/// Each named type becomes a Rust structure or enum deriving `StrictType`, `StrictDumb`,
+/// `StrictEncode` and `StrictDecode`, such that compiling the generated code back into a library
+/// with [`crate::LibBuilder`] produces types with the same semantic ids. Collections are
+/// represented by the confined collection types of `amplify`, and restricted strings by
+/// [`crate::RString`] parametrized with the character enums from the `Std` library.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RustGen {
+    extern_paths: BTreeMap<LibName, String>,
+}
+
+impl Default for RustGen {
+    fn default() -> Self {
+        RustGen {
+            extern_paths: bmap! { libname!(LIB_NAME_STD) => s!("strict_encoding::stl") },
+        }
+    }
+}
+
+impl RustGen {
+    pub fn new() -> Self { Self::default() }
+
+    /// Sets the Rust module path providing types from an external library.
+    ///
+    /// By default, types of a library `LibName` are imported from `super::lib_name` module, and
+    /// types of the `Std` library - from `strict_encoding::stl`.
+    pub fn with_extern_path(mut self, lib: impl Into<LibName>, path: impl ToString) -> Self {
+        self.extern_paths.insert(lib.into(), path.to_string());
+        self
+    }
+
+    pub fn generate(&self, lib: &TypeLib) -> Result<String, CodegenError> {
+        self.generate_symbolic(&lib.to_symbolic()?)
+    }
+
+    pub fn generate_symbolic(&self, lib: &SymbolicLib) -> Result<String, CodegenError> {
+        RustWriter::new(self, lib).write()
+    }
+
+    /// Generates code for the types from library `lib` which are a part of a type system.
+    pub fn generate_sys(&self, sys: &SymbolicSys, lib: &LibName) -> Result<String, CodegenError> {
+        let src = SysLib::with(sys, lib)?;
+        RustWriter::new(self, &src).write()
+    }
+}
+
+#[derive(Copy, Clone, Default)]
+struct Props {
+    copy: bool,
+    float: bool,
+}
+
+impl Props {
+    fn join(self, other: Props) -> Props {
+        Props {
+            copy: self.copy && other.copy,
+            float: self.float || other.float,
+        }
+    }
+}
+
+enum Coll {
+    String,
+    Blob,
+    Vec(String),
+    Set(String),
+    Map(String, String),
+}
+
+struct RustWriter<'a, S: TypeSource> {
+    gen: &'a RustGen,
+    src: &'a S,
+    ty: TypeName,
+    imports: BTreeMap<String, BTreeSet<String>>,
+    props: BTreeMap<TypeName, Props>,
+    visiting: BTreeSet<TypeName>,
+}
+
+impl<'a, S: TypeSource> RustWriter<'a, S> {
+    fn new(gen: &'a RustGen, src: &'a S) -> Self {
+        RustWriter {
+            gen,
+            src,
+            ty: tn!("Dumb"),
+            imports: empty!(),
+            props: empty!(),
+            visiting: empty!(),
+        }
+    }
+
+    fn unsupported(&self, reason: impl ToString) -> CodegenError {
+        CodegenError::unsupported(&self.ty, reason)
+    }
+
+    fn import(&mut self, path: &str, item: impl ToString) {
+        self.imports.entry(path.to_owned()).or_default().insert(item.to_string());
+    }
+
+    fn write(mut self) -> Result<String, CodegenError> {
+        let src = self.src;
+        let lib_name = src.lib_name();
+        let lib_const = format!("LIB_NAME_{}", snake_case(lib_name.as_str()).to_uppercase());
+
+        let mut items = String::new();
+        for (name, ty) in src.types() {
+            self.ty = name.clone();
+            items.push('\n');
+            items.push_str(&self.named(name, ty, &lib_const)?);
+        }
+
+        for derive in ["StrictDecode", "StrictDumb", "StrictEncode", "StrictType"] {
+            self.import("strict_encoding", derive);
+        }
+        let mut groups = [String::new(), String::new(), String::new()];
+        for (path, items) in &self.imports {
+            let group = match path.split("::").next() {
+                Some("std" | "core" | "alloc") => 0,
+                Some("crate" | "super" | "self") => 2,
+                _ => 1,
+            };
+            let mut items = items.iter().map(String::as_str).collect::<Vec<_>>();
+            items.sort_by_key(|item| (item.starts_with(char::is_uppercase), *item));
+            let line = match items.as_slice() {
+                [item] => format!("use {path}::{item};\n"),
+                items => use_list(path, items),
+            };
+            groups[group].push_str(&line);
+        }
+
+        let mut code = format!(
+            "// Rust types for the strict type library `{lib_name}`.\n// Generated by \
+             strict_types; do not edit manually.\n\n"
+        );
+        for group in groups.iter().filter(|group| !group.is_empty()) {
+            code.push_str(group);
+            code.push('\n');
+        }
+        code.push_str(&format!("pub const {lib_const}: &str = \"{lib_name}\";\n"));
+        code.push_str(&items);
+        Ok(code)
+    }
+
+    fn named(
+        &mut self,
+        name: &'a TypeName,
+        ty: &'a Ty<S::Ref>,
+        lib_const: &str,
+    ) -> Result<String, CodegenError> {
+        let props = self.props_named(name)?;
+        let copy = if props.copy { "Copy, " } else { "" };
+        let mut code = if props.float {
+            format!("#[derive({copy}Clone, PartialOrd, PartialEq, Debug)]\n")
+        } else {
+            format!("#[derive({copy}Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]\n")
+        };
+
+        let mut item = String::new();
+        let mut dumb = None;
+        let mut tags = "";
+        match ty {
+            Ty::Tuple(fields) => {
+                item =
+                    format!("pub struct {name}({});\n", self.tuple_fields(fields, "pub ", false)?);
+                dumb = self.tuple_dumb(fields, "Self")?;
+            }
+            Ty::Struct(fields) => {
+                let fields_code = self.struct_fields(fields, "    pub ", false)?;
+                item = format!("pub struct {name} {{\n{fields_code}}}\n");
+                dumb = self.struct_dumb(fields, "Self")?;
+            }
+            Ty::Enum(variants) => {
+                tags = ", tags = repr, into_u8, try_from_u8";
+                item.push_str(&format!("#[repr(u8)]\npub enum {name} {{\n"));
+                for (pos, variant) in variants.iter().enumerate() {
+                    let mut attrs =
+                        self.variant_rename(variant.name.as_str()).into_iter().collect::<Vec<_>>();
+                    if pos == 0 {
+                        attrs.push(s!("dumb"));
+                    }
+                    item.push_str(&attr_line("    ", attrs));
+                    item.push_str(&format!(
+                        "    {} = {},\n",
+                        pascal_case(variant.name.as_str()),
+                        variant.tag
+                    ));
+                }
+                item.push_str("}\n");
+            }
+            Ty::Union(variants) => {
+                tags = ", tags = custom";
+                let src = self.src;
+                let mut cases = Vec::with_capacity(variants.len());
+                for (variant, r) in variants.iter() {
+                    match src.resolve(r)? {
+                        Resolved::Inline(ty @ (Ty::Tuple(_) | Ty::Struct(_))) => {
+                            cases.push((variant, Some(ty)))
+                        }
+                        Resolved::Inline(Ty::Primitive(prim)) if *prim == Primitive::UNIT => {
+                            cases.push((variant, None))
+                        }
+                        _ => {
+                            return Err(self.unsupported(format_args!(
+                                "union variant `{}` must be a unit, a tuple or a structure",
+                                variant.name
+                            )));
+                        }
+                    }
+                }
+                // Unit variants are the only ones which can be marked as dumb with the derive
+                let unit_dumb = cases.iter().position(|(_, ty)| ty.is_none());
+
+                item.push_str(&format!("pub enum {name} {{\n"));
+                for (pos, (variant, content)) in cases.into_iter().enumerate() {
+                    let var_name = pascal_case(variant.name.as_str());
+                    let mut attrs = vec![format!("tag = {}", variant.tag)];
+                    attrs.extend(self.variant_rename(variant.name.as_str()));
+                    if unit_dumb == Some(pos) {
+                        attrs.push(s!("dumb"));
+                    }
+                    item.push_str(&attr_line("    ", attrs));
+                    let path = format!("Self::{var_name}");
+                    match content {
+                        None => item.push_str(&format!("    {var_name},\n")),
+                        Some(Ty::Tuple(fields)) => {
+                            let fields_code = self.tuple_fields(fields, "", true)?;
+                            item.push_str(&format!("    {var_name}({fields_code}),\n"));
+                            if unit_dumb.is_none() && pos == 0 {
+                                dumb = Some(self.tuple_dumb(fields, &path)?.unwrap_or_else(|| {
+                                    self.default_dumb(&path, fields.len(), "(", ")")
+                                }));
+                            }
+                        }
+                        Some(Ty::Struct(fields)) => {
+                            let fields_code = self.struct_fields(fields, "        ", true)?;
+                            item.push_str(&format!("    {var_name} {{\n{fields_code}    }},\n"));
+                            if unit_dumb.is_none() && pos == 0 {
+                                dumb = Some(match self.struct_dumb(fields, &path)? {
+                                    Some(dumb) => dumb,
+                                    None => self.struct_default_dumb(fields, &path),
+                                });
+                            }
+                        }
+                        Some(_) => unreachable!("filtered above"),
+                    }
+                }
+                item.push_str("}\n");
+            }
+            _ => {
+                return Err(self
+                    .unsupported("named types must be tuples, structures, enumerations or unions"))
+            }
+        }
+
+        // Types with no derivable dumb value get it implemented manually
+        code.push_str(if dumb.is_some() {
+            "#[derive(StrictType, StrictEncode, StrictDecode)]\n"
+        } else {
+            "#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]\n"
+        });
+        code.push_str(&format!("#[strict_type(lib = {lib_const}{tags})]\n"));
+        code.push_str(&item);
+        if let Some(dumb) = dumb {
+            code.push_str(&format!(
+                "\nimpl StrictDumb for {name} {{\n    fn strict_dumb() -> Self {{\n        \
+                 {dumb}\n    }}\n}}\n"
+            ));
+        }
+        Ok(code)
+    }
+
+    fn tuple_fields(
+        &mut self,
+        fields: &'a UnnamedFields<S::Ref>,
+        vis: &str,
+        nested: bool,
+    ) -> Result<String, CodegenError> {
+        let mut items = Vec::with_capacity(fields.len());
+        for r in fields.iter() {
+            let ty = if nested { self.nested_ref(r)? } else { self.type_ref(r)? };
+            items.push(format!("{vis}{ty}"));
+        }
+        Ok(items.join(", "))
+    }
+
+    fn struct_fields(
+        &mut self,
+        fields: &'a NamedFields<S::Ref>,
+        prefix: &str,
+        nested: bool,
+    ) -> Result<String, CodegenError> {
+        let indent = prefix.trim_end_matches("pub ");
+        let mut code = String::new();
+        for field in fields.iter() {
+            let (ident, renamed) = field_ident(field.name.as_str());
+            if renamed {
+                code.push_str(&attr_line(indent, vec![format!("rename = \"{}\"", field.name)]));
+            }
+            let ty = if nested { self.nested_ref(&field.ty)? } else { self.type_ref(&field.ty)? };
+            code.push_str(&format!("{prefix}{ident}: {ty},\n"));
+        }
+        Ok(code)
+    }
+
+    /// Constructs dumb value for a tuple if some of its fields can't provide it via `Default`.
+    fn tuple_dumb(
+        &mut self,
+        fields: &'a UnnamedFields<S::Ref>,
+        path: &str,
+    ) -> Result<Option<String>, CodegenError> {
+        let mut custom = false;
+        let mut items = Vec::with_capacity(fields.len());
+        for r in fields.iter() {
+            let dumb = self.dumb(r)?;
+            custom |= dumb.is_some();
+            items.push(dumb.unwrap_or_else(|| s!("strict_dumb!()")));
+        }
+        Ok(custom.then(|| format!("{path}({})", items.join(", "))))
+    }
+
+    /// Constructs dumb value for a structure if some of its fields can't provide it via
+    /// `Default`.
+    fn struct_dumb(
+        &mut self,
+        fields: &'a NamedFields<S::Ref>,
+        path: &str,
+    ) -> Result<Option<String>, CodegenError> {
+        let mut custom = false;
+        let mut items = Vec::with_capacity(fields.len());
+        for field in fields.iter() {
+            let dumb = self.dumb(&field.ty)?;
+            custom |= dumb.is_some();
+            let (ident, _) = field_ident(field.name.as_str());
+            items.push(format!("{ident}: {}", dumb.unwrap_or_else(|| s!("strict_dumb!()"))));
+        }
+        Ok(custom.then(|| struct_expr(path, items)))
+    }
+
+    fn default_dumb(&mut self, path: &str, count: usize, open: &str, close: &str) -> String {
+        self.import("strict_encoding", "strict_dumb");
+        format!("{path}{open}{}{close}", vec!["strict_dumb!()"; count].join(", "))
+    }
+
+    fn struct_default_dumb(&mut self, fields: &'a NamedFields<S::Ref>, path: &str) -> String {
+        self.import("strict_encoding", "strict_dumb");
+        let items = fields
+            .iter()
+            .map(|field| format!("{}: strict_dumb!()", field_ident(field.name.as_str()).0))
+            .collect();
+        struct_expr(path, items)
+    }
+
+    fn variant_rename(&self, name: &str) -> Option<String> {
+        (!is_plain_camel_case(name)).then(|| format!("rename = \"{name}\""))
+    }
+
+    /// Constructs an expression for a dumb value of the types which don't provide it via
+    /// `Default` implementation.
+    fn dumb(&mut self, r: &'a S::Ref) -> Result<Option<String>, CodegenError> {
+        let src = self.src;
+        let Resolved::Inline(ty) = src.resolve(r)? else {
+            return Ok(None);
+        };
+        let min = match ty {
+            Ty::Array(item, len) => {
+                let prim = matches!(src.resolve(item)?, Resolved::Inline(Ty::Primitive(_)));
+                if prim && *len <= 32 {
+                    return Ok(None);
+                }
+                self.import("strict_encoding", "strict_dumb");
+                return Ok(Some(format!("[strict_dumb!(); {len}]")));
+            }
+            Ty::List(_, sizing) | Ty::Set(_, sizing) | Ty::Map(_, _, sizing) if sizing.min > 0 => {
+                sizing.min
+            }
+            _ => return Ok(None),
+        };
+        self.import("amplify::confinement", "Confined");
+        let dumb = match ty {
+            Ty::List(item, _) => match src.resolve(item)? {
+                Resolved::Inline(Ty::UnicodeChar) => {
+                    format!("Confined::from_checked(\" \".repeat({min}))")
+                }
+                Resolved::Inline(Ty::Primitive(prim)) if *prim == Primitive::BYTE => {
+                    format!("Confined::from_checked(vec![0u8; {min}])")
+                }
+                _ if min == 1 => s!("Confined::from_iter_checked([strict_dumb!()])"),
+                _ => format!(
+                    "Confined::from_iter_checked(std::iter::repeat_with(|| \
+                     strict_dumb!()).take({min}))"
+                ),
+            },
+            Ty::Set(..) if min == 1 => s!("Confined::from_iter_checked([strict_dumb!()])"),
+            Ty::Map(..) if min == 1 => {
+                s!("Confined::from_iter_checked([(strict_dumb!(), strict_dumb!())])")
+            }
+            _ => {
+                return Err(self.unsupported(
+                    "sets and maps requiring more than a single element have no dumb value",
+                ))
+            }
+        };
+        self.import("strict_encoding", "strict_dumb");
+        Ok(Some(dumb))
+    }
+
+    fn type_ref(&mut self, r: &'a S::Ref) -> Result<String, CodegenError> {
+        let src = self.src;
+        match src.resolve(r)? {
+            Resolved::Inline(ty) => self.inline(ty),
+            Resolved::Local(name) => Ok(name.to_string()),
+            Resolved::Extern(lib, name) => {
+                let path = match self.gen.extern_paths.get(lib) {
+                    Some(path) => path.clone(),
+                    None => format!("super::{}", snake_case(lib.as_str())),
+                };
+                self.import(&path, name);
+                Ok(name.to_string())
+            }
+        }
+    }
+
+    /// Type which has to provide a dumb value on its own, since strict encoding requires it for
+    /// union variant fields, optional values and collection items.
+    fn nested_ref(&mut self, r: &'a S::Ref) -> Result<String, CodegenError> {
+        if self.dumb(r)?.is_some() {
+            return Err(self.unsupported(
+                "non-empty collections and large arrays are allowed only as fields of named \
+                 tuples and structures",
+            ));
+        }
+        self.type_ref(r)
+    }
+
+    /// Type of array and list items, where `Byte` is represented with `u8`.
+    fn item(&mut self, r: &'a S::Ref) -> Result<String, CodegenError> {
+        let src = self.src;
+        match src.resolve(r)? {
+            Resolved::Inline(Ty::Primitive(prim)) if *prim == Primitive::BYTE => Ok(s!("u8")),
+            Resolved::Inline(Ty::Primitive(prim)) if *prim == Primitive::U8 => {
+                Err(self.unsupported("arrays and lists of `U8` are always encoded as bytes"))
+            }
+            _ => self.nested_ref(r),
+        }
+    }
+
+    /// Detects types implementing `Default`, which is required for inline tuple fields.
+    fn is_default(&mut self, r: &'a S::Ref) -> Result<bool, CodegenError> {
+        let src = self.src;
+        let Resolved::Inline(ty) = src.resolve(r)? else {
+            return Ok(false);
+        };
+        Ok(match ty {
+            Ty::Primitive(_) => true,
+            Ty::Union(_) => ty.as_some().is_some(),
+            Ty::Array(..) | Ty::List(..) | Ty::Set(..) | Ty::Map(..) => self.dumb(r)?.is_none(),
+            _ => false,
+        })
+    }
+
+    fn inline(&mut self, ty: &'a Ty<S::Ref>) -> Result<String, CodegenError> {
+        let src = self.src;
+        match ty {
+            Ty::Primitive(prim) => self.primitive(*prim),
+            Ty::UnicodeChar => Err(self.unsupported("unicode character outside of a string")),
+            Ty::Enum(_) => Err(self.unsupported("inline enumerations")),
+            Ty::Struct(_) => Err(self.unsupported("inline structures")),
+            Ty::Union(_) => {
+                let inner = ty
+                    .as_some()
+                    .map(|some| src.resolve(some))
+                    .transpose()?
+                    .and_then(|some| match some {
+                        Resolved::Inline(Ty::Tuple(fields)) if fields.len() == 1 => fields.first(),
+                        _ => None,
+                    })
+                    .ok_or_else(|| self.unsupported("inline unions other than optional values"))?;
+                Ok(format!("Option<{}>", self.nested_ref(inner)?))
+            }
+            Ty::Tuple(fields) => {
+                if let Some(rstring) = self.rstring(fields)? {
+                    return Ok(rstring);
+                }
+                if !(2..=3).contains(&fields.len()) {
+                    return Err(self.unsupported("inline tuples must have two or three fields"));
+                }
+                for r in fields.iter() {
+                    if !self.is_default(r)? {
+                        return Err(self.unsupported(
+                            "inline tuples may contain only primitives, optional values and \
+                             possibly empty collections",
+                        ));
+                    }
+                }
+                Ok(format!("({})", self.tuple_fields(fields, "", false)?))
+            }
+            Ty::Array(item, len) => {
+                if !self.props_ref(item)?.copy {
+                    return Err(self.unsupported("array items must be copyable"));
+                }
+                Ok(format!("[{}; {len}]", self.item(item)?))
+            }
+            Ty::List(item, sizing) => {
+                let coll = match src.resolve(item)? {
+                    Resolved::Inline(Ty::UnicodeChar) => Coll::String,
+                    Resolved::Inline(Ty::Primitive(prim)) if *prim == Primitive::BYTE => Coll::Blob,
+                    _ => Coll::Vec(self.item(item)?),
+                };
+                Ok(self.confined(coll, *sizing))
+            }
+            Ty::Set(item, sizing) => {
+                let item = self.nested_ref(item)?;
+                Ok(self.confined(Coll::Set(item), *sizing))
+            }
+            Ty::Map(key, val, sizing) => {
+                let key = self.nested_ref(key)?;
+                let val = self.nested_ref(val)?;
+                Ok(self.confined(Coll::Map(key, val), *sizing))
+            }
+        }
+    }
+
+    fn primitive(&mut self, prim: Primitive) -> Result<String, CodegenError> {
+        let (path, name) = match prim {
+            Primitive::UNIT => return Ok(s!("()")),
+            Primitive::U8 => return Ok(s!("u8")),
+            Primitive::U16 => return Ok(s!("u16")),
+            Primitive::U32 => return Ok(s!("u32")),
+            Primitive::U64 => return Ok(s!("u64")),
+            Primitive::U128 => return Ok(s!("u128")),
+            Primitive::I8 => return Ok(s!("i8")),
+            Primitive::I16 => return Ok(s!("i16")),
+            Primitive::I32 => return Ok(s!("i32")),
+            Primitive::I64 => return Ok(s!("i64")),
+            Primitive::I128 => return Ok(s!("i128")),
+            Primitive::BYTE => ("strict_encoding", "Byte"),
+            Primitive::U24 => ("amplify::num", "u24"),
+            Primitive::U40 => ("amplify::num", "u40"),
+            Primitive::U48 => ("amplify::num", "u48"),
+            Primitive::U56 => ("amplify::num", "u56"),
+            Primitive::U256 => ("amplify::num", "u256"),
+            Primitive::U512 => ("amplify::num", "u512"),
+            Primitive::U1024 => ("amplify::num", "u1024"),
+            Primitive::I256 => ("amplify::num", "i256"),
+            Primitive::I512 => ("amplify::num", "i512"),
+            Primitive::I1024 => ("amplify::num", "i1024"),
+            Primitive::N8 => ("std::num", "NonZeroU8"),
+            Primitive::N16 => ("std::num", "NonZeroU16"),
+            float if FLOATS.contains(&float) => {
+                self.import("amplify::num::apfloat", "ieee");
+                let name = match float.byte_size() {
+                    2 => "Half",
+                    4 => "Single",
+                    8 => "Double",
+                    10 => "X87DoubleExtended",
+                    16 => "Quad",
+                    _ => "Oct",
+                };
+                return Ok(format!("ieee::{name}"));
+            }
+            _ => {
+                return Err(self.unsupported(format_args!(
+                    "primitive type `{prim}` has no strict encoding in Rust"
+                )))
+            }
+        };
+        self.import(path, name);
+        Ok(name.to_owned())
+    }
+
+    /// Detects restricted strings made of `Std` library characters.
+    fn rstring(
+        &mut self,
+        fields: &'a UnnamedFields<S::Ref>,
+    ) -> Result<Option<String>, CodegenError> {
+        let src = self.src;
+        let std = libname!(LIB_NAME_STD);
+        let [first, rest] = fields.as_slice() else {
+            return Ok(None);
+        };
+        let (Resolved::Extern(first_lib, _), Resolved::Inline(Ty::List(item, sizing))) =
+            (src.resolve(first)?, src.resolve(rest)?)
+        else {
+            return Ok(None);
+        };
+        if *first_lib != std
+            || !matches!(src.resolve(item)?, Resolved::Extern(lib, _) if *lib == std)
+        {
+            return Ok(None);
+        }
+        self.import("strict_encoding", "RString");
+        let first = self.type_ref(first)?;
+        let item = self.type_ref(item)?;
+        Ok(Some(format!(
+            "RString<{first}, {item}, {}, {}>",
+            sizing.min + 1,
+            sizing.max.saturating_add(1)
+        )))
+    }
+
+    fn confined(&mut self, coll: Coll, sizing: Sizing) -> String {
+        let prefix = match (sizing.min, sizing.max) {
+            (0, 0xFF) => Some("Tiny"),
+            (0, 0xFFFF) => Some("Small"),
+            (0, 0xFF_FFFF) => Some("Medium"),
+            (0, 0xFFFF_FFFF) => Some("Large"),
+            _ => None,
+        };
+        let (alias, params, inner) = match coll {
+            Coll::String => ("String", s!(""), s!("String")),
+            Coll::Blob => ("Blob", s!(""), s!("Vec<u8>")),
+            Coll::Vec(item) => ("Vec", format!("<{item}>"), format!("Vec<{item}>")),
+            Coll::Set(item) => ("OrdSet", format!("<{item}>"), format!("BTreeSet<{item}>")),
+            Coll::Map(key, val) => {
+                ("OrdMap", format!("<{key}, {val}>"), format!("BTreeMap<{key}, {val}>"))
+            }
+        };
+        if let Some(prefix) = prefix {
+            let alias = format!("{prefix}{alias}");
+            self.import("amplify::confinement", &alias);
+            return format!("{alias}{params}");
+        }
+        self.import("amplify::confinement", "Confined");
+        if inner.starts_with("BTree") {
+            self.import("std::collections", &inner[..inner.find('<').unwrap_or(inner.len())]);
+        }
+        format!("Confined<{inner}, {}, {}>", sizing.min, sizing.max)
+    }
+
+    fn props_named(&mut self, name: &'a TypeName) -> Result<Props, CodegenError> {
+        if let Some(props) = self.props.get(name) {
+            return Ok(*props);
+        }
+        let src = self.src;
+        let ty = src
+            .local(name)
+            .ok_or_else(|| CodegenError::unsupported(name, "type is absent from the library"))?;
+        if !self.visiting.insert(name.clone()) {
+            return Err(CodegenError::unsupported(
+                name,
+                "recursive types can't be represented with derived strict encoding",
+            ));
+        }
+        let props = self.props(ty)?;
+        self.visiting.remove(name);
+        self.props.insert(name.clone(), props);
+        Ok(props)
+    }
+
+    fn props_ref(&mut self, r: &'a S::Ref) -> Result<Props, CodegenError> {
+        let src = self.src;
+        Ok(match src.resolve(r)? {
+            Resolved::Inline(ty) => self.props(ty)?,
+            Resolved::Local(name) => self.props_named(name)?,
+            // Types from the standard library are all character enumerations
+            Resolved::Extern(lib, _) => Props {
+                copy: lib.as_str() == LIB_NAME_STD,
+                float: false,
+            },
+        })
+    }
+
+    fn props(&mut self, ty: &'a Ty<S::Ref>) -> Result<Props, CodegenError> {
+        let mut props = Props {
+            copy: true,
+            float: false,
+        };
+        match ty {
+            Ty::Primitive(prim) => props.float = FLOATS.contains(prim),
+            Ty::UnicodeChar | Ty::Enum(_) => {}
+            Ty::Union(variants) => {
+                for r in variants.values() {
+                    props = props.join(self.props_ref(r)?);
+                }
+            }
+            Ty::Tuple(fields) => {
+                for r in fields.iter() {
+                    props = props.join(self.props_ref(r)?);
+                }
+            }
+            Ty::Struct(fields) => {
+                for field in fields.iter() {
+                    props = props.join(self.props_ref(&field.ty)?);
+                }
+            }
+            Ty::Array(item, _) => props = self.props_ref(item)?,
+            Ty::List(item, _) | Ty::Set(item, _) => {
+                props.copy = false;
+                props.float = self.props_ref(item)?.float;
+            }
+            Ty::Map(key, val, _) => {
+                props.copy = false;
+                props.float = self.props_ref(key)?.float || self.props_ref(val)?.float;
+            }
+        }
+        Ok(props)
+    }
+}
+
+/// Formats structure construction expression inside the body of `StrictDumb::strict_dumb`.
+fn struct_expr(path: &str, fields: Vec<String>) -> String {
+    let mut code = format!("{path} {{\n");
+    for field in fields {
+        code.push_str(&format!("            {field},\n"));
+    }
+    code.push_str("        }");
+    code
+}
+
+fn attr_line(indent: &str, attrs: Vec<String>) -> String {
+    if attrs.is_empty() {
+        return s!("");
+    }
+    format!("{indent}#[strict_type({})]\n", attrs.join(", "))
+}
+
+/// Formats a multi-item `use` statement, wrapping it the same way as `rustfmt` does.
+fn use_list(path: &str, items: &[&str]) -> String {
+    let line = format!("use {path}::{{{}}};\n", items.join(", "));
+    if line.len() <= MAX_WIDTH + 1 {
+        return line;
+    }
+    let mut code = format!("use {path}::{{\n");
+    let mut row = String::new();
+    for item in items {
+        if !row.is_empty() && row.len() + item.len() + 2 > MAX_WIDTH {
+            code.push_str(row.trim_end());
+            code.push('\n');
+            row.clear();
+        }
+        if row.is_empty() {
+            row.push_str("    ");
+        }
+        row.push_str(item);
+        row.push_str(", ");
+    }
+    code.push_str(row.trim_end());
+    code.push_str("\n};\n");
+    code
+}
+
+fn pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// Returns Rust identifier for a field and whether the field has to be renamed for the derive
+/// macros to restore the original name.
+fn field_ident(name: &str) -> (String, bool) {
+    let ident = snake_case(name);
+    match ident.as_str() {
+        "self" | "super" | "crate" => (format!("{ident}_"), true),
+        kw if KEYWORDS.contains(&kw) => (format!("r#{ident}"), true),
+        _ => (ident, !is_plain_camel_case(name)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn gen(src: &str) -> Result<String, CodegenError> {
+        RustGen::new().generate_symbolic(&SymbolicLib::from_str(src).unwrap())
+    }
+
+    #[test]
+    fn floats() {
+        let code = gen("typelib Test\ndata Point : x F32, y F64\n").unwrap();
+        assert!(code.contains("use amplify::num::apfloat::ieee;\n"));
+        assert!(code.contains("#[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]\n"));
+        assert!(code.contains("    pub x: ieee::Single,\n    pub y: ieee::Double,\n"));
+    }
+
+    #[test]
+    fn unsupported() {
+        let err = |src: &str| match gen(&format!("typelib Test\n{src}\n")).unwrap_err() {
+            CodegenError::Unsupported { ty, .. } => ty,
+            err => panic!("unexpected error {err}"),
+        };
+        assert_eq!(err("data Rec : data U8, next Rec?"), tn!("Rec"));
+        assert_eq!(err("data Bytes : [U8]"), tn!("Bytes"));
+        assert_eq!(err("data Int : I24"), tn!("Int"));
+        assert_eq!(err("data Sum : a (U8) | b {[Byte] ^ 1..0xff}"), tn!("Sum"));
+        assert_eq!(err("data Pairs : [(U8, [Byte ^ 1..0xff])]"), tn!("Pairs"));
+    }
+}
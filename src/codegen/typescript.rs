@@ -0,0 +1,781 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of TypeScript types and strict encoding codecs for a type system.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use encoding::{NumCls, Primitive, Sizing, TypeName};
+
+use super::CodegenError;
+use crate::ast::{EnumVariants, NamedFields, UnionVariants, UnnamedFields};
+use crate::util::SizingExt;
+use crate::{SemId, SymbolicSys, Ty};
+
+const RUNTIME: &str = include_str!("runtime.ts");
+
+/// Names of the runtime and built-in TypeScript types, which are never used for the generated
+/// types.
+const RESERVED: [&str; 9] = [
+    "Array",
+    "Comparator",
+    "Decoder",
+    "Encoder",
+    "Record",
+    "StrictError",
+    "StrictReader",
+    "StrictWriter",
+    "Uint8Array",
+];
+
+/// Generator of TypeScript source code for the named types of a type system.
+///
+/// Each named type is represented by a TypeScript type together with `encodeX`, `decodeX` and
+/// `compareX` functions, which write and read the value using exactly the same strict encoding as
+/// [`crate::TypeSystem::strict_write_value`] and [`crate::TypeSystem::strict_read_type`]. The
+/// comparison function follows the order in which strict encoding requires set elements and map
+/// keys to be serialized.
+///
+/// Integers up to 32 bits are represented with `number`, larger ones - with `bigint`; byte
+/// strings and byte arrays - with `Uint8Array`; unicode and ASCII strings - with `string`;
+/// optional values - with `T | null`; sets - with arrays and maps - with arrays of key-value
+/// tuples. Enumerations are unions of variant names, and unions are objects with `type` field
+/// containing variant name and `value` field containing variant data.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct TsGen {
+    runtime_path: Option<String>,
+}
+
+impl TsGen {
+    pub fn new() -> Self { Self::default() }
+
+    /// Makes the generated code to import the runtime from the given module instead of including
+    /// it into the generated source. The source of the module is provided by [`TsGen::runtime`].
+    pub fn with_runtime_import(mut self, path: impl ToString) -> Self {
+        self.runtime_path = Some(path.to_string());
+        self
+    }
+
+    /// TypeScript runtime used by the generated code for reading and writing strict encoded
+    /// data.
+    pub fn runtime() -> &'static str { RUNTIME }
+
+    pub fn generate(&self, sys: &SymbolicSys) -> Result<String, CodegenError> {
+        TsWriter::new(sys).write(self.runtime_path.as_deref())
+    }
+}
+
+struct TsWriter<'a> {
+    sys: &'a SymbolicSys,
+    names: BTreeMap<SemId, String>,
+    ty: TypeName,
+    imports: BTreeSet<&'static str>,
+}
+
+impl<'a> TsWriter<'a> {
+    fn new(sys: &'a SymbolicSys) -> Self {
+        let mut counts = BTreeMap::<&TypeName, usize>::new();
+        for fqn in sys.as_symbols().iter().filter_map(|sym| sym.fqn.as_ref()) {
+            *counts.entry(&fqn.name).or_default() += 1;
+        }
+        let names = sys
+            .as_symbols()
+            .iter()
+            .filter_map(|sym| {
+                let fqn = sym.fqn.as_ref()?;
+                let name = fqn.name.as_str();
+                // Names present in multiple libraries are qualified with the library name
+                let name = if counts[&fqn.name] > 1 || RESERVED.contains(&name) {
+                    format!("{}{name}", fqn.lib)
+                } else {
+                    name.to_owned()
+                };
+                Some((sym.id, name))
+            })
+            .collect();
+        TsWriter {
+            sys,
+            names,
+            ty: tn!("Dumb"),
+            imports: empty!(),
+        }
+    }
+
+    fn unsupported(&self, reason: impl ToString) -> CodegenError {
+        CodegenError::unsupported(&self.ty, reason)
+    }
+
+    fn use_rt(&mut self, name: &'static str) -> &'static str {
+        self.imports.insert(name);
+        name
+    }
+
+    fn get(&self, id: SemId) -> Result<&'a Ty<SemId>, CodegenError> {
+        self.sys.as_types().get(id).ok_or(CodegenError::UnknownType(id))
+    }
+
+    fn write(mut self, runtime_path: Option<&str>) -> Result<String, CodegenError> {
+        let mut symbols = self
+            .sys
+            .as_symbols()
+            .iter()
+            .filter_map(|sym| Some((sym.fqn.as_ref()?, sym.id)))
+            .collect::<Vec<_>>();
+        symbols.sort();
+
+        let mut items = String::new();
+        for (fqn, id) in symbols {
+            self.ty = fqn.name.clone();
+            let name = self.names[&id].clone();
+            let ty = self.get(id)?;
+            items.push_str(&format!("\n/** Type `{fqn}` with semantic id `{id}`. */\n"));
+            items.push_str(&self.named(&name, ty)?);
+        }
+
+        let mut code = format!(
+            "// TypeScript types for the strict type system `{}`.\n// Generated by strict_types; \
+             do not edit manually.\n\n",
+            self.sys.id()
+        );
+        match runtime_path {
+            None => code.push_str(RUNTIME),
+            Some(path) => {
+                self.imports.extend(["StrictReader", "StrictWriter"]);
+                let imports = self.imports.iter().copied().collect::<Vec<_>>();
+                code.push_str(&format!("import {{ {} }} from \"{path}\";\n", imports.join(", ")));
+            }
+        }
+        code.push_str(&items);
+        Ok(code)
+    }
+
+    fn named(&mut self, name: &str, ty: &'a Ty<SemId>) -> Result<String, CodegenError> {
+        let (decl, enc, dec, cmp) = match ty {
+            Ty::Enum(variants) => return Ok(self.named_enum(name, variants)),
+            Ty::Union(variants) if !ty.is_option() => return self.named_union(name, variants),
+            Ty::Struct(fields) => self.named_struct(name, fields)?,
+            Ty::Tuple(fields) if fields.len() > 1 && self.rstring(fields)?.is_none() => {
+                let types =
+                    fields.iter().map(|id| self.ts_type(*id)).collect::<Result<Vec<_>, _>>()?;
+                let mut enc = String::new();
+                let mut dec = Vec::with_capacity(fields.len());
+                let mut cmp = Vec::with_capacity(fields.len());
+                for (pos, id) in fields.iter().enumerate() {
+                    enc.push_str(&self.enc_stmts(*id, &format!("v[{pos}]"), "  ")?);
+                    dec.push(self.dec(*id)?);
+                    cmp.push(self.cmp(*id, &format!("a[{pos}]"), &format!("b[{pos}]"))?);
+                }
+                (
+                    format!("export type {name} = [{}];\n", types.join(", ")),
+                    enc,
+                    format!("  return [{}];\n", dec.join(", ")),
+                    chain(cmp),
+                )
+            }
+            _ => (
+                format!("export type {name} = {};\n", self.ts_inline(ty)?),
+                self.enc_stmts_inline(ty, "v", "  ")?,
+                format!("  return {};\n", self.dec_inline(ty)?),
+                format!("  return {};\n", self.cmp_inline(ty, "a", "b")?),
+            ),
+        };
+        Ok(format!(
+            "{decl}\nexport function encode{name}(w: StrictWriter, v: {name}): void \
+             {{\n{enc}}}\n\nexport function decode{name}(r: StrictReader): {name} \
+             {{\n{dec}}}\n\nexport function compare{name}(a: {name}, b: {name}): number \
+             {{\n{cmp}}}\n"
+        ))
+    }
+
+    fn named_struct(
+        &mut self,
+        name: &str,
+        fields: &'a NamedFields<SemId>,
+    ) -> Result<(String, String, String, String), CodegenError> {
+        let mut decl = format!("export interface {name} {{\n");
+        let mut enc = String::new();
+        let mut dec = s!("  return {\n");
+        let mut cmp = Vec::with_capacity(fields.len());
+        for field in fields {
+            let fname = &field.name;
+            decl.push_str(&format!("  {fname}: {};\n", self.ts_type(field.ty)?));
+            enc.push_str(&self.enc_stmts(field.ty, &format!("v.{fname}"), "  ")?);
+            dec.push_str(&format!("    {fname}: {},\n", self.dec(field.ty)?));
+            cmp.push(self.cmp(field.ty, &format!("a.{fname}"), &format!("b.{fname}"))?);
+        }
+        decl.push_str("}\n");
+        dec.push_str("  };\n");
+        let cmp = chain(cmp);
+        Ok((decl, enc, dec, cmp))
+    }
+
+    fn named_enum(&mut self, name: &str, variants: &EnumVariants) -> String {
+        let cmp_tags = self.use_rt("cmpTags");
+        let mut code = format!("export type {name} =\n");
+        let mut tags = format!("const {name}Tags: Record<{name}, number> = {{\n");
+        for variant in variants {
+            code.push_str(&format!("  | \"{}\"\n", variant.name));
+            tags.push_str(&format!("  {}: {},\n", variant.name, variant.tag));
+        }
+        code.pop();
+        code.push_str(";\n\n");
+        code.push_str(&tags);
+        code.push_str(&format!(
+            "}};\n\nexport function encode{name}(w: StrictWriter, v: {name}): void {{\n  \
+             w.enum(v, {name}Tags);\n}}\n\nexport function decode{name}(r: StrictReader): {name} \
+             {{\n  return r.enum({name}Tags);\n}}\n\nexport function compare{name}(a: {name}, b: \
+             {name}): number {{\n  return {cmp_tags}(a, b, {name}Tags);\n}}\n"
+        ));
+        code
+    }
+
+    fn named_union(
+        &mut self,
+        name: &str,
+        variants: &'a UnionVariants<SemId>,
+    ) -> Result<String, CodegenError> {
+        let cmp_tags = self.use_rt("cmpTags");
+        let mut code = format!("export type {name} =\n");
+        let mut tags = format!("const {name}Tags: Record<{name}[\"type\"], number> = {{\n");
+        let mut enc = String::new();
+        let mut dec = String::new();
+        let mut cmp = String::new();
+        for (variant, id) in variants {
+            let vname = &variant.name;
+            tags.push_str(&format!("  {vname}: {},\n", variant.tag));
+            if self.get(*id)? == &Ty::UNIT {
+                code.push_str(&format!("  | {{ type: \"{vname}\" }}\n"));
+                dec.push_str(&format!("    case \"{vname}\":\n      return {{ type }};\n"));
+                continue;
+            }
+            code.push_str(&format!("  | {{ type: \"{vname}\"; value: {} }}\n", self.ts_type(*id)?));
+            enc.push_str(&format!(
+                "    case \"{vname}\":\n{}      break;\n",
+                self.enc_stmts(*id, "v.value", "      ")?
+            ));
+            dec.push_str(&format!(
+                "    case \"{vname}\":\n      return {{ type, value: {} }};\n",
+                self.dec(*id)?
+            ));
+            cmp.push_str(&format!(
+                "  if (a.type === \"{vname}\" && b.type === \"{vname}\") {{\n    return {};\n  \
+                 }}\n",
+                self.cmp(*id, "a.value", "b.value")?
+            ));
+        }
+        code.pop();
+        code.push_str(";\n\n");
+        code.push_str(&tags);
+        code.push_str(&format!(
+            "}};\n\nexport function encode{name}(w: StrictWriter, v: {name}): void {{\n  \
+             w.enum(v.type, {name}Tags);\n"
+        ));
+        if !enc.is_empty() {
+            code.push_str(&format!("  switch (v.type) {{\n{enc}  }}\n"));
+        }
+        code.push_str(&format!(
+            "}}\n\nexport function decode{name}(r: StrictReader): {name} {{\n  const type = \
+             r.enum({name}Tags);\n  switch (type) {{\n{dec}  }}\n}}\n\nexport function \
+             compare{name}(a: {name}, b: {name}): number {{\n{cmp}  return {cmp_tags}(a.type, \
+             b.type, {name}Tags);\n}}\n"
+        ));
+        Ok(code)
+    }
+
+    fn ts_type(&mut self, id: SemId) -> Result<String, CodegenError> {
+        if let Some(name) = self.names.get(&id) {
+            return Ok(name.clone());
+        }
+        self.ts_inline(self.get(id)?)
+    }
+
+    fn ts_inline(&mut self, ty: &'a Ty<SemId>) -> Result<String, CodegenError> {
+        Ok(match ty {
+            Ty::Primitive(prim) => match self.num(*prim)? {
+                Num::Unit => s!("null"),
+                Num::Float(_) => s!("number"),
+                Num::Int { len, .. } if len <= 4 => s!("number"),
+                Num::Int { .. } => s!("bigint"),
+            },
+            Ty::UnicodeChar => s!("string"),
+            Ty::Enum(variants) => variants
+                .iter()
+                .map(|variant| format!("\"{}\"", variant.name))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Ty::Union(_) => format!("{} | null", self.ts_type(self.some(ty)?)?),
+            Ty::Tuple(fields) => match self.rstring(fields)? {
+                Some(_) => s!("string"),
+                None if fields.len() == 1 => self.ts_type(fields[0])?,
+                None => {
+                    let types =
+                        fields.iter().map(|id| self.ts_type(*id)).collect::<Result<Vec<_>, _>>()?;
+                    format!("[{}]", types.join(", "))
+                }
+            },
+            Ty::Struct(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                for field in fields {
+                    items.push(format!("{}: {}", field.name, self.ts_type(field.ty)?));
+                }
+                format!("{{ {} }}", items.join("; "))
+            }
+            Ty::Array(id, _) | Ty::List(id, _) => match self.item(*id)? {
+                Item::Byte => s!("Uint8Array"),
+                Item::Char if matches!(ty, Ty::List(..)) => s!("string"),
+                Item::Ascii(_) if matches!(ty, Ty::List(..)) => s!("string"),
+                _ => format!("Array<{}>", self.ts_type(*id)?),
+            },
+            Ty::Set(id, _) => format!("Array<{}>", self.ts_type(*id)?),
+            Ty::Map(key, val, _) => {
+                format!("Array<[{}, {}]>", self.ts_type(*key)?, self.ts_type(*val)?)
+            }
+        })
+    }
+
+    /// Encodes value with one statement per field of inline tuples and structures.
+    fn enc_stmts(&mut self, id: SemId, v: &str, indent: &str) -> Result<String, CodegenError> {
+        if self.names.contains_key(&id) {
+            return Ok(format!("{indent}{};\n", self.enc(id, v)?));
+        }
+        self.enc_stmts_inline(self.get(id)?, v, indent)
+    }
+
+    fn enc_stmts_inline(
+        &mut self,
+        ty: &'a Ty<SemId>,
+        v: &str,
+        indent: &str,
+    ) -> Result<String, CodegenError> {
+        let mut code = String::new();
+        match ty {
+            Ty::Tuple(fields) if fields.len() > 1 && self.rstring(fields)?.is_none() => {
+                for (pos, id) in fields.iter().enumerate() {
+                    code.push_str(&self.enc_stmts(*id, &format!("{v}[{pos}]"), indent)?);
+                }
+            }
+            Ty::Struct(fields) => {
+                for field in fields {
+                    code.push_str(&self.enc_stmts(
+                        field.ty,
+                        &format!("{v}.{}", field.name),
+                        indent,
+                    )?);
+                }
+            }
+            _ => code = format!("{indent}{};\n", self.enc_inline(ty, v)?),
+        }
+        Ok(code)
+    }
+
+    fn enc(&mut self, id: SemId, v: &str) -> Result<String, CodegenError> {
+        if let Some(name) = self.names.get(&id) {
+            return Ok(format!("encode{name}(w, {v})"));
+        }
+        self.enc_inline(self.get(id)?, v)
+    }
+
+    fn enc_fn(&mut self, id: SemId) -> Result<String, CodegenError> {
+        if let Some(name) = self.names.get(&id) {
+            return Ok(format!("encode{name}"));
+        }
+        Ok(format!("(w, v) => {}", self.enc_inline(self.get(id)?, "v")?))
+    }
+
+    fn enc_inline(&mut self, ty: &'a Ty<SemId>, v: &str) -> Result<String, CodegenError> {
+        Ok(match ty {
+            Ty::Primitive(prim) => match self.num(*prim)? {
+                Num::Unit => format!("w.unit({v})"),
+                Num::Float(len) => format!("w.f{}({v})", len * 8),
+                Num::Int {
+                    len,
+                    signed,
+                    non_zero: false,
+                } => format!("w.int({v}, {len}, {signed})"),
+                Num::Int { len, signed, .. } => format!("w.int({v}, {len}, {signed}, true)"),
+            },
+            Ty::UnicodeChar => format!("w.char({v})"),
+            Ty::Enum(variants) => format!("w.enum({v}, {})", tags(variants)),
+            Ty::Union(_) => format!("w.option({v}, {})", self.enc_fn(self.some(ty)?)?),
+            Ty::Tuple(fields) => match self.rstring(fields)? {
+                Some((sizing, first, rest)) => {
+                    format!("w.rstring({v}, {}, \"{first}\", \"{rest}\")", bounds(sizing))
+                }
+                None if fields.len() == 1 => self.enc(fields[0], v)?,
+                None => {
+                    let mut items = Vec::with_capacity(fields.len());
+                    for (pos, id) in fields.iter().enumerate() {
+                        items.push(self.enc(*id, &format!("{v}[{pos}]"))?);
+                    }
+                    group(items, ", ")
+                }
+            },
+            Ty::Struct(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                for field in fields {
+                    items.push(self.enc(field.ty, &format!("{v}.{}", field.name))?);
+                }
+                group(items, ", ")
+            }
+            Ty::Array(id, len) => match self.item(*id)? {
+                Item::Byte => format!("w.bytes({v}, {len})"),
+                _ => format!("w.array({v}, {len}, {})", self.enc_fn(*id)?),
+            },
+            Ty::List(id, sizing) => match self.item(*id)? {
+                Item::Byte => format!("w.blob({v}, {})", bounds(*sizing)),
+                Item::Char => format!("w.string({v}, {})", bounds(*sizing)),
+                Item::Ascii(chars) => format!("w.ascii({v}, {}, \"{chars}\")", bounds(*sizing)),
+                Item::Other => format!("w.list({v}, {}, {})", bounds(*sizing), self.enc_fn(*id)?),
+            },
+            Ty::Set(id, sizing) => format!(
+                "w.set({v}, {}, {}, {})",
+                bounds(*sizing),
+                self.enc_fn(*id)?,
+                self.cmp_fn(*id)?
+            ),
+            Ty::Map(key, val, sizing) => format!(
+                "w.map({v}, {}, {}, {}, {})",
+                bounds(*sizing),
+                self.enc_fn(*key)?,
+                self.enc_fn(*val)?,
+                self.cmp_fn(*key)?
+            ),
+        })
+    }
+
+    fn dec(&mut self, id: SemId) -> Result<String, CodegenError> {
+        if let Some(name) = self.names.get(&id) {
+            return Ok(format!("decode{name}(r)"));
+        }
+        self.dec_inline(self.get(id)?)
+    }
+
+    fn dec_fn(&mut self, id: SemId) -> Result<String, CodegenError> {
+        if let Some(name) = self.names.get(&id) {
+            return Ok(format!("decode{name}"));
+        }
+        let ty = self.get(id)?;
+        // Explicit return type prevents inference of arrays in place of tuples
+        Ok(format!("(r): {} => {}", self.ts_inline(ty)?, self.dec_inline(ty)?))
+    }
+
+    fn dec_inline(&mut self, ty: &'a Ty<SemId>) -> Result<String, CodegenError> {
+        Ok(match ty {
+            Ty::Primitive(prim) => match self.num(*prim)? {
+                Num::Unit => s!("r.unit()"),
+                Num::Float(len) => format!("r.f{}()", len * 8),
+                Num::Int {
+                    len,
+                    signed,
+                    non_zero,
+                } => {
+                    let method = if len <= 4 { "int" } else { "big" };
+                    let non_zero = if non_zero { ", true" } else { "" };
+                    format!("r.{method}({len}, {signed}{non_zero})")
+                }
+            },
+            Ty::UnicodeChar => s!("r.char()"),
+            Ty::Enum(variants) => format!("r.enum({})", tags(variants)),
+            Ty::Union(_) => format!("r.option({})", self.dec_fn(self.some(ty)?)?),
+            Ty::Tuple(fields) => match self.rstring(fields)? {
+                Some((sizing, first, rest)) => {
+                    format!("r.rstring({}, \"{first}\", \"{rest}\")", bounds(sizing))
+                }
+                None if fields.len() == 1 => self.dec(fields[0])?,
+                None => {
+                    let items =
+                        fields.iter().map(|id| self.dec(*id)).collect::<Result<Vec<_>, _>>()?;
+                    format!("[{}]", items.join(", "))
+                }
+            },
+            Ty::Struct(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                for field in fields {
+                    items.push(format!("{}: {}", field.name, self.dec(field.ty)?));
+                }
+                format!("{{ {} }}", items.join(", "))
+            }
+            Ty::Array(id, len) => match self.item(*id)? {
+                Item::Byte => format!("r.bytes({len})"),
+                _ => format!("r.array({len}, {})", self.dec_fn(*id)?),
+            },
+            Ty::List(id, sizing) => match self.item(*id)? {
+                Item::Byte => format!("r.blob({})", bounds(*sizing)),
+                Item::Char => format!("r.string({})", bounds(*sizing)),
+                Item::Ascii(chars) => format!("r.ascii({}, \"{chars}\")", bounds(*sizing)),
+                Item::Other => format!("r.list({}, {})", bounds(*sizing), self.dec_fn(*id)?),
+            },
+            Ty::Set(id, sizing) => {
+                format!("r.set({}, {}, {})", bounds(*sizing), self.dec_fn(*id)?, self.cmp_fn(*id)?)
+            }
+            Ty::Map(key, val, sizing) => format!(
+                "r.map({}, {}, {}, {})",
+                bounds(*sizing),
+                self.dec_fn(*key)?,
+                self.dec_fn(*val)?,
+                self.cmp_fn(*key)?
+            ),
+        })
+    }
+
+    fn cmp(&mut self, id: SemId, a: &str, b: &str) -> Result<String, CodegenError> {
+        if let Some(name) = self.names.get(&id) {
+            return Ok(format!("compare{name}({a}, {b})"));
+        }
+        self.cmp_inline(self.get(id)?, a, b)
+    }
+
+    fn cmp_fn(&mut self, id: SemId) -> Result<String, CodegenError> {
+        if let Some(name) = self.names.get(&id) {
+            return Ok(format!("compare{name}"));
+        }
+        let cmp = self.cmp_inline(self.get(id)?, "a", "b")?;
+        // Runtime comparators are used directly
+        match cmp.strip_suffix("(a, b)") {
+            Some(name) if name.chars().all(char::is_alphanumeric) => Ok(name.to_owned()),
+            _ => Ok(format!("(a, b) => {cmp}")),
+        }
+    }
+
+    fn cmp_inline(&mut self, ty: &'a Ty<SemId>, a: &str, b: &str) -> Result<String, CodegenError> {
+        Ok(match ty {
+            Ty::Primitive(prim) => match self.num(*prim)? {
+                Num::Unit => s!("0"),
+                Num::Float(_) | Num::Int { .. } => format!("{}({a}, {b})", self.use_rt("cmpNum")),
+            },
+            Ty::UnicodeChar => format!("{}({a}, {b})", self.use_rt("cmpStr")),
+            Ty::Enum(variants) => {
+                format!("{}({a}, {b}, {})", self.use_rt("cmpTags"), tags(variants))
+            }
+            Ty::Union(_) => {
+                let cmp = self.cmp_fn(self.some(ty)?)?;
+                format!("{}({a}, {b}, {cmp})", self.use_rt("cmpOption"))
+            }
+            Ty::Tuple(fields) => match self.rstring(fields)? {
+                Some(_) => format!("{}({a}, {b})", self.use_rt("cmpStr")),
+                None if fields.len() == 1 => self.cmp(fields[0], a, b)?,
+                None => {
+                    let mut items = Vec::with_capacity(fields.len());
+                    for (pos, id) in fields.iter().enumerate() {
+                        items.push(self.cmp(
+                            *id,
+                            &format!("{a}[{pos}]"),
+                            &format!("{b}[{pos}]"),
+                        )?);
+                    }
+                    group(items, " || ")
+                }
+            },
+            Ty::Struct(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                for field in fields {
+                    let name = &field.name;
+                    items.push(self.cmp(
+                        field.ty,
+                        &format!("{a}.{name}"),
+                        &format!("{b}.{name}"),
+                    )?);
+                }
+                group(items, " || ")
+            }
+            Ty::Array(id, _) | Ty::List(id, _) => match self.item(*id)? {
+                Item::Byte => format!("{}({a}, {b})", self.use_rt("cmpBytes")),
+                Item::Char | Item::Ascii(_) if matches!(ty, Ty::List(..)) => {
+                    format!("{}({a}, {b})", self.use_rt("cmpStr"))
+                }
+                _ => {
+                    let cmp = self.cmp_fn(*id)?;
+                    format!("{}({a}, {b}, {cmp})", self.use_rt("cmpList"))
+                }
+            },
+            Ty::Set(id, _) => {
+                let cmp = self.cmp_fn(*id)?;
+                format!("{}({a}, {b}, {cmp})", self.use_rt("cmpList"))
+            }
+            Ty::Map(key, val, _) => {
+                let cmp_key = self.cmp_fn(*key)?;
+                let cmp_val = self.cmp_fn(*val)?;
+                format!("{}({a}, {b}, {cmp_key}, {cmp_val})", self.use_rt("cmpMap"))
+            }
+        })
+    }
+
+    fn num(&self, prim: Primitive) -> Result<Num, CodegenError> {
+        if prim == Primitive::UNIT {
+            return Ok(Num::Unit);
+        }
+        let info = prim.info();
+        let len = prim.byte_size();
+        match info.ty {
+            _ if prim == Primitive::BYTE => Ok(Num::Int {
+                len: 1,
+                signed: false,
+                non_zero: false,
+            }),
+            NumCls::Float if prim == Primitive::F32 || prim == Primitive::F64 => {
+                Ok(Num::Float(len))
+            }
+            NumCls::Float => Err(self.unsupported(format_args!(
+                "float primitive with code {:#04x} has no TypeScript representation",
+                prim.into_code()
+            ))),
+            _ if len == 0 || len > 128 => Err(self.unsupported(format_args!(
+                "unknown primitive with code {:#04x}",
+                prim.into_code()
+            ))),
+            cls => Ok(Num::Int {
+                len,
+                signed: cls == NumCls::Signed,
+                non_zero: cls == NumCls::NonZero,
+            }),
+        }
+    }
+
+    fn some(&self, ty: &Ty<SemId>) -> Result<SemId, CodegenError> {
+        ty.as_some()
+            .copied()
+            .ok_or_else(|| self.unsupported("inline unions other than optional values"))
+    }
+
+    fn item(&self, id: SemId) -> Result<Item, CodegenError> {
+        Ok(match self.get(id)? {
+            ty if ty.is_byte() => Item::Byte,
+            Ty::UnicodeChar => Item::Char,
+            ty @ Ty::Enum(variants) if ty.is_char_enum() => Item::Ascii(chars(variants)),
+            _ => Item::Other,
+        })
+    }
+
+    /// Detects restricted strings, returning their sizing and allowed characters.
+    fn rstring(
+        &self,
+        fields: &UnnamedFields<SemId>,
+    ) -> Result<Option<(Sizing, String, String)>, CodegenError> {
+        let [first, rest] = fields.as_slice() else {
+            return Ok(None);
+        };
+        let (Ty::Enum(first), Ty::List(rest, sizing)) = (self.get(*first)?, self.get(*rest)?)
+        else {
+            return Ok(None);
+        };
+        let Item::Ascii(rest) = self.item(*rest)? else {
+            return Ok(None);
+        };
+        if !first.iter().all(|variant| (32..=127).contains(&variant.tag)) {
+            return Ok(None);
+        }
+        let sizing = Sizing::new(sizing.min + 1, sizing.max.saturating_add(1));
+        Ok(Some((sizing, chars(first), rest)))
+    }
+}
+
+enum Num {
+    Unit,
+    Float(u16),
+    Int {
+        len: u16,
+        signed: bool,
+        non_zero: bool,
+    },
+}
+
+enum Item {
+    Byte,
+    Char,
+    Ascii(String),
+    Other,
+}
+
+/// Formats return statement for a chain of field comparisons, one comparison per line.
+fn chain(items: Vec<String>) -> String {
+    match items.len() {
+        0 => s!("  return 0;\n"),
+        1 => format!("  return {};\n", items[0]),
+        _ => format!("  return (\n    {}\n  );\n", items.join(" ||\n    ")),
+    }
+}
+
+/// Joins expressions, wrapping them into parentheses if there is more than one.
+fn group(items: Vec<String>, sep: &str) -> String {
+    match items.len() {
+        1 => items.join(sep),
+        _ => format!("({})", items.join(sep)),
+    }
+}
+
+/// Formats collection size limits and the byte width of its length prefix.
+fn bounds(sizing: Sizing) -> String {
+    format!("{}, {}, {}", sizing.min, sizing.max, sizing.byte_size())
+}
+
+fn tags(variants: &EnumVariants) -> String {
+    let tags = variants
+        .iter()
+        .map(|variant| format!("{}: {}", variant.name, variant.tag))
+        .collect::<Vec<_>>();
+    format!("{{ {} }}", tags.join(", "))
+}
+
+/// Constructs TypeScript string literal content listing characters of a character enum.
+fn chars(variants: &EnumVariants) -> String {
+    let mut s = String::with_capacity(variants.len());
+    for variant in variants {
+        match variant.tag {
+            b'"' => s.push_str("\\\""),
+            b'\\' => s.push_str("\\\\"),
+            0x7F => s.push_str("\\x7f"),
+            tag => s.push(tag as char),
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{SymbolicLib, SystemBuilder};
+
+    fn gen(src: &str) -> Result<String, CodegenError> {
+        let lib = SymbolicLib::from_str(&format!("typelib Test\n{src}\n")).unwrap();
+        let sys = SystemBuilder::new().import(lib.compile().unwrap()).unwrap().finalize().unwrap();
+        TsGen::new().with_runtime_import("./runtime").generate(&sys)
+    }
+
+    #[test]
+    fn reserved_names() {
+        let code = gen("data Array : [U16]\ndata Holder : items Array").unwrap();
+        assert!(code.contains("export type TestArray = Array<number>;\n"));
+        assert!(code.contains("export interface Holder {\n  items: TestArray;\n}\n"));
+    }
+
+    #[test]
+    fn unsupported() {
+        let err = |src: &str| match gen(src).unwrap_err() {
+            CodegenError::Unsupported { ty, .. } => ty,
+            err => panic!("unexpected error {err}"),
+        };
+        assert_eq!(err("data Half : F16"), tn!("Half"));
+        assert_eq!(err("data Quad : F128"), tn!("Quad"));
+    }
+}
@@ -20,8 +20,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::{fmt, iter};
 
 use amplify::confinement::LargeVec;
 use strict_encoding::STRICT_TYPES_LIB;
@@ -58,42 +58,53 @@ impl Display for MemoryLayout {
     }
 }
 
+impl<'sys> TypeTree<'sys> {
+    /// Constructs Vesper representation of the type layout, annotating each item with the bounds
+    /// of its encoded size.
+    pub fn to_sized_vesper(&'sys self) -> TypeVesper {
+        let mut iter = self.iter();
+        build_vesper(
+            iter::from_fn(|| iter.next_sized())
+                .map(|(item, size)| (item.depth, item.to_vesper(size))),
+        )
+    }
+}
+
 impl MemoryLayout {
     fn new() -> Self { Self { items: empty!() } }
 
     pub fn to_vesper(&self) -> TypeVesper {
-        let mut root = None;
-        let mut path: Vec<usize> = vec![];
-        for item in &self.items {
-            let expr = item.to_vesper();
-            let depth = item.depth;
+        build_vesper(self.items.iter().map(|item| (item.depth, item.to_vesper(None))))
+    }
+}
 
-            if path.is_empty() && depth == 0 {
-                debug_assert_eq!(root, None);
-                root = Some(expr);
-                continue;
-            }
+fn build_vesper(items: impl IntoIterator<Item = (u32, TypeVesper)>) -> TypeVesper {
+    let mut root = None;
+    let mut path: Vec<usize> = vec![];
+    for (depth, expr) in items {
+        if path.is_empty() && depth == 0 {
+            debug_assert_eq!(root, None);
+            root = Some(expr);
+            continue;
+        }
 
-            debug_assert!(depth > 0);
-            // if the stack top is the same depth or deeper:
-            // - remove everything down from the depth
-            // - take the remaining top and add the item as a new child
-            // - create new item and push it to stack
-            if path.len() >= depth as usize {
-                let _ = path.split_off(depth as usize - 1);
-            }
-            // if the stack top is one level up
-            // - create new item and add it as a child to the stack top item
-            // - push the newly created item to stack
-            let mut head = root.as_mut().expect("already set");
-            for el in &path {
-                head = head.content.get_mut(*el).expect("algorithm inconsistency");
-            }
-            path.push(head.content.len());
-            head.content
-                .push(Box::new(expr))
-                .expect("invalid type layout containing too much items");
+        debug_assert!(depth > 0);
+        // if the stack top is the same depth or deeper:
+        // - remove everything down from the depth
+        // - take the remaining top and add the item as a new child
+        // - create new item and push it to stack
+        if path.len() >= depth as usize {
+            let _ = path.split_off(depth as usize - 1);
+        }
+        // if the stack top is one level up
+        // - create new item and add it as a child to the stack top item
+        // - push the newly created item to stack
+        let mut head = root.as_mut().expect("already set");
+        for el in &path {
+            head = head.content.get_mut(*el).expect("algorithm inconsistency");
         }
-        root.expect("invalid type layout with zero items")
+        path.push(head.content.len());
+        head.content.push(Box::new(expr)).expect("invalid type layout containing too much items");
     }
+    root.expect("invalid type layout with zero items")
 }
@@ -23,5 +23,8 @@
 pub mod vesper;
 mod translate;
 mod memory;
+mod size;
 
 pub use memory::MemoryLayout;
+pub use size::SizeBounds;
+pub(crate) use size::SizeCalc;
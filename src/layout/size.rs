@@ -0,0 +1,259 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Static bounds of the strict encoded size of types.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+use strict_encoding::STRICT_TYPES_LIB;
+
+use crate::typesys::UnknownType;
+use crate::util::SizingExt;
+use crate::{SemId, Ty, TypeRef, TypeSystem};
+
+/// Bounds of the strict encoded size of a type, in bytes.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = STRICT_TYPES_LIB)]
+pub struct SizeBounds {
+    /// Minimal number of bytes taken by any value of the type.
+    pub min: u64,
+    /// Maximal number of bytes taken by any value of the type, or `None` if it exceeds
+    /// `u64::MAX`.
+    pub max: Option<u64>,
+}
+
+impl Display for SizeBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{max}"),
+            Some(max) => write!(f, "{}..{max}", self.min),
+            None => write!(f, "{}..", self.min),
+        }
+    }
+}
+
+impl SizeBounds {
+    /// Bounds of a type which always takes exactly `len` bytes.
+    pub const fn fixed(len: u64) -> Self {
+        Self {
+            min: len,
+            max: Some(len),
+        }
+    }
+
+    /// Bounds of a type with a known minimum and no upper limit.
+    pub const fn unbounded(min: u64) -> Self { Self { min, max: None } }
+
+    /// Detects whether all values of the type have the same encoded size.
+    pub fn is_fixed(&self) -> bool { self.max == Some(self.min) }
+
+    /// Detects whether the encoded size of the type has an upper limit.
+    pub fn is_bounded(&self) -> bool { self.max.is_some() }
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            min: self.min.saturating_add(other.min),
+            max: self.max.zip(other.max).and_then(|(a, b)| a.checked_add(b)),
+        }
+    }
+
+    fn mul(self, min: u64, max: u64) -> Self {
+        Self {
+            min: self.min.saturating_mul(min),
+            max: self.max.and_then(|len| len.checked_mul(max)),
+        }
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.zip(other.max).map(|(a, b)| a.max(b)),
+        }
+    }
+}
+
+impl TypeSystem {
+    /// Computes minimal and maximal strict encoded size of the values of a type.
+    pub fn size_bounds(&self, sem_id: SemId) -> Result<SizeBounds, UnknownType> {
+        SizeCalc::new(self).bounds(sem_id)
+    }
+}
+
+/// Calculator of the encoded size bounds, which remembers the bounds of all types it has
+/// processed, such that it can be reused for many types of the same type system.
+///
+/// Semantic ids are hashes of the type definitions, so a type system can't contain recursive
+/// types and the calculation always terminates.
+pub(crate) struct SizeCalc<'a> {
+    types: &'a TypeSystem,
+    known: BTreeMap<SemId, SizeBounds>,
+}
+
+impl<'a> SizeCalc<'a> {
+    pub fn new(types: &'a TypeSystem) -> Self {
+        SizeCalc {
+            types,
+            known: empty!(),
+        }
+    }
+
+    pub fn bounds(&mut self, sem_id: SemId) -> Result<SizeBounds, UnknownType> {
+        if let Some(bounds) = self.known.get(&sem_id) {
+            return Ok(*bounds);
+        }
+        let ty = self.types.get(sem_id).ok_or(UnknownType(sem_id))?;
+        let bounds = match ty {
+            Ty::Primitive(prim) => SizeBounds::fixed(prim.byte_size() as u64),
+            Ty::UnicodeChar => SizeBounds {
+                min: 1,
+                max: Some(4),
+            },
+            Ty::Enum(_) => SizeBounds::fixed(1),
+            Ty::Union(variants) => {
+                let mut bounds = None::<SizeBounds>;
+                for id in variants.values() {
+                    let var = self.bounds(*id)?;
+                    bounds = Some(bounds.map(|b| b.union(var)).unwrap_or(var));
+                }
+                let bounds = bounds.expect("union variants are non-empty");
+                SizeBounds::fixed(1).add(bounds)
+            }
+            Ty::Tuple(fields) => {
+                let mut bounds = SizeBounds::fixed(0);
+                for id in fields {
+                    bounds = bounds.add(self.bounds(*id)?);
+                }
+                bounds
+            }
+            Ty::Struct(fields) => {
+                let mut bounds = SizeBounds::fixed(0);
+                for field in fields {
+                    bounds = bounds.add(self.bounds(field.ty)?);
+                }
+                bounds
+            }
+            Ty::Array(id, len) => {
+                let len = *len as u64;
+                self.bounds(*id)?.mul(len, len)
+            }
+            // Lengths of byte and unicode strings are counted in bytes, not in characters
+            Ty::List(id, sizing) if id.is_byte() || id.is_unicode_char() => {
+                let prefix = SizeBounds::fixed(sizing.byte_size() as u64);
+                prefix.add(SizeBounds::fixed(1).mul(sizing.min, sizing.max))
+            }
+            Ty::List(id, sizing) | Ty::Set(id, sizing) => {
+                let prefix = SizeBounds::fixed(sizing.byte_size() as u64);
+                prefix.add(self.bounds(*id)?.mul(sizing.min, sizing.max))
+            }
+            Ty::Map(key, val, sizing) => {
+                let prefix = SizeBounds::fixed(sizing.byte_size() as u64);
+                let item = self.bounds(*key)?.add(self.bounds(*val)?);
+                prefix.add(item.mul(sizing.min, sizing.max))
+            }
+        };
+        self.known.insert(sem_id, bounds);
+        Ok(bounds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::typesys::TypeFqn;
+    use crate::value::test_helpers::events_system;
+    use crate::SymbolicSys;
+
+    fn system() -> SymbolicSys {
+        let src = "data Name : [Unicode ^ 1..0x10]
+data Key : [Byte ^ 32]
+data Registry : {Key -> ^ ..0x1000 Event}
+data Wrapper : Name
+data Blob : [Byte ^ 2..0x100]
+data Chars : [Unicode ^ 3]
+";
+        events_system(src)
+    }
+
+    fn bounds(sys: &SymbolicSys, name: &'static str) -> SizeBounds {
+        let sem_id = sys.to_sem_id(TypeFqn::with("Events", name)).unwrap();
+        sys.as_types().size_bounds(sem_id).unwrap()
+    }
+
+    #[test]
+    fn bounds_of_types() {
+        let sys = system();
+        assert_eq!(bounds(&sys, "Kind"), SizeBounds::fixed(1));
+        assert_eq!(bounds(&sys, "Event"), SizeBounds {
+            min: 1,
+            max: Some(10)
+        });
+        assert_eq!(bounds(&sys, "Name"), SizeBounds {
+            min: 2,
+            max: Some(17)
+        });
+        assert_eq!(bounds(&sys, "Wrapper"), bounds(&sys, "Name"));
+        assert_eq!(bounds(&sys, "Blob"), SizeBounds {
+            min: 4,
+            max: Some(0x102)
+        });
+        assert_eq!(bounds(&sys, "Chars"), SizeBounds {
+            min: 3,
+            max: Some(12)
+        });
+        assert_eq!(bounds(&sys, "Key"), SizeBounds::fixed(32));
+        assert_eq!(bounds(&sys, "Index"), SizeBounds {
+            min: 1,
+            max: Some(1 + 0xff * 12)
+        });
+        assert_eq!(bounds(&sys, "Registry"), SizeBounds {
+            min: 2,
+            max: Some(2 + 0x1000 * 42)
+        });
+        assert!(bounds(&sys, "Key").is_fixed());
+        assert!(!bounds(&sys, "Event").is_fixed());
+    }
+
+    #[test]
+    fn overflow() {
+        let sys =
+            events_system("data Huge : [[Byte ^ ..0xffffffffffffffff] ^ ..0xffffffffffffffff]\n");
+        let huge = bounds(&sys, "Huge");
+        assert_eq!(huge, SizeBounds::unbounded(8));
+        assert!(!huge.is_bounded());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(SizeBounds::fixed(32).to_string(), "32");
+        assert_eq!(
+            SizeBounds {
+                min: 1,
+                max: Some(10)
+            }
+            .to_string(),
+            "1..10"
+        );
+        assert_eq!(SizeBounds::unbounded(8).to_string(), "8..");
+    }
+}
@@ -25,11 +25,12 @@ use encoding::Primitive;
 
 use super::vesper::{Attr, Pred, TypeVesper};
 use crate::ast::ItemCase;
+use crate::layout::SizeBounds;
 use crate::typesys::{NestedCase, TypeInfo};
 use crate::Ty;
 
 impl TypeInfo {
-    pub(super) fn to_vesper(&self) -> TypeVesper {
+    pub(super) fn to_vesper(&self, size: Option<SizeBounds>) -> TypeVesper {
         let TypeInfo {
             ty,
             fqn,
@@ -142,6 +143,9 @@ impl TypeInfo {
         if let Some(ItemCase::UnionVariant(ref pos, _)) = item {
             attributes.push(Attr::Tag(*pos));
         }
+        if let Some(size) = size {
+            attributes.push(Attr::Size(size));
+        }
 
         TypeVesper {
             subject,
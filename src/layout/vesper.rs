@@ -28,6 +28,7 @@ use amplify::num::u24;
 use encoding::{Ident, Sizing};
 use vesper::{AttrVal, Attribute, Expression, Predicate, TExpr};
 
+use crate::layout::SizeBounds;
 use crate::Cls;
 
 pub type TypeVesper = TExpr<Pred>;
@@ -82,6 +83,7 @@ pub enum AttrExpr {
     EnumVariant(u8),
     Len(u16),
     LenRange(LenRange),
+    Size(SizeBounds),
 }
 
 impl Expression for AttrExpr {}
@@ -98,6 +100,7 @@ pub enum Attr {
     EnumVariant(u8, Ident),
     Len(u16),
     LenRange(LenRange),
+    Size(SizeBounds),
 }
 
 impl Attribute for Attr {
@@ -112,6 +115,7 @@ impl Attribute for Attr {
             Attr::Tag(_) => Some(ident!("tag")),
             Attr::Len(_) => Some(ident!("len")),
             Attr::LenRange(_) => Some(ident!("len")),
+            Attr::Size(_) => Some(ident!("size")),
             Attr::AsciiEnum(_) => Some(ident!("charset")),
             Attr::AsciiFirst(_) => Some(ident!("first")),
             Attr::AsciiRest(_) => Some(ident!("rest")),
@@ -127,6 +131,7 @@ impl Attribute for Attr {
             Attr::Tag(tag) => AttrVal::Expr(AttrExpr::Tag(*tag)),
             Attr::Len(len) => AttrVal::Expr(AttrExpr::Len(*len)),
             Attr::LenRange(range) => AttrVal::Expr(AttrExpr::LenRange(range.clone())),
+            Attr::Size(size) => AttrVal::Expr(AttrExpr::Size(*size)),
             Attr::AsciiEnum(name) => AttrVal::Ident(name.clone()),
             Attr::AsciiFirst(name) => AttrVal::Ident(name.clone()),
             Attr::AsciiRest(name) => AttrVal::Ident(name.clone()),
@@ -59,15 +59,21 @@ pub mod typesys;
 pub mod value;
 pub mod stl;
 pub mod layout;
+pub mod codegen;
 
 pub use ast::{Cls, PrimitiveRef, SemId, Translate, Ty, TypeRef};
 pub use typelib::{
     CompileError, Dependency, LibBuilder, LibRef, SymbolRef, SymbolicLib, TranspileError,
     TranspileRef, TypeLib, TypeLibId,
 };
-pub use typesys::{SymbolicSys, SystemBuilder, TypeSymbol, TypeSysId, TypeSystem};
-pub use util::{parse_args, BuildFragment, PreFragment, SemVer, StlFormat, UnknownFormat, Urn};
-pub use value::{decode, ston, typify, KeyStep, Path, PathError, Step, StrictVal};
+pub use typesys::{LibRegistry, SymbolicSys, SystemBuilder, TypeSymbol, TypeSysId, TypeSystem};
+pub use util::{
+    parse_args, BuildFragment, Pos, PreFragment, SemVer, Span, StlFormat, UnknownFormat, Urn,
+    UrnParseError,
+};
+pub use value::{
+    decode, ston, typify, EditError, KeyStep, Path, PathError, PathParseError, Step, StrictVal,
+};
 
 pub trait CommitConsume {
     fn commit_consume(&mut self, data: impl AsRef<[u8]>);
@@ -0,0 +1,652 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compatibility checks between different versions of the same type library.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+
+use encoding::{FieldName, Primitive, Sizing, TypeName, Variant, VariantName};
+
+use crate::ast::{NamedFields, Path, Step};
+use crate::typelib::{ExternRef, InlineRef, InlineRef1, InlineRef2, LibRef, TypeLib};
+use crate::util::SizingExt;
+use crate::{Cls, SemId, Ty, TypeRef};
+
+/// Compatibility of a change in a type library with the data encoded using the previous version
+/// of the library.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum Compatibility {
+    /// Data encoded with either version decode with the other version.
+    #[display("wire-compatible")]
+    WireCompatible,
+
+    /// Data encoded with the old version decode with the new version, but not vice versa.
+    Widening,
+
+    /// Data encoded with the old version may not decode with the new version.
+    Breaking,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display)]
+#[display(doc_comments)]
+pub enum ChangeKind {
+    /// type added.
+    TypeAdded,
+
+    /// type removed.
+    TypeRemoved,
+
+    /// type renamed from `{from}`.
+    TypeRenamed { from: TypeName },
+
+    /// semantic id changed from {old} to {new}.
+    SemIdChanged { old: SemId, new: SemId },
+
+    /// type class changed from {old} to {new}.
+    ClassChanged { old: Cls, new: Cls },
+
+    /// primitive type changed from {old} to {new}.
+    PrimitiveChanged { old: Primitive, new: Primitive },
+
+    /// reference changed from `{old}` to `{new}`.
+    RefChanged { old: String, new: String },
+
+    /// field `{0}` added.
+    FieldAdded(FieldName),
+
+    /// field `{0}` removed.
+    FieldRemoved(FieldName),
+
+    /// field `{0}` moved to a different position.
+    FieldMoved(FieldName),
+
+    /// field `{from}` renamed to `{to}`.
+    FieldRenamed { from: FieldName, to: FieldName },
+
+    /// number of fields changed from {old} to {new}.
+    FieldCountChanged { old: usize, new: usize },
+
+    /// variant `{0}` added.
+    VariantAdded(Variant),
+
+    /// variant `{0}` removed.
+    VariantRemoved(Variant),
+
+    /// variant `{name}` re-tagged from {old} to {new}.
+    VariantRetagged { name: VariantName, old: u8, new: u8 },
+
+    /// variant with tag {tag} renamed from `{from}` to `{to}`.
+    VariantRenamed {
+        tag: u8,
+        from: VariantName,
+        to: VariantName,
+    },
+
+    /// array length changed from {old} to {new}.
+    ArrayLenChanged { old: u16, new: u16 },
+
+    /// collection size bounds changed from {old} to {new}.
+    SizingChanged { old: Sizing, new: Sizing },
+}
+
+impl ChangeKind {
+    pub fn compatibility(&self) -> Compatibility {
+        match self {
+            ChangeKind::TypeAdded
+            | ChangeKind::TypeRenamed { .. }
+            | ChangeKind::SemIdChanged { .. }
+            | ChangeKind::FieldRenamed { .. }
+            | ChangeKind::VariantRenamed { .. } => Compatibility::WireCompatible,
+
+            ChangeKind::VariantAdded(_) => Compatibility::Widening,
+            ChangeKind::SizingChanged { old, new }
+                if old.byte_size() == new.byte_size()
+                    && new.min <= old.min
+                    && new.max >= old.max =>
+            {
+                Compatibility::Widening
+            }
+
+            ChangeKind::TypeRemoved
+            | ChangeKind::ClassChanged { .. }
+            | ChangeKind::PrimitiveChanged { .. }
+            | ChangeKind::RefChanged { .. }
+            | ChangeKind::FieldAdded(_)
+            | ChangeKind::FieldRemoved(_)
+            | ChangeKind::FieldMoved(_)
+            | ChangeKind::FieldCountChanged { .. }
+            | ChangeKind::VariantRemoved(_)
+            | ChangeKind::VariantRetagged { .. }
+            | ChangeKind::ArrayLenChanged { .. }
+            | ChangeKind::SizingChanged { .. } => Compatibility::Breaking,
+        }
+    }
+}
+
+/// A single change between two versions of a type library.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TypeChange {
+    /// Name of the changed type (in the new library version, if the type is present there).
+    pub ty: TypeName,
+    /// Path to the changed part of the type.
+    pub path: Path,
+    pub kind: ChangeKind,
+}
+
+impl TypeChange {
+    pub fn compatibility(&self) -> Compatibility { self.kind.compatibility() }
+}
+
+impl Display for TypeChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}{}: {}", self.compatibility(), self.ty, self.path, self.kind)
+    }
+}
+
+/// Report on the differences between two versions of a type library, produced by [`diff`].
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct LibDiff {
+    pub changes: Vec<TypeChange>,
+}
+
+impl LibDiff {
+    pub fn is_empty(&self) -> bool { self.changes.is_empty() }
+
+    /// Returns the least compatible of all the changes.
+    pub fn compatibility(&self) -> Compatibility {
+        self.changes
+            .iter()
+            .map(TypeChange::compatibility)
+            .max()
+            .unwrap_or(Compatibility::WireCompatible)
+    }
+
+    pub fn changes_with(&self, compatibility: Compatibility) -> impl Iterator<Item = &TypeChange> {
+        self.changes.iter().filter(move |change| change.compatibility() == compatibility)
+    }
+}
+
+impl Display for LibDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two versions of a type library, reporting all changes in the types and their
+/// compatibility with the data encoded using the old version of the library.
+///
+/// Types are matched by their names; a removed type which has exactly the same definition as an
+/// added one is reported as renamed.
+pub fn diff(old: &TypeLib, new: &TypeLib) -> LibDiff {
+    let mut differ = Differ {
+        old_names: old.types.iter().map(|(name, ty)| (ty.sem_id_named(name), name)).collect(),
+        new_names: new.types.iter().map(|(name, ty)| (ty.sem_id_named(name), name)).collect(),
+        renames: BTreeMap::new(),
+        ty: tn!("Dumb"),
+        path: Path::new(),
+        changes: vec![],
+    };
+
+    let removed = old.types.keys().filter(|name| !new.types.contains_key(*name));
+    let mut added =
+        new.types.keys().filter(|name| !old.types.contains_key(*name)).collect::<BTreeSet<_>>();
+    for name in removed {
+        let ty = &old.types[name];
+        match added.iter().find(|added| &new.types[**added] == ty).copied() {
+            Some(renamed) => {
+                added.remove(renamed);
+                differ.renames.insert(name, renamed);
+                differ.ty = renamed.clone();
+                differ.push(ChangeKind::TypeRenamed { from: name.clone() });
+            }
+            None => {
+                differ.ty = name.clone();
+                differ.push(ChangeKind::TypeRemoved);
+            }
+        }
+    }
+    for name in added {
+        differ.ty = name.clone();
+        differ.push(ChangeKind::TypeAdded);
+    }
+
+    for (name, new_ty) in new.types.iter() {
+        let Some(old_ty) = old.types.get(name) else {
+            continue;
+        };
+        differ.ty = name.clone();
+        let (old_id, new_id) = (old_ty.sem_id_named(name), new_ty.sem_id_named(name));
+        if old_id != new_id {
+            differ.push(ChangeKind::SemIdChanged {
+                old: old_id,
+                new: new_id,
+            });
+        }
+        differ.diff_ty(old_ty, new_ty);
+    }
+
+    LibDiff {
+        changes: differ.changes,
+    }
+}
+
+enum RefCase<'a, Inner: TypeRef> {
+    Inline(&'a Ty<Inner>),
+    Named(&'a SemId),
+    Extern(&'a ExternRef),
+}
+
+trait DiffRef: TypeRef + Display {
+    type Inner: DiffRef;
+    fn case(&self) -> RefCase<'_, Self::Inner>;
+}
+
+impl DiffRef for LibRef {
+    type Inner = InlineRef;
+    fn case(&self) -> RefCase<'_, Self::Inner> {
+        match self {
+            LibRef::Inline(ty) => RefCase::Inline(ty),
+            LibRef::Named(id) => RefCase::Named(id),
+            LibRef::Extern(ext) => RefCase::Extern(ext),
+        }
+    }
+}
+
+impl DiffRef for InlineRef {
+    type Inner = InlineRef1;
+    fn case(&self) -> RefCase<'_, Self::Inner> {
+        match self {
+            InlineRef::Inline(ty) => RefCase::Inline(ty),
+            InlineRef::Named(id) => RefCase::Named(id),
+            InlineRef::Extern(ext) => RefCase::Extern(ext),
+        }
+    }
+}
+
+impl DiffRef for InlineRef1 {
+    type Inner = InlineRef2;
+    fn case(&self) -> RefCase<'_, Self::Inner> {
+        match self {
+            InlineRef1::Inline(ty) => RefCase::Inline(ty),
+            InlineRef1::Named(id) => RefCase::Named(id),
+            InlineRef1::Extern(ext) => RefCase::Extern(ext),
+        }
+    }
+}
+
+impl DiffRef for InlineRef2 {
+    type Inner = InlineRef2;
+    fn case(&self) -> RefCase<'_, Self::Inner> {
+        match self {
+            InlineRef2::Named(id) => RefCase::Named(id),
+            InlineRef2::Extern(ext) => RefCase::Extern(ext),
+        }
+    }
+}
+
+struct Differ<'lib> {
+    old_names: BTreeMap<SemId, &'lib TypeName>,
+    new_names: BTreeMap<SemId, &'lib TypeName>,
+    renames: BTreeMap<&'lib TypeName, &'lib TypeName>,
+    ty: TypeName,
+    path: Path,
+    changes: Vec<TypeChange>,
+}
+
+impl Differ<'_> {
+    fn push(&mut self, kind: ChangeKind) {
+        self.changes.push(TypeChange {
+            ty: self.ty.clone(),
+            path: self.path.clone(),
+            kind,
+        });
+    }
+
+    fn nested<R: DiffRef>(&mut self, old: &R, new: &R, step: Step) {
+        let pushed = self.path.push(step).is_ok();
+        self.diff_ref(old, new);
+        if pushed {
+            self.path.pop();
+        }
+    }
+
+    fn diff_ref<R: DiffRef>(&mut self, old: &R, new: &R) {
+        match (old.case(), new.case()) {
+            (RefCase::Inline(old), RefCase::Inline(new)) => self.diff_ty(old, new),
+            // Changes inside named types are reported for those types separately
+            (RefCase::Named(old_id), RefCase::Named(new_id)) => {
+                let old_name = self.old_names.get(old_id).copied();
+                let new_name = self.new_names.get(new_id).copied();
+                let same = match (old_name, new_name) {
+                    (Some(old_name), Some(new_name)) => {
+                        old_name == new_name || self.renames.get(old_name) == Some(&new_name)
+                    }
+                    _ => old_id == new_id,
+                };
+                if !same {
+                    self.push(ChangeKind::RefChanged {
+                        old: old_name.map(TypeName::to_string).unwrap_or_else(|| old.to_string()),
+                        new: new_name.map(TypeName::to_string).unwrap_or_else(|| new.to_string()),
+                    });
+                }
+            }
+            (RefCase::Extern(old_ext), RefCase::Extern(new_ext)) if old_ext == new_ext => {}
+            _ => self.push(ChangeKind::RefChanged {
+                old: old.to_string(),
+                new: new.to_string(),
+            }),
+        }
+    }
+
+    fn diff_ty<R: DiffRef>(&mut self, old: &Ty<R>, new: &Ty<R>) {
+        match (old, new) {
+            (Ty::Primitive(old), Ty::Primitive(new)) if old != new => {
+                self.push(ChangeKind::PrimitiveChanged {
+                    old: *old,
+                    new: *new,
+                });
+            }
+            (Ty::Primitive(_), Ty::Primitive(_)) | (Ty::UnicodeChar, Ty::UnicodeChar) => {}
+            (Ty::Enum(old), Ty::Enum(new)) => {
+                self.diff_variants(old.iter(), new.iter());
+            }
+            (Ty::Union(old), Ty::Union(new)) => {
+                self.diff_variants(old.keys(), new.keys());
+                for (variant, old_ty) in old.iter() {
+                    if let Some(new_ty) = new.ty_by_name(&variant.name) {
+                        self.nested(old_ty, new_ty, Step::Variant(variant.name.clone()));
+                    }
+                }
+            }
+            (Ty::Tuple(old), Ty::Tuple(new)) => {
+                if old.len() != new.len() {
+                    self.push(ChangeKind::FieldCountChanged {
+                        old: old.len(),
+                        new: new.len(),
+                    });
+                }
+                for (pos, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+                    self.nested(old, new, Step::UnnamedField(pos as u8));
+                }
+            }
+            (Ty::Struct(old), Ty::Struct(new)) => self.diff_fields(old, new),
+            (Ty::Array(old, old_len), Ty::Array(new, new_len)) => {
+                if old_len != new_len {
+                    self.push(ChangeKind::ArrayLenChanged {
+                        old: *old_len,
+                        new: *new_len,
+                    });
+                }
+                self.nested(old, new, Step::Index);
+            }
+            (Ty::List(old, old_sizing), Ty::List(new, new_sizing)) => {
+                self.diff_sizing(*old_sizing, *new_sizing);
+                self.nested(old, new, Step::List);
+            }
+            (Ty::Set(old, old_sizing), Ty::Set(new, new_sizing)) => {
+                self.diff_sizing(*old_sizing, *new_sizing);
+                self.nested(old, new, Step::Set);
+            }
+            (Ty::Map(old_key, old, old_sizing), Ty::Map(new_key, new, new_sizing)) => {
+                self.diff_sizing(*old_sizing, *new_sizing);
+                self.nested(old_key, new_key, Step::MapKey);
+                self.nested(old, new, Step::MapValue);
+            }
+            (old, new) => self.push(ChangeKind::ClassChanged {
+                old: old.cls(),
+                new: new.cls(),
+            }),
+        }
+    }
+
+    fn diff_sizing(&mut self, old: Sizing, new: Sizing) {
+        if old != new {
+            self.push(ChangeKind::SizingChanged { old, new });
+        }
+    }
+
+    fn diff_variants<'v>(
+        &mut self,
+        old: impl Iterator<Item = &'v Variant> + Clone,
+        new: impl Iterator<Item = &'v Variant> + Clone,
+    ) {
+        let mut renamed = BTreeSet::new();
+        for variant in old.clone() {
+            if let Some(same) = new.clone().find(|v| v.name == variant.name) {
+                if same.tag != variant.tag {
+                    self.push(ChangeKind::VariantRetagged {
+                        name: variant.name.clone(),
+                        old: variant.tag,
+                        new: same.tag,
+                    });
+                }
+            } else if let Some(same) =
+                new.clone().find(|v| v.tag == variant.tag && old.clone().all(|o| o.name != v.name))
+            {
+                renamed.insert(&same.name);
+                self.push(ChangeKind::VariantRenamed {
+                    tag: variant.tag,
+                    from: variant.name.clone(),
+                    to: same.name.clone(),
+                });
+            } else {
+                self.push(ChangeKind::VariantRemoved(variant.clone()));
+            }
+        }
+        for variant in new {
+            if !renamed.contains(&variant.name) && old.clone().all(|v| v.name != variant.name) {
+                self.push(ChangeKind::VariantAdded(variant.clone()));
+            }
+        }
+    }
+
+    fn diff_fields<R: DiffRef>(&mut self, old: &NamedFields<R>, new: &NamedFields<R>) {
+        let has_old = |name: &FieldName| old.iter().any(|field| &field.name == name);
+        let has_new = |name: &FieldName| new.iter().any(|field| &field.name == name);
+        let mut renamed = BTreeSet::new();
+        for (old_field, new_field) in old.iter().zip(new.iter()) {
+            if old_field.name == new_field.name {
+                continue;
+            }
+            if !has_new(&old_field.name) && !has_old(&new_field.name) {
+                renamed.insert(&new_field.name);
+                self.push(ChangeKind::FieldRenamed {
+                    from: old_field.name.clone(),
+                    to: new_field.name.clone(),
+                });
+            } else if has_new(&old_field.name) {
+                self.push(ChangeKind::FieldMoved(old_field.name.clone()));
+            }
+        }
+        for field in old.iter() {
+            if !has_new(&field.name)
+                && !(new.iter().zip(old.iter()))
+                    .any(|(n, o)| o.name == field.name && renamed.contains(&n.name))
+            {
+                self.push(ChangeKind::FieldRemoved(field.name.clone()));
+            }
+        }
+        for field in new.iter() {
+            if !has_old(&field.name) && !renamed.contains(&field.name) {
+                self.push(ChangeKind::FieldAdded(field.name.clone()));
+            }
+        }
+        for (pos, old_field) in old.iter().enumerate() {
+            let new_field = match new.iter().find(|field| field.name == old_field.name) {
+                Some(field) => field,
+                None => match new.iter().nth(pos) {
+                    Some(field) if renamed.contains(&field.name) => field,
+                    _ => continue,
+                },
+            };
+            self.nested(&old_field.ty, &new_field.ty, Step::NamedField(new_field.name.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value::test_helpers::lib_from_source;
+
+    const V1: &str = "typelib Test
+data Kind : alpha | beta | gamma
+data Shape : circle U16 | rect (w U16, h U16)
+data Item : id U64, kind Kind, tags [U8 ^ ..0xff], name [Unicode ^ 1..0xff]
+data Point : x I32, y I32
+data Legacy : U8
+";
+
+    fn kinds(diff: &LibDiff) -> Vec<(String, ChangeKind)> {
+        diff.changes.iter().map(|c| (format!("{}{}", c.ty, c.path), c.kind.clone())).collect()
+    }
+
+    #[test]
+    fn same() {
+        let diff = diff(&lib_from_source(V1), &lib_from_source(V1));
+        assert!(diff.is_empty());
+        assert_eq!(diff.compatibility(), Compatibility::WireCompatible);
+    }
+
+    #[test]
+    fn wire_compatible() {
+        let v2 = "typelib Test
+data Kind : alpha | beta | delta
+data Shape : circle U16 | rect (width U16, h U16)
+data Item : id U64, kind Kind, tags [U8 ^ ..0xff], name [Unicode ^ 1..0xff]
+data Position : x I32, y I32
+data Legacy : U8
+data Fresh : U16
+";
+        let diff = diff(&lib_from_source(V1), &lib_from_source(v2));
+        assert_eq!(diff.compatibility(), Compatibility::WireCompatible);
+        assert_eq!(kinds(&diff), vec![
+            (s!("Position"), ChangeKind::TypeRenamed { from: tn!("Point") }),
+            (s!("Fresh"), ChangeKind::TypeAdded),
+            (s!("Item"), ChangeKind::SemIdChanged {
+                old: lib_from_source(V1).types[&tn!("Item")].sem_id_named(&tn!("Item")),
+                new: lib_from_source(v2).types[&tn!("Item")].sem_id_named(&tn!("Item")),
+            }),
+            (s!("Kind"), ChangeKind::SemIdChanged {
+                old: lib_from_source(V1).types[&tn!("Kind")].sem_id_named(&tn!("Kind")),
+                new: lib_from_source(v2).types[&tn!("Kind")].sem_id_named(&tn!("Kind")),
+            }),
+            (s!("Kind"), ChangeKind::VariantRenamed {
+                tag: 2,
+                from: vname!("gamma"),
+                to: vname!("delta"),
+            }),
+            (s!("Shape"), ChangeKind::SemIdChanged {
+                old: lib_from_source(V1).types[&tn!("Shape")].sem_id_named(&tn!("Shape")),
+                new: lib_from_source(v2).types[&tn!("Shape")].sem_id_named(&tn!("Shape")),
+            }),
+            (s!("Shape.rect"), ChangeKind::FieldRenamed {
+                from: fname!("w"),
+                to: fname!("width"),
+            }),
+        ]);
+    }
+
+    #[test]
+    fn widening() {
+        let v2 = "typelib Test
+data Kind : alpha | beta | gamma | delta
+data Shape : circle U16 | rect (w U16, h U16)
+data Item : id U64, kind Kind, tags [U8 ^ ..0xfe], name [Unicode ^ ..0xff]
+data Point : x I32, y I32
+data Legacy : U8
+";
+        let diff = diff(&lib_from_source(V1), &lib_from_source(v2));
+        let changes = diff
+            .changes
+            .iter()
+            .filter(|c| !matches!(c.kind, ChangeKind::SemIdChanged { .. }))
+            .map(|c| (c.path.to_string(), c.kind.clone(), c.compatibility()))
+            .collect::<Vec<_>>();
+        assert_eq!(changes, vec![
+            (
+                s!(".tags"),
+                ChangeKind::SizingChanged {
+                    old: Sizing::new(0, 0xff),
+                    new: Sizing::new(0, 0xfe),
+                },
+                Compatibility::Breaking
+            ),
+            (
+                s!(".name"),
+                ChangeKind::SizingChanged {
+                    old: Sizing::new(1, 0xff),
+                    new: Sizing::new(0, 0xff),
+                },
+                Compatibility::Widening
+            ),
+            (
+                s!(""),
+                ChangeKind::VariantAdded(Variant::named(3, vname!("delta"))),
+                Compatibility::Widening
+            ),
+        ]);
+        assert_eq!(diff.compatibility(), Compatibility::Breaking);
+    }
+
+    #[test]
+    fn breaking() {
+        let v2 = "typelib Test
+data Kind : alpha | beta#5 | gamma#2
+data Shape : circle U32 | rect (w U16, h U16, d U16)
+data Item : kind Kind, id U64, name [Unicode ^ 1..0xff], extra U8
+data Point : x I32, y I32
+";
+        let diff = diff(&lib_from_source(V1), &lib_from_source(v2));
+        assert_eq!(diff.compatibility(), Compatibility::Breaking);
+        let changes = diff
+            .changes
+            .iter()
+            .filter(|c| !matches!(c.kind, ChangeKind::SemIdChanged { .. }))
+            .map(|c| (format!("{}{}", c.ty, c.path), c.kind.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(changes, vec![
+            (s!("Legacy"), ChangeKind::TypeRemoved),
+            (s!("Item"), ChangeKind::FieldMoved(fname!("id"))),
+            (s!("Item"), ChangeKind::FieldMoved(fname!("kind"))),
+            (s!("Item"), ChangeKind::FieldMoved(fname!("name"))),
+            (s!("Item"), ChangeKind::FieldRemoved(fname!("tags"))),
+            (s!("Item"), ChangeKind::FieldAdded(fname!("extra"))),
+            (s!("Kind"), ChangeKind::VariantRetagged {
+                name: vname!("beta"),
+                old: 1,
+                new: 5,
+            }),
+            (s!("Shape.circle.0"), ChangeKind::PrimitiveChanged {
+                old: Primitive::U16,
+                new: Primitive::U32,
+            }),
+            (s!("Shape.rect"), ChangeKind::FieldAdded(fname!("d"))),
+        ]);
+        assert!(diff.to_string().starts_with("breaking: Legacy: type removed.\n"));
+    }
+}
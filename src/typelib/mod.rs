@@ -27,12 +27,16 @@ mod serialize;
 mod transpile;
 mod symbolic;
 mod translate;
+mod parse;
+mod diff;
 
 pub(crate) use compile::NestedContext;
 #[allow(deprecated)]
 pub use compile::TranslateError;
 pub use compile::{CompileError, TypeIndex};
+pub use diff::{diff, ChangeKind, Compatibility, LibDiff, TypeChange};
 pub use id::TypeLibId;
+pub use parse::{ParseError, ParseErrorKind};
 pub use symbolic::{ExternTypes, SymbolRef, SymbolicLib, TranspileError, TranspileRef};
 use translate::SymbolContext;
 pub use translate::SymbolError;
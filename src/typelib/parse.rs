@@ -0,0 +1,996 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parser for the `.sty` source files, producing [`SymbolicLib`] from the text generated by its
+//! [`Display`](std::fmt::Display) implementation (or written by hand in the same syntax).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use baid64::DisplayBaid64;
+use strict_encoding::{
+    FieldName, InvalidRString, LibName, Primitive, Sizing, TypeName, Variant, VariantName,
+};
+
+use crate::ast::{EnumVariants, Field, NamedFields, UnionVariants, UnnamedFields};
+use crate::typelib::{LibBuilder, SymbolRef, SymbolicLib, TranspileError, TranspileRef};
+use crate::{Pos, Span, Ty, TypeLib};
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display("{span}: {kind}")]
+pub struct ParseError {
+    pub span: Span,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum ParseErrorKind {
+    /// unexpected character `{0}`.
+    UnexpectedChar(char),
+
+    /// unterminated block comment.
+    UnterminatedComment,
+
+    /// invalid number literal `{0}`.
+    InvalidNumber(String),
+
+    /// expected {expected}, found {found}.
+    Unexpected {
+        expected: &'static str,
+        found: String,
+    },
+
+    /// invalid identifier: {0}
+    #[from]
+    InvalidIdent(InvalidRString),
+
+    /// library name is not specified with `typelib` statement.
+    NoLibName,
+
+    /// library name is already specified.
+    RepeatedLibName,
+
+    /// unknown pragma `@{0}`.
+    UnknownPragma(String),
+
+    /// library `{0}` is imported more than once.
+    DuplicateImport(LibName),
+
+    /// imported library `{0}` is not provided.
+    UnknownLib(LibName),
+
+    /// library `{0}` is used but not imported.
+    NotImported(LibName),
+
+    /// checkword `{found}` doesn't match library {lib}#{expected}.
+    LibMnemonicMismatch {
+        lib: LibName,
+        expected: String,
+        found: String,
+    },
+
+    /// type `{name}` is absent from library `{lib}`.
+    UnknownExtern { lib: LibName, name: TypeName },
+
+    /// checkword `{found}` doesn't match the semantic id of type {name}#{expected}.
+    TypeMnemonicMismatch {
+        name: TypeName,
+        expected: String,
+        found: String,
+    },
+
+    /// type `{0}` is defined more than once.
+    DuplicateType(TypeName),
+
+    /// unknown type `{0}`.
+    UnknownType(TypeName),
+
+    /// name `{0}` is repeated.
+    DuplicateName(String),
+
+    /// tag {0} is repeated.
+    DuplicateTag(u8),
+
+    /// array length {0} exceeds 65535.
+    ArrayTooLarge(u64),
+
+    /// invalid sizing with the minimum {min} exceeding the maximum {max}.
+    InvalidSizing { min: u64, max: u64 },
+
+    /// enum and union variants can't be mixed.
+    MixedVariants,
+
+    /// the number of fields or variants exceeds 255.
+    TooManyItems,
+
+    /// {0}
+    #[display(inner)]
+    #[from]
+    Transpile(TranspileError),
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum Token {
+    Ident(String),
+    Number(u64),
+    Punct(&'static str),
+    Eof,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "`{ident}`"),
+            Token::Number(no) => write!(f, "`{no}`"),
+            Token::Punct(punct) => write!(f, "`{punct}`"),
+            Token::Eof => f.write_str("end of file"),
+        }
+    }
+}
+
+const PUNCTUATION: [&str; 16] =
+    ["->", "..", ":", ",", "|", "#", ".", "?", "^", "(", ")", "[", "]", "{", "}", "@"];
+
+struct Lexer<'src> {
+    src: &'src str,
+    pos: Pos,
+}
+
+impl<'src> Lexer<'src> {
+    fn rest(&self) -> &'src str { &self.src[self.pos.offset..] }
+
+    fn bump(&mut self, len: usize) {
+        let consumed = &self.src[self.pos.offset..self.pos.offset + len];
+        for c in consumed.chars() {
+            self.pos.advance(c);
+        }
+    }
+
+    fn error(&self, start: Pos, kind: impl Into<ParseErrorKind>) -> ParseError {
+        ParseError {
+            span: Span::new(start, self.pos),
+            kind: kind.into(),
+        }
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.bump(rest.len() - trimmed.len());
+            if trimmed.starts_with("--") {
+                let len = trimmed.find('\n').unwrap_or(trimmed.len());
+                self.bump(len);
+            } else if trimmed.starts_with("{-") {
+                let start = self.pos;
+                match trimmed.find("-}") {
+                    Some(len) => self.bump(len + 2),
+                    None => {
+                        self.bump(trimmed.len());
+                        return Err(self.error(start, ParseErrorKind::UnterminatedComment));
+                    }
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn tokenize(mut self) -> Result<Vec<(Token, Span)>, ParseError> {
+        let mut tokens = vec![];
+        loop {
+            self.skip_trivia()?;
+            let start = self.pos;
+            let rest = self.rest();
+            let Some(c) = rest.chars().next() else {
+                tokens.push((Token::Eof, Span::new(start, start)));
+                return Ok(tokens);
+            };
+            let token = if c.is_ascii_alphabetic() || c == '_' {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                self.bump(len);
+                Token::Ident(rest[..len].to_owned())
+            } else if c.is_ascii_digit() {
+                let len = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+                self.bump(len);
+                let lit = &rest[..len];
+                let no = match lit.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => u64::from_str(lit),
+                };
+                Token::Number(no.map_err(|_| {
+                    self.error(start, ParseErrorKind::InvalidNumber(lit.to_owned()))
+                })?)
+            } else if let Some(punct) = PUNCTUATION.iter().find(|p| rest.starts_with(**p)) {
+                self.bump(punct.len());
+                Token::Punct(punct)
+            } else if c == '-' {
+                self.bump(1);
+                Token::Punct("-")
+            } else {
+                self.bump(c.len_utf8());
+                return Err(self.error(start, ParseErrorKind::UnexpectedChar(c)));
+            };
+            tokens.push((token, Span::new(start, self.pos)));
+        }
+    }
+}
+
+/// Body of a struct field or a union variant, which are indistinguishable until the separator
+/// following them is seen.
+enum Body {
+    Compound(Ty<TranspileRef>),
+    Term(TranspileRef),
+}
+
+impl Body {
+    fn into_field(self) -> TranspileRef {
+        match self {
+            Body::Compound(ty) => ty.into(),
+            Body::Term(r) => r,
+        }
+    }
+
+    fn into_variant(self) -> Result<Ty<TranspileRef>, ParseErrorKind> {
+        match self {
+            Body::Compound(ty) => Ok(ty),
+            Body::Term(r) => newtype(r),
+        }
+    }
+}
+
+fn newtype(r: TranspileRef) -> Result<Ty<TranspileRef>, ParseErrorKind> {
+    if r == TranspileRef::unit() {
+        return Ok(Ty::UNIT);
+    }
+    Ok(Ty::Tuple(UnnamedFields::try_from(vec![r]).map_err(|_| ParseErrorKind::TooManyItems)?))
+}
+
+/// Collects references to the types from other libraries made by a type definition, including
+/// the ones from the nested inline types.
+fn extern_refs<'ty>(ty: &'ty Ty<TranspileRef>, refs: &mut Vec<&'ty SymbolRef>) {
+    for (r, _) in ty.type_refs() {
+        match r {
+            TranspileRef::Embedded(ty) => extern_refs(ty, refs),
+            TranspileRef::Named(_) => {}
+            TranspileRef::Extern(ext) => refs.push(ext),
+        }
+    }
+}
+
+/// Finds the span of the type definition which has caused the library compilation to fail.
+///
+/// For the errors on exceeding the library limits this is the first definition, in the source
+/// order, which brings the number of types, dependencies or the types used from a single
+/// dependency over the limit.
+fn offending_definition(
+    err: &TranspileError,
+    types: &BTreeMap<TypeName, Ty<TranspileRef>>,
+    definitions: &[(TypeName, Span)],
+) -> Option<Span> {
+    let mut externs = definitions.iter().map(|(name, span)| {
+        let mut refs = vec![];
+        extern_refs(&types[name], &mut refs);
+        (refs, *span)
+    });
+    let found = match err {
+        TranspileError::UnknownType { within, .. } => {
+            return definitions
+                .iter()
+                .find(|(name, _)| &types[name] == within)
+                .map(|(_, span)| *span)
+        }
+        TranspileError::TooManyTypes => {
+            return definitions.get(u16::MAX as usize).map(|(_, span)| *span)
+        }
+        TranspileError::UnknownLib(lib) => {
+            externs.find(|(refs, _)| refs.iter().any(|ext| &ext.lib_name == lib))
+        }
+        TranspileError::LibTooLarge(lib) => {
+            let mut used = BTreeSet::new();
+            externs.find(|(refs, _)| {
+                used.extend(refs.iter().filter(|ext| &ext.lib_name == lib).map(|ext| ext.sem_id));
+                used.len() > u16::MAX as usize
+            })
+        }
+        TranspileError::TooManyDependencies => {
+            let mut used = BTreeSet::new();
+            externs.find(|(refs, _)| {
+                used.extend(refs.iter().map(|ext| &ext.lib_name));
+                used.len() > u8::MAX as usize
+            })
+        }
+    };
+    found.map(|(_, span)| span)
+}
+
+fn primitive(name: &str) -> Option<Ty<TranspileRef>> {
+    match name {
+        "Byte" => return Some(Ty::BYTE),
+        "F16b" => return Some(Ty::F16B),
+        "Unicode" => return Some(Ty::UNICODE),
+        _ => {}
+    }
+    let cls = name.chars().next()?;
+    let bits = name.get(1..)?;
+    if bits.starts_with('0') {
+        return None;
+    }
+    let bits = u16::from_str(bits).ok()?;
+    if bits == 0 || bits % 8 != 0 {
+        return None;
+    }
+    let bytes = bits / 8;
+    if bytes >= 0x20 && (bytes % 16 != 0 || bytes / 16 - 2 >= 0x20) {
+        return None;
+    }
+    let prim = match cls {
+        'U' => Primitive::unsigned(bytes),
+        'I' => Primitive::signed(bytes),
+        'N' => Primitive::non_zero(bytes),
+        'F' => Primitive::float(bytes),
+        _ => return None,
+    };
+    Some(Ty::Primitive(prim))
+}
+
+struct Parser<'libs> {
+    tokens: Vec<(Token, Span)>,
+    cursor: usize,
+    libs: BTreeMap<LibName, &'libs TypeLib>,
+    imports: BTreeMap<LibName, &'libs TypeLib>,
+    extern_types: BTreeMap<LibName, BTreeMap<crate::SemId, TypeName>>,
+    declared: BTreeSet<String>,
+}
+
+impl<'libs> Parser<'libs> {
+    fn peek_at(&self, offset: usize) -> &Token {
+        let pos = (self.cursor + offset).min(self.tokens.len() - 1);
+        &self.tokens[pos].0
+    }
+
+    fn peek(&self) -> &Token { self.peek_at(0) }
+
+    fn span(&self) -> Span { self.tokens[self.cursor].1 }
+
+    fn next(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.cursor].clone();
+        if self.cursor < self.tokens.len() - 1 {
+            self.cursor += 1;
+        }
+        token
+    }
+
+    fn is_punct(&self, offset: usize, punct: &str) -> bool {
+        matches!(self.peek_at(offset), Token::Punct(p) if *p == punct)
+    }
+
+    fn is_keyword(&self, offset: usize, keyword: &str) -> bool {
+        matches!(self.peek_at(offset), Token::Ident(ident) if ident == keyword)
+    }
+
+    fn error_at(&self, span: Span, kind: impl Into<ParseErrorKind>) -> ParseError {
+        ParseError {
+            span,
+            kind: kind.into(),
+        }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        self.error_at(self.span(), ParseErrorKind::Unexpected {
+            expected,
+            found: self.peek().to_string(),
+        })
+    }
+
+    fn expect_punct(&mut self, punct: &'static str) -> Result<Span, ParseError> {
+        if !self.is_punct(0, punct) {
+            return Err(self.unexpected(punct));
+        }
+        Ok(self.next().1)
+    }
+
+    fn expect_ident(&mut self, expected: &'static str) -> Result<(String, Span), ParseError> {
+        match self.next() {
+            (Token::Ident(ident), span) => Ok((ident, span)),
+            _ => {
+                self.cursor -= 1;
+                Err(self.unexpected(expected))
+            }
+        }
+    }
+
+    fn expect_name<T>(&mut self, expected: &'static str) -> Result<(T, Span), ParseError>
+    where T: TryFrom<String, Error = InvalidRString> {
+        let (ident, span) = self.expect_ident(expected)?;
+        let name = T::try_from(ident).map_err(|err| self.error_at(span, err))?;
+        Ok((name, span))
+    }
+
+    fn expect_number(&mut self, expected: &'static str) -> Result<(u64, Span), ParseError> {
+        match self.next() {
+            (Token::Number(no), span) => Ok((no, span)),
+            _ => {
+                self.cursor -= 1;
+                Err(self.unexpected(expected))
+            }
+        }
+    }
+
+    fn expect_mnemonic(&mut self) -> Result<(String, Span), ParseError> {
+        let (mut mnemonic, mut span) = self.expect_ident("checkword")?;
+        while self.is_punct(0, "-") {
+            self.next();
+            let (word, end) = self.expect_ident("checkword")?;
+            mnemonic.push('-');
+            mnemonic.push_str(&word);
+            span.end = end.end;
+        }
+        Ok((mnemonic, span))
+    }
+
+    /// Detects `data Name :` sequence, which starts a new type definition.
+    fn is_decl_start(&self, offset: usize) -> bool {
+        self.is_keyword(offset, "data")
+            && matches!(self.peek_at(offset + 1), Token::Ident(_))
+            && self.is_punct(offset + 2, ":")
+    }
+
+    fn is_ty_start(&self, offset: usize) -> bool {
+        match self.peek_at(offset) {
+            Token::Ident(_) => !self.is_decl_start(offset),
+            Token::Punct(p) => ["(", "[", "{"].contains(p),
+            _ => false,
+        }
+    }
+
+    /// Detects whether the next identifier is a field or a variant name (and not a type name).
+    fn is_name_start(&self) -> bool {
+        let Token::Ident(ident) = self.peek() else {
+            return false;
+        };
+        if ident.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return false;
+        }
+        self.is_punct(1, "#") || self.is_punct(1, "|") || self.is_ty_start(1)
+    }
+
+    fn is_empty_marker(&self) -> bool {
+        self.is_punct(0, "(") && self.is_punct(1, "|") && self.is_punct(2, ")")
+    }
+
+    fn parse(mut self, source: &str) -> Result<SymbolicLib, ParseError> {
+        self.declared = self
+            .tokens
+            .windows(3)
+            .filter_map(|w| match (&w[0].0, &w[1].0, &w[2].0) {
+                (Token::Ident(kw), Token::Ident(name), Token::Punct(":")) if kw == "data" => {
+                    Some(name.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut lib_name = None;
+        let mut mnemonic = None;
+        let mut types = BTreeMap::new();
+        let mut definitions = Vec::new();
+        loop {
+            let span = self.span();
+            match self.peek().clone() {
+                Token::Eof => break,
+                Token::Punct("@") => {
+                    self.next();
+                    let (pragma, span) = self.expect_ident("pragma name")?;
+                    match pragma.as_str() {
+                        "context" => {}
+                        "mnemonic" => {
+                            self.expect_punct("(")?;
+                            mnemonic = Some(self.expect_mnemonic()?);
+                            self.expect_punct(")")?;
+                        }
+                        _ => return Err(self.error_at(span, ParseErrorKind::UnknownPragma(pragma))),
+                    }
+                }
+                Token::Ident(kw) if kw == "typelib" => {
+                    self.next();
+                    if lib_name.is_some() {
+                        return Err(self.error_at(span, ParseErrorKind::RepeatedLibName));
+                    }
+                    lib_name = Some(self.expect_name::<LibName>("library name")?.0);
+                }
+                Token::Ident(_) if lib_name.is_none() => {
+                    return Err(self.error_at(span, ParseErrorKind::NoLibName));
+                }
+                Token::Ident(kw) if kw == "import" => {
+                    self.next();
+                    self.parse_import()?;
+                }
+                Token::Ident(kw) if kw == "data" => {
+                    self.next();
+                    let (name, name_span) = self.expect_name::<TypeName>("type name")?;
+                    self.expect_punct(":")?;
+                    let ty = self.parse_ty()?;
+                    if let Some((found, span)) = mnemonic.take() {
+                        let expected = ty.sem_id_named(&name).to_baid64_mnemonic();
+                        if found != expected {
+                            return Err(self.error_at(
+                                span,
+                                ParseErrorKind::TypeMnemonicMismatch {
+                                    name,
+                                    expected,
+                                    found,
+                                },
+                            ));
+                        }
+                    }
+                    if types.insert(name.clone(), ty).is_some() {
+                        return Err(self.error_at(name_span, ParseErrorKind::DuplicateType(name)));
+                    }
+                    definitions
+                        .push((name, Span::new(span.start, self.tokens[self.cursor - 1].1.end)));
+                }
+                _ => return Err(self.unexpected("`import` or `data` statement")),
+            }
+        }
+
+        let Some(lib_name) = lib_name else {
+            return Err(self.error_at(self.span(), ParseErrorKind::NoLibName));
+        };
+        if let Some((_, span)) = mnemonic {
+            return Err(self.error_at(span, ParseErrorKind::Unexpected {
+                expected: "type definition",
+                found: Token::Eof.to_string(),
+            }));
+        }
+
+        let known_libs = self.imports.values().map(|lib| lib.to_dependency());
+        let mut builder = LibBuilder::new(lib_name, known_libs);
+        builder.extern_types = self.extern_types;
+        builder.types = types.clone();
+        builder.compile_symbols().map_err(|err| {
+            let span = offending_definition(&err, &types, &definitions).unwrap_or_else(|| {
+                let mut end = Pos::start();
+                source.chars().for_each(|c| end.advance(c));
+                Span::new(Pos::start(), end)
+            });
+            ParseError {
+                span,
+                kind: err.into(),
+            }
+        })
+    }
+
+    fn parse_import(&mut self) -> Result<(), ParseError> {
+        let (lib_name, span) = self.expect_name::<LibName>("library name")?;
+        self.expect_punct("#")?;
+        let (found, mnemonic_span) = self.expect_mnemonic()?;
+        let Some(lib) = self.libs.get(&lib_name).copied() else {
+            return Err(self.error_at(span, ParseErrorKind::UnknownLib(lib_name)));
+        };
+        let expected = lib.id().to_baid64_mnemonic();
+        if found != expected {
+            return Err(self.error_at(mnemonic_span, ParseErrorKind::LibMnemonicMismatch {
+                lib: lib_name,
+                expected,
+                found,
+            }));
+        }
+        if self.imports.insert(lib_name.clone(), lib).is_some() {
+            return Err(self.error_at(span, ParseErrorKind::DuplicateImport(lib_name)));
+        }
+
+        while self.is_keyword(0, "use") {
+            self.next();
+            let (ty_name, span) = self.expect_name::<TypeName>("type name")?;
+            self.expect_punct("#")?;
+            let (found, mnemonic_span) = self.expect_mnemonic()?;
+            let sym = self.resolve_extern(&lib_name, ty_name, span)?;
+            let expected = sym.sem_id.to_baid64_mnemonic();
+            if found != expected {
+                return Err(self.error_at(mnemonic_span, ParseErrorKind::TypeMnemonicMismatch {
+                    name: sym.ty_name,
+                    expected,
+                    found,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_extern(
+        &mut self,
+        lib_name: &LibName,
+        ty_name: TypeName,
+        span: Span,
+    ) -> Result<SymbolRef, ParseError> {
+        let Some(lib) = self.imports.get(lib_name) else {
+            return Err(self.error_at(span, ParseErrorKind::NotImported(lib_name.clone())));
+        };
+        let Some(ty) = lib.types.get(&ty_name) else {
+            return Err(self.error_at(span, ParseErrorKind::UnknownExtern {
+                lib: lib_name.clone(),
+                name: ty_name,
+            }));
+        };
+        let sem_id = ty.sem_id_named(&ty_name);
+        let lib_id = lib.id();
+        self.extern_types.entry(lib_name.clone()).or_default().insert(sem_id, ty_name.clone());
+        Ok(SymbolRef::with(lib_name.clone(), ty_name, lib_id, sem_id))
+    }
+
+    /// Parses the right side of a type definition, or a parenthesized compound type.
+    fn parse_ty(&mut self) -> Result<Ty<TranspileRef>, ParseError> {
+        if self.is_name_start() {
+            return self.parse_named();
+        }
+        let span = self.span();
+        let mut fields = vec![self.parse_term()?];
+        while self.is_punct(0, ",") {
+            self.next();
+            fields.push(self.parse_term()?);
+        }
+        if fields.len() == 1 {
+            return newtype(fields.remove(0)).map_err(|err| self.error_at(span, err));
+        }
+        UnnamedFields::try_from(fields)
+            .map(Ty::Tuple)
+            .map_err(|_| self.error_at(span, ParseErrorKind::TooManyItems))
+    }
+
+    fn parse_variant_head(&mut self) -> Result<(VariantName, Option<u8>, Span), ParseError> {
+        let (name, span) = self.expect_name::<VariantName>("variant name")?;
+        if !self.is_punct(0, "#") {
+            return Ok((name, None, span));
+        }
+        self.next();
+        let (tag, tag_span) = self.expect_number("variant tag")?;
+        let tag = u8::try_from(tag)
+            .map_err(|_| self.error_at(tag_span, ParseErrorKind::InvalidNumber(tag.to_string())))?;
+        Ok((name, Some(tag), span))
+    }
+
+    /// Parses enum, union or struct, which start with a variant or a field name.
+    fn parse_named(&mut self) -> Result<Ty<TranspileRef>, ParseError> {
+        let (name, tag, span) = self.parse_variant_head()?;
+
+        if !self.is_ty_start(0) {
+            let mut variants = vec![(name, tag, span)];
+            while self.is_punct(0, "|") {
+                self.next();
+                if self.is_empty_marker() {
+                    self.cursor += 3;
+                    continue;
+                }
+                let head = self.parse_variant_head()?;
+                if self.is_ty_start(0) {
+                    return Err(self.error_at(head.2, ParseErrorKind::MixedVariants));
+                }
+                variants.push(head);
+            }
+            let variants = self.tag_variants(variants)?;
+            return EnumVariants::try_from(
+                variants.into_iter().map(|(v, _)| v).collect::<BTreeSet<_>>(),
+            )
+            .map(Ty::Enum)
+            .map_err(|_| self.error_at(span, ParseErrorKind::TooManyItems));
+        }
+
+        let body = self.parse_body()?;
+        if tag.is_some() || self.is_punct(0, "|") {
+            let mut variants = vec![((name, tag, span), body)];
+            while self.is_punct(0, "|") {
+                self.next();
+                if self.is_empty_marker() {
+                    self.cursor += 3;
+                    continue;
+                }
+                let head = self.parse_variant_head()?;
+                if !self.is_ty_start(0) {
+                    return Err(self.error_at(head.2, ParseErrorKind::MixedVariants));
+                }
+                variants.push((head, self.parse_body()?));
+            }
+            let (heads, bodies): (Vec<_>, Vec<_>) = variants.into_iter().unzip();
+            let mut map = BTreeMap::new();
+            for ((variant, span), body) in self.tag_variants(heads)?.into_iter().zip(bodies) {
+                let ty = body.into_variant().map_err(|err| self.error_at(span, err))?;
+                map.insert(variant, ty.into());
+            }
+            return UnionVariants::try_from(map)
+                .map(Ty::Union)
+                .map_err(|_| self.error_at(span, ParseErrorKind::TooManyItems));
+        }
+
+        let name = FieldName::try_from(name.to_string()).map_err(|err| self.error_at(span, err))?;
+        let mut names = bset![name.clone()];
+        let mut fields = vec![Field {
+            name,
+            ty: body.into_field(),
+        }];
+        while self.is_punct(0, ",") {
+            self.next();
+            let (name, span) = self.expect_name::<FieldName>("field name")?;
+            if !names.insert(name.clone()) {
+                return Err(self.error_at(span, ParseErrorKind::DuplicateName(name.to_string())));
+            }
+            let ty = self.parse_body()?.into_field();
+            fields.push(Field { name, ty });
+        }
+        NamedFields::try_from(fields)
+            .map(Ty::Struct)
+            .map_err(|_| self.error_at(span, ParseErrorKind::TooManyItems))
+    }
+
+    /// Assigns tags to the variants following the same rules as the `Display` implementations:
+    /// a tag not given explicitly is the previous one plus one.
+    fn tag_variants(
+        &self,
+        heads: Vec<(VariantName, Option<u8>, Span)>,
+    ) -> Result<Vec<(Variant, Span)>, ParseError> {
+        let mut next_tag = 0u8;
+        let mut names = BTreeSet::new();
+        let mut tags = BTreeSet::new();
+        let mut variants = Vec::with_capacity(heads.len());
+        for (name, tag, span) in heads {
+            let tag = tag.unwrap_or(next_tag);
+            next_tag = tag.saturating_add(1);
+            if !names.insert(name.clone()) {
+                return Err(self.error_at(span, ParseErrorKind::DuplicateName(name.to_string())));
+            }
+            if !tags.insert(tag) {
+                return Err(self.error_at(span, ParseErrorKind::DuplicateTag(tag)));
+            }
+            variants.push((Variant::named(tag, name), span));
+        }
+        Ok(variants)
+    }
+
+    fn parse_body(&mut self) -> Result<Body, ParseError> {
+        if self.is_punct(0, "(") && !self.is_punct(1, ")") {
+            self.next();
+            let ty = self.parse_ty()?;
+            self.expect_punct(")")?;
+            return Ok(Body::Compound(ty));
+        }
+        if self.is_name_start() {
+            let (name, span) = self.expect_name::<FieldName>("field name")?;
+            let field = Field {
+                name,
+                ty: self.parse_term()?,
+            };
+            let fields = NamedFields::try_from(vec![field])
+                .map_err(|_| self.error_at(span, ParseErrorKind::TooManyItems))?;
+            return Ok(Body::Compound(Ty::Struct(fields)));
+        }
+        self.parse_term().map(Body::Term)
+    }
+
+    fn parse_sizing(&mut self) -> Result<(Sizing, bool), ParseError> {
+        if !self.is_punct(0, "^") {
+            return Ok((Sizing::U16, false));
+        }
+        let start = self.next().1;
+        let (min, max, fixed) = if self.is_punct(0, "..") {
+            self.next();
+            (0, self.expect_number("maximal size")?.0, false)
+        } else {
+            let (min, _) = self.expect_number("size")?;
+            if self.is_punct(0, "..") {
+                self.next();
+                match self.peek() {
+                    Token::Number(_) => (min, self.expect_number("maximal size")?.0, false),
+                    _ => (min, Sizing::U16.max, false),
+                }
+            } else {
+                (min, min, true)
+            }
+        };
+        if min > max {
+            let mut span = start;
+            span.end = self.tokens[self.cursor - 1].1.end;
+            return Err(self.error_at(span, ParseErrorKind::InvalidSizing { min, max }));
+        }
+        Ok((Sizing::new(min, max), fixed))
+    }
+
+    fn parse_term(&mut self) -> Result<TranspileRef, ParseError> {
+        let (token, span) = self.next();
+        let mut r = match token {
+            Token::Punct("(") if self.is_punct(0, ")") => {
+                self.next();
+                TranspileRef::unit()
+            }
+            Token::Punct("(") => {
+                let ty = self.parse_ty()?;
+                self.expect_punct(")")?;
+                ty.into()
+            }
+            Token::Punct("[") => {
+                let item = self.parse_term()?;
+                let (sizing, fixed) = self.parse_sizing()?;
+                self.expect_punct("]")?;
+                if fixed {
+                    let len = u16::try_from(sizing.min).map_err(|_| {
+                        self.error_at(span, ParseErrorKind::ArrayTooLarge(sizing.min))
+                    })?;
+                    Ty::Array(item, len).into()
+                } else {
+                    Ty::List(item, sizing).into()
+                }
+            }
+            Token::Punct("{") => {
+                let key = self.parse_term()?;
+                if self.is_punct(0, "->") {
+                    self.next();
+                    let (sizing, _) = self.parse_sizing()?;
+                    let value = self.parse_term()?;
+                    self.expect_punct("}")?;
+                    Ty::Map(key, value, sizing).into()
+                } else {
+                    let (sizing, _) = self.parse_sizing()?;
+                    self.expect_punct("}")?;
+                    Ty::Set(key, sizing).into()
+                }
+            }
+            Token::Ident(lib) if self.is_punct(0, ".") => {
+                self.next();
+                let lib_name = LibName::try_from(lib).map_err(|err| self.error_at(span, err))?;
+                let (ty_name, name_span) = self.expect_name::<TypeName>("type name")?;
+                let span = Span::new(span.start, name_span.end);
+                TranspileRef::Extern(self.resolve_extern(&lib_name, ty_name, span)?)
+            }
+            Token::Ident(name) if self.declared.contains(&name) => TranspileRef::Named(
+                TypeName::try_from(name).map_err(|err| self.error_at(span, err))?,
+            ),
+            Token::Ident(name) => match primitive(&name) {
+                Some(ty) => ty.into(),
+                None => {
+                    let name = TypeName::try_from(name).map_err(|err| self.error_at(span, err))?;
+                    return Err(self.error_at(span, ParseErrorKind::UnknownType(name)));
+                }
+            },
+            _ => {
+                self.cursor -= 1;
+                return Err(self.unexpected("type"));
+            }
+        };
+        while self.is_punct(0, "?") {
+            let span = self.next().1;
+            let some = newtype(r).map_err(|err| self.error_at(span, err))?;
+            let variants = bmap! {
+                Variant::none() => TranspileRef::unit(),
+                Variant::some() => some.into()
+            };
+            r = Ty::Union(UnionVariants::try_from(variants).expect("two variants")).into();
+        }
+        Ok(r)
+    }
+}
+
+impl SymbolicLib {
+    /// Parses `.sty` source text, resolving imported libraries from `libs`.
+    ///
+    /// Checkwords from `import`, `use` and `@mnemonic` statements are verified against the
+    /// provided libraries and the parsed types.
+    pub fn from_source<'libs>(
+        source: &str,
+        libs: impl IntoIterator<Item = &'libs TypeLib>,
+    ) -> Result<SymbolicLib, ParseError> {
+        let lexer = Lexer {
+            src: source,
+            pos: Pos::start(),
+        };
+        let parser = Parser {
+            tokens: lexer.tokenize()?,
+            cursor: 0,
+            libs: libs.into_iter().map(|lib| (lib.name.clone(), lib)).collect(),
+            imports: empty!(),
+            extern_types: empty!(),
+            declared: empty!(),
+        };
+        parser.parse(source)
+    }
+}
+
+impl FromStr for SymbolicLib {
+    type Err = ParseError;
+
+    /// Parses `.sty` source text of a library without dependencies.
+    fn from_str(s: &str) -> Result<Self, Self::Err> { SymbolicLib::from_source(s, None) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stl::{std_stl, std_sym, strict_types_stl, strict_types_sym};
+
+    #[test]
+    fn std_roundtrip() {
+        let src = include_str!("../../stl/Std@0.1.0.sty");
+        let lib = SymbolicLib::from_str(src).unwrap();
+        assert_eq!(lib, std_sym());
+        assert!(src.ends_with(&format!("{lib}\n")));
+        assert_eq!(lib.compile().unwrap(), std_stl());
+    }
+
+    #[test]
+    fn strict_types_roundtrip() {
+        let src = include_str!("../../stl/StrictTypes@0.1.0.sty");
+        let std = std_stl();
+        let lib = SymbolicLib::from_source(src, [&std]).unwrap();
+        assert_eq!(lib, strict_types_sym());
+        assert!(src.ends_with(&format!("{lib}\n")));
+        assert_eq!(lib.compile().unwrap(), strict_types_stl());
+    }
+
+    #[test]
+    fn display_roundtrip() {
+        let src = "typelib Test
+data Array : [Byte ^ 32]
+data Fixed : [Byte ^ 32..0x20]
+data Custom : a | b#5 | c | _D#68
+data Lone : single | (|)
+data Opt : U8?, {Array -> ^ 1..0xff [Unicode ^ ..0xff]?}
+data Rec : data [Rec], next Rec?, pair (U8, [Custom])
+data Sum : unit () | tuple (U16, I128) | named (a F32, b Custom) | nested x Opt | one#9 Lone
+data Unit : ()
+";
+        let lib = SymbolicLib::from_str(src).unwrap();
+        let printed = format!("{lib:#}");
+        assert_eq!(SymbolicLib::from_str(&printed).unwrap(), lib);
+        assert_eq!(SymbolicLib::from_str(&lib.to_string()).unwrap(), lib);
+        let ty = lib.types().get(&tn!("Fixed")).unwrap();
+        assert!(matches!(ty.as_wrapped_ty(), Some(Ty::List(_, sizing)) if sizing.min == 32));
+    }
+
+    #[test]
+    fn error_span() {
+        let err = SymbolicLib::from_str("typelib Test\n\ndata Some : U8, Absent\n").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnknownType(tn!("Absent")));
+        assert_eq!(err.span.start.line, 3);
+        assert_eq!(err.span.start.col, 17);
+        assert_eq!(err.to_string(), "3:17: unknown type `Absent`.");
+
+        let err = SymbolicLib::from_str("typelib Test\n@mnemonic(wrong-words-here)\ndata A : U8\n")
+            .unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::TypeMnemonicMismatch { .. }));
+        assert_eq!((err.span.start.line, err.span.start.col), (2, 11));
+
+        let src = include_str!("../../stl/StrictTypes@0.1.0.sty");
+        let err = SymbolicLib::from_str(src).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnknownLib(libname!("Std")));
+
+        let mut src = String::from("typelib Test\n");
+        for no in 0..=u16::MAX {
+            src.push_str(&format!("data T{no} : U8\n"));
+        }
+        let err = SymbolicLib::from_str(&src).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Transpile(TranspileError::TooManyTypes));
+        assert_eq!((err.span.start.line, err.span.start.col), (0x10001, 1));
+        assert_eq!((err.span.end.line, err.span.end.col), (0x10001, 17));
+    }
+}
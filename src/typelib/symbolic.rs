@@ -157,6 +157,7 @@ impl Display for TranspileRef {
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
 #[display(doc_comments)]
+#[allow(clippy::large_enum_variant)]
 pub enum TranspileError {
     /// type `{unknown}` referenced inside `{within}` is not known.
     UnknownType {
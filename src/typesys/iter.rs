@@ -29,7 +29,7 @@ use encoding::Sizing;
 use strict_encoding::STRICT_TYPES_LIB;
 
 use crate::ast::ItemCase;
-use crate::layout::MemoryLayout;
+use crate::layout::{MemoryLayout, SizeBounds, SizeCalc};
 use crate::typesys::TypeFqn;
 use crate::{ast, SemId, SymbolicSys, Ty};
 
@@ -53,6 +53,8 @@ impl<'sys> TypeTree<'sys> {
             path: vec![],
             sys: self.sys,
             nested: vec![],
+            size: None,
+            sizes: SizeCalc::new(self.sys.as_types()),
         }
     }
 
@@ -145,14 +147,26 @@ pub struct TypeTreeIter<'sys> {
     path: Vec<(u32, ast::Iter<'sys, SemId>)>,
     sys: &'sys SymbolicSys,
     nested: Vec<NestedCase>,
+    size: Option<Option<SizeBounds>>,
+    sizes: SizeCalc<'sys>,
 }
 
 impl<'sys> Iterator for TypeTreeIter<'sys> {
     type Item = TypeInfo;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next(&mut self) -> Option<Self::Item> { self.next_sized().map(|(info, _)| info) }
+}
+
+impl<'sys> TypeTreeIter<'sys> {
+    /// Returns the next item of the type tree together with the bounds of its encoded size, if
+    /// all the types it depends on are known.
+    ///
+    /// The size is kept out of [`TypeInfo`], which is a part of the strict encoded
+    /// [`MemoryLayout`].
+    pub fn next_sized(&mut self) -> Option<(TypeInfo, Option<SizeBounds>)> {
         if let Some(ty) = self.ty {
             let fqn = self.sys.symbols.lookup(self.sem_id);
+            let size = self.sizes.bounds(self.sem_id).ok();
             self.ty = None;
 
             let mut nested = vec![];
@@ -212,6 +226,9 @@ impl<'sys> Iterator for TypeTreeIter<'sys> {
             if push {
                 self.path.push((self.depth, iter));
             }
+            // Wrappers are not reported on their own, so the item takes the size of the outermost
+            // one
+            let size = *self.size.get_or_insert(size);
             self.nested.extend(nested);
             if ret {
                 let mut item = None;
@@ -224,7 +241,8 @@ impl<'sys> Iterator for TypeTreeIter<'sys> {
                     nested: Confined::from_checked(self.nested.clone()),
                 };
                 self.nested = vec![];
-                return Some(info);
+                self.size = None;
+                return Some((info, size));
             }
         }
         loop {
@@ -234,6 +252,7 @@ impl<'sys> Iterator for TypeTreeIter<'sys> {
                 None => {
                     self.path.pop();
                     self.nested = vec![];
+                    self.size = None;
                     continue;
                 }
                 Some((id, item)) => {
@@ -242,7 +261,7 @@ impl<'sys> Iterator for TypeTreeIter<'sys> {
                         self.item = item;
                     }
                     self.ty = self.sys.get(*id);
-                    return self.next();
+                    return self.next_sized();
                 }
             }
         }
@@ -25,9 +25,11 @@ mod translate;
 mod id;
 mod symbols;
 mod iter;
+mod registry;
 
 pub use id::TypeSysId;
 pub use iter::{NestedCase, TypeInfo, TypeTree, TypeTreeIter};
+pub use registry::{LibRegistry, RegistryError};
 pub use symbols::{SymbolicSys, Symbols};
 pub use translate::{Error, SystemBuilder, TypeSymbol};
 pub use type_sys::{SymTy, TypeFqn, TypeSystem, UnknownType};
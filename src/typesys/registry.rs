@@ -0,0 +1,350 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Registry of type libraries stored in a directory, which composes type systems resolving
+//! library dependencies.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::{fs, io};
+
+use amplify::confinement::{Confined, U24 as U24MAX};
+use encoding::{LibName, StrictDeserialize};
+
+use crate::typesys::{translate, SymbolicSys, SystemBuilder};
+use crate::{Dependency, TypeLib, TypeLibId};
+
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum RegistryError {
+    /// unable to read '{path}': {err}
+    Io { path: String, err: io::Error },
+
+    /// file '{path}' doesn't contain a valid type library: {details}
+    InvalidLib { path: String, details: String },
+
+    /// file '{path}' is named after library `{expected}`, but contains library `{found}`.
+    NameMismatch {
+        path: String,
+        expected: String,
+        found: LibName,
+    },
+
+    /// library `{dependency}` required by `{dependent}` is absent in the registry.
+    AbsentDependency {
+        dependency: Dependency,
+        dependent: Dependency,
+    },
+
+    /// library `{dependency}` required by `{dependent}` is absent in the registry, which has
+    /// only other versions of this library.
+    MismatchedDependency {
+        dependency: Dependency,
+        dependent: Dependency,
+    },
+
+    /// libraries `{first}` and `{second}` are both required, but have the same name.
+    Conflict {
+        first: Dependency,
+        second: Dependency,
+    },
+
+    #[display(inner)]
+    #[from]
+    System(translate::Error),
+}
+
+/// Registry of type libraries, which indexes libraries by their ids and names and builds type
+/// systems out of the libraries and all their dependencies.
+///
+/// Libraries are loaded from `.stl` files and, with `armor` feature, from `.sta` files, which
+/// may be named after the library and its version, like `Std@0.1.0.stl`.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct LibRegistry {
+    libs: BTreeMap<TypeLibId, TypeLib>,
+    names: BTreeMap<LibName, BTreeSet<TypeLibId>>,
+    versions: BTreeMap<TypeLibId, String>,
+}
+
+impl LibRegistry {
+    pub fn new() -> Self { Self::default() }
+
+    /// Loads all type libraries from a directory. Errors in all files are reported together.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, Vec<RegistryError>> {
+        let dir = dir.as_ref();
+        let io_err = |err| {
+            vec![RegistryError::Io {
+                path: dir.display().to_string(),
+                err,
+            }]
+        };
+        let mut paths = fs::read_dir(dir)
+            .and_then(|entries| {
+                entries.map(|entry| entry.map(|entry| entry.path())).collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(io_err)?;
+        paths.sort();
+
+        let mut registry = LibRegistry::new();
+        let mut errors = vec![];
+        for path in paths {
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some("stl") => {}
+                #[cfg(feature = "armor")]
+                Some("sta") => {}
+                _ => continue,
+            }
+            if let Err(err) = registry.load_file(&path) {
+                errors.push(err);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(registry)
+    }
+
+    /// Loads type library from a file, detecting its format from the file extension. If the file
+    /// name contains library version after `@`, the version is recorded in the registry.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<TypeLibId, RegistryError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let invalid = |details: String| RegistryError::InvalidLib {
+            path: display.clone(),
+            details,
+        };
+        let data = fs::read(path).map_err(|err| RegistryError::Io {
+            path: display.clone(),
+            err,
+        })?;
+        let lib = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("stl") => {
+                let data = Confined::try_from(data).map_err(|err| invalid(err.to_string()))?;
+                TypeLib::from_strict_serialized::<U24MAX>(data)
+                    .map_err(|err| invalid(err.to_string()))?
+            }
+            // Armored libraries have their id in the header, which is verified when parsing
+            #[cfg(feature = "armor")]
+            Some("sta") => {
+                use armor::AsciiArmor;
+                let s = String::from_utf8(data).map_err(|err| invalid(err.to_string()))?;
+                TypeLib::from_ascii_armored_str(&s).map_err(|err| invalid(err.to_string()))?
+            }
+            _ => return Err(invalid(s!("unknown file format"))),
+        };
+
+        let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        let version = match stem.split_once('@') {
+            Some((name, _)) if name != lib.name.as_str() => {
+                return Err(RegistryError::NameMismatch {
+                    path: display,
+                    expected: name.to_owned(),
+                    found: lib.name,
+                })
+            }
+            Some((_, version)) => Some(version.to_owned()),
+            None => None,
+        };
+        let id = self.insert(lib);
+        if let Some(version) = version {
+            self.versions.insert(id, version);
+        }
+        Ok(id)
+    }
+
+    /// Adds type library to the registry.
+    pub fn insert(&mut self, lib: TypeLib) -> TypeLibId {
+        let id = lib.id();
+        self.names.entry(lib.name.clone()).or_default().insert(id);
+        self.libs.insert(id, lib);
+        id
+    }
+
+    pub fn get(&self, id: TypeLibId) -> Option<&TypeLib> { self.libs.get(&id) }
+
+    /// Version of the library, if it was given in the name of the library file.
+    pub fn version(&self, id: TypeLibId) -> Option<&str> {
+        self.versions.get(&id).map(String::as_str)
+    }
+
+    /// Returns all libraries with the given name.
+    pub fn by_name<'a>(&'a self, name: &LibName) -> impl Iterator<Item = &'a TypeLib> + 'a {
+        self.names.get(name).into_iter().flatten().filter_map(|id| self.libs.get(id))
+    }
+
+    /// Finds library with the given name and version.
+    pub fn find(&self, name: &LibName, version: &str) -> Option<&TypeLib> {
+        self.by_name(name).find(|lib| self.version(lib.id()) == Some(version))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TypeLib> { self.libs.values() }
+
+    /// Resolves all libraries which the root library depends on, directly or transitively.
+    ///
+    /// Returns the libraries ordered such that each library follows its dependencies, and ends
+    /// with the root library, which is not required to be a part of the registry. Absent and
+    /// conflicting libraries are reported together.
+    pub fn resolve<'a>(
+        &'a self,
+        root: &'a TypeLib,
+    ) -> Result<Vec<&'a TypeLib>, Vec<RegistryError>> {
+        let mut resolver = Resolver {
+            registry: self,
+            visited: BTreeMap::new(),
+            libs: vec![],
+            errors: vec![],
+        };
+        resolver.visit(root);
+        if !resolver.errors.is_empty() {
+            return Err(resolver.errors);
+        }
+        Ok(resolver.libs)
+    }
+
+    /// Builds type system out of the root library and all its dependencies.
+    pub fn build(&self, root: &TypeLib) -> Result<SymbolicSys, Vec<RegistryError>> {
+        let mut builder = SystemBuilder::new();
+        for lib in self.resolve(root)? {
+            builder = builder.import(lib.clone()).map_err(|err| vec![err.into()])?;
+        }
+        builder.finalize().map_err(|errors| errors.into_iter().map(RegistryError::from).collect())
+    }
+}
+
+struct Resolver<'a> {
+    registry: &'a LibRegistry,
+    /// Libraries which were already visited, indexed by their names.
+    visited: BTreeMap<LibName, Dependency>,
+    libs: Vec<&'a TypeLib>,
+    errors: Vec<RegistryError>,
+}
+
+impl<'a> Resolver<'a> {
+    fn visit(&mut self, lib: &'a TypeLib) {
+        let dependent = lib.to_dependency();
+        self.visited.insert(lib.name.clone(), dependent.clone());
+        for dependency in &lib.dependencies {
+            match self.visited.get(&dependency.name) {
+                Some(visited) if visited.id == dependency.id => continue,
+                Some(visited) => {
+                    self.errors.push(RegistryError::Conflict {
+                        first: visited.clone(),
+                        second: dependency.clone(),
+                    });
+                    continue;
+                }
+                None => {}
+            }
+            match self.registry.get(dependency.id) {
+                Some(dep) => self.visit(dep),
+                None => {
+                    let dependency = dependency.clone();
+                    let dependent = dependent.clone();
+                    self.errors.push(if self.registry.names.contains_key(&dependency.name) {
+                        RegistryError::MismatchedDependency {
+                            dependency,
+                            dependent,
+                        }
+                    } else {
+                        RegistryError::AbsentDependency {
+                            dependency,
+                            dependent,
+                        }
+                    });
+                }
+            }
+        }
+        self.libs.push(lib);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stl::{std_stl, strict_types_stl};
+    use crate::value::test_helpers::lib_from_source;
+
+    #[test]
+    fn open() {
+        let registry =
+            LibRegistry::open(Path::new(env!("CARGO_MANIFEST_DIR")).join("stl")).unwrap();
+        let std = std_stl();
+        let st = strict_types_stl();
+        assert_eq!(registry.iter().count(), 2);
+        assert_eq!(registry.get(st.id()), Some(&st));
+        assert_eq!(registry.version(std.id()), Some("0.1.0"));
+        assert_eq!(registry.find(&std.name, "0.1.0"), Some(&std));
+        assert_eq!(registry.find(&std.name, "0.2.0"), None);
+
+        let libs = registry.resolve(&st).unwrap();
+        assert_eq!(libs, [&std, &st]);
+        let sys = registry.build(&st).unwrap();
+        let expected = SystemBuilder::new().import(std).unwrap().import(st).unwrap().finalize();
+        assert_eq!(sys, expected.unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let std = std_stl();
+        let other_std = lib_from_source("typelib Std\ndata Other : U8\n");
+        let mut dependent = lib_from_source("typelib Dependent\ndata Dep : U16\n");
+        let mut registry = LibRegistry::new();
+        registry.insert(dependent.clone());
+
+        let mut root = lib_from_source("typelib Root\ndata Root : U32\n");
+        root.dependencies.push(std.to_dependency()).unwrap();
+        root.dependencies.push(dependent.to_dependency()).unwrap();
+        let err = registry.build(&root).unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert_eq!(
+            err[0].to_string(),
+            format!(
+                "library `{}` required by `{}` is absent in the registry.",
+                std.to_dependency(),
+                root.to_dependency()
+            )
+        );
+
+        registry.insert(other_std.clone());
+        let err = registry.resolve(&root).unwrap_err();
+        let [RegistryError::MismatchedDependency { dependency, .. }] = &err[..] else {
+            panic!("mismatched dependency is not detected: {err:?}")
+        };
+        assert_eq!(dependency.id, std.id());
+
+        // Dependent library requires other version of a library than the root does
+        registry.insert(std.clone());
+        dependent.dependencies.push(other_std.to_dependency()).unwrap();
+        registry.insert(dependent.clone());
+        root.dependencies = none!();
+        root.dependencies.push(std.to_dependency()).unwrap();
+        root.dependencies.push(dependent.to_dependency()).unwrap();
+        let err = registry.resolve(&root).unwrap_err();
+        let [RegistryError::Conflict { first, second }] = &err[..] else {
+            panic!("conflict is not detected: {err:?}")
+        };
+        assert_eq!(bset![first.id, second.id], bset![std.id(), other_std.id()]);
+
+        let err = LibRegistry::open(Path::new(env!("CARGO_MANIFEST_DIR")).join("absent"));
+        assert!(matches!(&err.unwrap_err()[..], [RegistryError::Io { .. }]));
+    }
+}
@@ -25,10 +25,11 @@ use std::fmt::{self, Display, Formatter};
 use std::ops::Index;
 
 use amplify::confinement::{self, MediumOrdSet, SmallOrdSet};
+use baid64::DisplayBaid64;
 use encoding::{StrictDeserialize, StrictSerialize, STRICT_TYPES_LIB};
 
 use crate::typesys::{translate, SymTy, TypeFqn, TypeSymbol, TypeSysId, TypeTree};
-use crate::typify::TypeSpec;
+use crate::typify::{SpecError, TypeSpec};
 use crate::{Dependency, SemId, Translate, Ty, TypeSystem};
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -80,6 +81,8 @@ impl Symbols {
     pub fn lookup(&self, sem_id: SemId) -> Option<&TypeFqn> {
         self.symbols.iter().find(|sym| sym.id == sem_id).and_then(|sym| sym.fqn.as_ref())
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TypeSymbol> { self.symbols.iter() }
 }
 
 impl Index<&'static str> for Symbols {
@@ -158,9 +161,43 @@ impl SymbolicSys {
     pub fn lookup(&self, sem_id: SemId) -> Option<&TypeFqn> { self.symbols.lookup(sem_id) }
 
     pub fn to_sem_id(&self, spec: impl Into<TypeSpec>) -> Option<SemId> {
-        match spec.into() {
-            TypeSpec::SemId(sem_id) => Some(sem_id),
-            TypeSpec::Fqn(fqn) => self.resolve(fqn).copied(),
+        self.resolve_spec(spec).ok()
+    }
+
+    /// Resolves type specification into a semantic id, verifying that the checkword of a type
+    /// name matches the type semantic id, and that a semantic id prefix matches a single type.
+    /// Complete semantic ids are returned as is.
+    pub fn resolve_spec(&self, spec: impl Into<TypeSpec>) -> Result<SemId, SpecError> {
+        let spec = spec.into();
+        match &spec {
+            TypeSpec::SemId(sem_id) => Ok(*sem_id),
+            TypeSpec::SemIdPrefix(prefix) => {
+                let mut found = self.types.as_unconfined().keys().filter(|sem_id| {
+                    format!("{sem_id:-#}").replace('-', "").starts_with(prefix.as_str())
+                });
+                match (found.next(), found.count()) {
+                    (None, _) => Err(SpecError::TypeAbsent(spec)),
+                    (Some(sem_id), 0) => Ok(*sem_id),
+                    (Some(_), count) => Err(SpecError::AmbiguousPrefix {
+                        prefix: prefix.clone(),
+                        count: count + 1,
+                    }),
+                }
+            }
+            TypeSpec::Fqn(fqn, checkword) => {
+                let sem_id =
+                    *self.resolve(fqn.clone()).ok_or(SpecError::TypeAbsent(spec.clone()))?;
+                match checkword {
+                    Some(found) if *found != sem_id.to_baid64_mnemonic() => {
+                        Err(SpecError::CheckwordMismatch {
+                            fqn: fqn.clone(),
+                            sem_id,
+                            found: found.clone(),
+                        })
+                    }
+                    _ => Ok(sem_id),
+                }
+            }
         }
     }
 
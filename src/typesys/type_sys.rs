@@ -37,7 +37,7 @@ use crate::{SemId, Ty};
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, Error)]
 #[display("type with id `{0}` is not a part of the type system.")]
-pub struct UnknownType(SemId);
+pub struct UnknownType(pub(crate) SemId);
 
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
 #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
@@ -157,7 +157,7 @@ impl TypeSystem {
         fields: &UnnamedFields<SemId>,
     ) -> Result<Option<(SemId, Sizing)>, UnknownType> {
         let rest = fields[1];
-        let rest = self.find(rest).ok_or(UnknownType(rest))?;
+        let rest = self.get(rest).ok_or(UnknownType(rest))?;
         if let Ty::List(rest, sizing) = rest {
             let mut sizing = *sizing;
             sizing.min += 1;
@@ -176,8 +176,8 @@ impl TypeSystem {
             return Ok(false);
         };
 
-        Ok(self.find(first).ok_or(UnknownType(first))?.is_char_enum()
-            && self.find(rest).ok_or(UnknownType(rest))?.is_char_enum())
+        Ok(self.get(first).ok_or(UnknownType(first))?.is_char_enum()
+            && self.get(rest).ok_or(UnknownType(rest))?.is_char_enum())
     }
 }
 
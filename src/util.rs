@@ -25,11 +25,31 @@ use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
 use amplify::confinement::TinyVec;
-use strict_encoding::{Ident, STRICT_TYPES_LIB};
+use amplify::hex::FromHex;
+use amplify::num::u24;
+use baid64::{Baid64ParseError, DisplayBaid64, FromBaid64Str};
+use strict_encoding::{Ident, Sizing, STRICT_TYPES_LIB};
 
 use crate::typelib::TypeLibId;
 use crate::SemId;
 
+pub(crate) trait SizingExt {
+    /// Number of bytes used by strict encoding for the length prefix of a collection.
+    fn byte_size(&self) -> usize;
+}
+
+impl SizingExt for Sizing {
+    fn byte_size(&self) -> usize {
+        match self.max {
+            one if one <= u8::MAX as u64 => 1,
+            two if two <= u16::MAX as u64 => 2,
+            three if three <= u24::MAX.into_u64() => 3,
+            four if four <= u32::MAX as u64 => 4,
+            _ => 8,
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
 #[display("unknown name for the file format '{0}'")]
 pub struct UnknownFormat(String);
@@ -71,59 +91,49 @@ pub fn parse_args() -> (StlFormat, Option<String>) {
     (format, dir)
 }
 
-/* TODO: Move into layout mod
-/// Measure of a type size in bytes
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Display)]
-pub enum Size {
-    /// Type has a fixed size known at compile time
-    #[display(inner)]
-    Fixed(u16),
-
-    /// Type has variable size
-    #[display("variable")]
-    Variable,
-}
-
-impl PartialOrd for Size {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+/// Position inside a source text.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[display("{line}:{col}")]
+pub struct Pos {
+    /// Byte offset from the start of the text.
+    pub offset: usize,
+    /// Line number, starting from 1.
+    pub line: usize,
+    /// Column number (in characters), starting from 1.
+    pub col: usize,
 }
 
-impl Ord for Size {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match (self, other) {
-            (Size::Variable, Size::Variable) => Ordering::Equal,
-            (Size::Variable, _) => Ordering::Greater,
-            (_, Size::Variable) => Ordering::Less,
-            (Size::Fixed(a), Size::Fixed(b)) => a.cmp(b),
+impl Pos {
+    pub fn start() -> Pos {
+        Pos {
+            offset: 0,
+            line: 1,
+            col: 1,
         }
     }
-}
-
-impl Add for Size {
-    type Output = Size;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Size::Fixed(a), Size::Fixed(b)) => Size::Fixed(a + b),
-            _ => Size::Variable,
+    pub(crate) fn advance(&mut self, c: char) {
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
         }
     }
 }
 
-impl AddAssign for Size {
-    fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+/// Range of a source text, used in parse error reporting.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[display("{start}")]
+pub struct Span {
+    pub start: Pos,
+    pub end: Pos,
 }
 
-impl Sum for Size {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        let mut acc = Size::Fixed(0);
-        for item in iter {
-            acc += item;
-        }
-        acc
-    }
+impl Span {
+    pub fn new(start: Pos, end: Pos) -> Span { Span { start, end } }
 }
- */
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Display, From)]
 #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
@@ -200,6 +210,12 @@ impl Display for SemVer {
     }
 }
 
+/// Identifier of a type library or a type.
+///
+/// Parsing accepts URNs (`urn:sten:lib:...` and `urn:sten:id:...`) as well as bare `stl:...` and
+/// `semid:...` identifiers. Identifiers may be given in Baid64, with or without a checksum and a
+/// mnemonic, or in hex. Identifiers without a prefix are accepted only if their checksum or
+/// mnemonic tells whether they identify a library or a type.
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, From)]
 pub enum Urn {
     #[from]
@@ -210,3 +226,118 @@ pub enum Urn {
     #[display("urn:sten:id:{0}", alt = "urn:sten:id:{0:#}")]
     Type(SemId),
 }
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum UrnParseError {
+    /// unsupported URN `{0}`; only `urn:sten:lib:` and `urn:sten:id:` URNs are known.
+    UnknownNamespace(String),
+
+    /// identifier `{0}` may belong both to a library and a type; use `stl:`, `semid:` or URN
+    /// prefix to distinguish them.
+    Ambiguous(String),
+
+    /// invalid identifier `{0}`: {1}
+    Invalid(String, String),
+}
+
+impl FromStr for Urn {
+    type Err = UrnParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = s.strip_prefix("urn:") {
+            return if let Some(id) = rest.strip_prefix("sten:lib:") {
+                parse_id(s, id).map(Urn::Lib)
+            } else if let Some(id) = rest.strip_prefix("sten:id:") {
+                parse_id(s, id).map(Urn::Type)
+            } else {
+                Err(UrnParseError::UnknownNamespace(s.to_owned()))
+            };
+        }
+        if s.starts_with("stl:") {
+            return parse_id(s, s).map(Urn::Lib);
+        }
+        if s.starts_with("semid:") {
+            return parse_id(s, s).map(Urn::Type);
+        }
+        if is_hex_id(s) {
+            return Err(UrnParseError::Ambiguous(s.to_owned()));
+        }
+        // Checksums and mnemonics commit to the identifier prefix, so only one of the parsers
+        // accepts identifiers which have them
+        match (TypeLibId::from_baid64_str(s), SemId::from_baid64_str(s)) {
+            (Ok(id), Err(_)) => Ok(Urn::Lib(id)),
+            (Err(_), Ok(id)) => Ok(Urn::Type(id)),
+            (Ok(_), Ok(_)) => Err(UrnParseError::Ambiguous(s.to_owned())),
+            (Err(err), Err(_)) => Err(UrnParseError::Invalid(s.to_owned(), err.to_string())),
+        }
+    }
+}
+
+fn is_hex_id(s: &str) -> bool { s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()) }
+
+/// Parses identifier given in Baid64 or hex, which may be prefixed with its human-readable part.
+fn parse_id<Id>(orig: &str, id: &str) -> Result<Id, UrnParseError>
+where Id: FromStr<Err = Baid64ParseError> + DisplayBaid64 + FromHex {
+    let invalid = |err: String| UrnParseError::Invalid(orig.to_owned(), err);
+    let hex = id.rsplit_once(':').map(|(_, hex)| hex).unwrap_or(id);
+    if is_hex_id(hex) {
+        if id != hex && !id.starts_with(&format!("{}:", Id::HRI)) {
+            return Err(invalid(format!("`{}` prefix is expected", Id::HRI)));
+        }
+        return Id::from_hex(hex).map_err(|err| invalid(err.to_string()));
+    }
+    Id::from_str(id).map_err(|err| invalid(err.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::ToHex;
+
+    use super::*;
+    use crate::stl::strict_types_stl;
+    use crate::Ty;
+
+    #[test]
+    fn urn_parse() {
+        let lib_id = strict_types_stl().id();
+        let sem_id = Ty::<SemId>::UNICODE.sem_id_unnamed();
+
+        for urn in [Urn::Lib(lib_id), Urn::Type(sem_id)] {
+            assert_eq!(Urn::from_str(&urn.to_string()), Ok(urn));
+            assert_eq!(Urn::from_str(&format!("{urn:#}")), Ok(urn));
+        }
+        for s in [
+            lib_id.to_string(),
+            format!("{lib_id:#}"),
+            format!("{lib_id:-}"),
+            format!("stl:{}", lib_id.to_hex()),
+            format!("urn:sten:lib:{}", lib_id.to_hex()),
+        ] {
+            assert_eq!(Urn::from_str(&s), Ok(Urn::Lib(lib_id)), "{s}");
+        }
+        for s in [
+            sem_id.to_string(),
+            format!("{sem_id:#}"),
+            format!("{sem_id:-}"),
+            format!("semid:{}", sem_id.to_hex()),
+            format!("urn:sten:id:{}", sem_id.to_hex()),
+        ] {
+            assert_eq!(Urn::from_str(&s), Ok(Urn::Type(sem_id)), "{s}");
+        }
+
+        for s in [format!("{sem_id:-#}"), sem_id.to_hex()] {
+            assert_eq!(Urn::from_str(&s), Err(UrnParseError::Ambiguous(s.clone())));
+        }
+        let s = s!("urn:isbn:0451450523");
+        assert_eq!(Urn::from_str(&s), Err(UrnParseError::UnknownNamespace(s.clone())));
+        let s = format!("urn:sten:lib:{sem_id}");
+        assert!(matches!(Urn::from_str(&s), Err(UrnParseError::Invalid(..))));
+        let s = format!("urn:sten:id:stl:{}", sem_id.to_hex());
+        assert_eq!(
+            Urn::from_str(&s),
+            Err(UrnParseError::Invalid(s.clone(), s!("`semid` prefix is expected")))
+        );
+        assert!(matches!(Urn::from_str("semid:"), Err(UrnParseError::Invalid(..))));
+    }
+}
@@ -22,67 +22,1078 @@
 
 //! Converts strict values from/to non-STON value serialization formats (JSON, YAML, TOML etc).
 
-use crate::StrictVal;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
-impl From<serde_json::Value> for StrictVal {
-    fn from(json: serde_json::Value) -> Self {
+use amplify::confinement::TinyString;
+use amplify::hex::{FromHex, ToHex};
+use amplify::num::apfloat::Status;
+use base64::Engine;
+use encoding::{FieldName, Primitive, Sizing, VariantName};
+
+use super::typify::{self, SpecError, TypeSpec, TypedVal};
+use super::{float, EnumTag, KeyStep, Path, Step, StrictNum};
+use crate::typesys::TypeSymbol;
+use crate::{SemId, StrictVal, SymbolicSys, Ty, TypeRef, TypeSystem};
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum Error {
+    /// number `{0}` can't be represented as a strict value.
+    Number(String),
+
+    /// TOML datetime `{0}` can't be represented as a strict value.
+    Datetime(String),
+}
+
+impl TryFrom<serde_json::Value> for StrictVal {
+    type Error = Error;
+
+    fn try_from(json: serde_json::Value) -> Result<Self, Self::Error> {
         use serde_json::Value;
 
-        match json {
+        Ok(match json {
             Value::Null => StrictVal::Unit,
             Value::Bool(v) => StrictVal::bool(v),
-            Value::Number(no) if no.is_u64() => StrictVal::num(no.as_u64().unwrap()),
-            Value::Number(no) if no.is_i64() => StrictVal::num(no.as_i64().unwrap()),
-            Value::Number(no) if no.is_f64() => todo!(),
-            Value::Number(_) => {
-                unreachable!()
-            }
+            Value::Number(no) => match (no.as_u64(), no.as_i64(), no.as_f64()) {
+                (Some(n), _, _) => StrictVal::num(n),
+                (None, Some(n), _) => StrictVal::num(n),
+                (None, None, Some(f)) => StrictVal::num(f),
+                (None, None, None) => return Err(Error::Number(no.to_string())),
+            },
             Value::String(s) => StrictVal::String(s),
-            Value::Array(vec) => StrictVal::list(vec.into_iter().map(StrictVal::from)),
-            Value::Object(map) => {
-                StrictVal::map(map.into_iter().map(|(k, v)| (k, StrictVal::from(v))))
+            Value::Array(vec) => {
+                StrictVal::List(vec.into_iter().map(StrictVal::try_from).collect::<Result<_, _>>()?)
             }
-        }
+            Value::Object(map) => StrictVal::Map(
+                map.into_iter()
+                    .map(|(k, v)| Ok((StrictVal::String(k), StrictVal::try_from(v)?)))
+                    .collect::<Result<_, Error>>()?,
+            ),
+        })
     }
 }
 
-impl From<serde_yaml::Value> for StrictVal {
-    fn from(yaml: serde_yaml::Value) -> Self {
+impl TryFrom<serde_yaml::Value> for StrictVal {
+    type Error = Error;
+
+    fn try_from(yaml: serde_yaml::Value) -> Result<Self, Self::Error> {
         use serde_yaml::Value;
 
-        match yaml {
+        Ok(match yaml {
             Value::Null => StrictVal::Unit,
             Value::Bool(v) => StrictVal::bool(v),
-            Value::Number(no) if no.is_u64() => StrictVal::num(no.as_u64().unwrap()),
-            Value::Number(no) if no.is_i64() => StrictVal::num(no.as_i64().unwrap()),
-            Value::Number(no) if no.is_f64() => todo!(),
-            Value::Number(_) => {
-                unreachable!()
-            }
+            Value::Number(no) => match (no.as_u64(), no.as_i64(), no.as_f64()) {
+                (Some(n), _, _) => StrictVal::num(n),
+                (None, Some(n), _) => StrictVal::num(n),
+                (None, None, Some(f)) => StrictVal::num(f),
+                (None, None, None) => return Err(Error::Number(no.to_string())),
+            },
             Value::String(s) => StrictVal::String(s),
-            Value::Sequence(vec) => StrictVal::list(vec.into_iter().map(StrictVal::from)),
-            Value::Mapping(map) => {
-                StrictVal::map(map.into_iter().map(|(k, v)| (k, StrictVal::from(v))))
+            Value::Sequence(vec) => {
+                StrictVal::List(vec.into_iter().map(StrictVal::try_from).collect::<Result<_, _>>()?)
             }
-            Value::Tagged(tagged) => StrictVal::from(tagged.value),
-        }
+            Value::Mapping(map) => StrictVal::Map(
+                map.into_iter()
+                    .map(|(k, v)| Ok((StrictVal::try_from(k)?, StrictVal::try_from(v)?)))
+                    .collect::<Result<_, Error>>()?,
+            ),
+            Value::Tagged(tagged) => StrictVal::try_from(tagged.value)?,
+        })
     }
 }
 
-impl From<toml::Value> for StrictVal {
-    fn from(toml: toml::Value) -> Self {
+impl TryFrom<toml::Value> for StrictVal {
+    type Error = Error;
+
+    fn try_from(toml: toml::Value) -> Result<Self, Self::Error> {
         use toml::Value;
 
-        match toml {
+        Ok(match toml {
             Value::Integer(no) => StrictVal::num(no),
-            Value::Float(_f) => todo!(),
+            Value::Float(f) => StrictVal::num(f),
             Value::Boolean(v) => StrictVal::bool(v),
             Value::String(s) => StrictVal::String(s),
-            Value::Array(vec) => StrictVal::list(vec.into_iter().map(StrictVal::from)),
-            Value::Table(map) => {
-                StrictVal::map(map.into_iter().map(|(k, v)| (k, StrictVal::from(v))))
+            Value::Array(vec) => {
+                StrictVal::List(vec.into_iter().map(StrictVal::try_from).collect::<Result<_, _>>()?)
             }
-            Value::Datetime(_dt) => todo!(),
+            Value::Table(map) => StrictVal::Map(
+                map.into_iter()
+                    .map(|(k, v)| Ok((StrictVal::String(k), StrictVal::try_from(v)?)))
+                    .collect::<Result<_, Error>>()?,
+            ),
+            Value::Datetime(dt) => return Err(Error::Datetime(dt.to_string())),
+        })
+    }
+}
+
+/// Error converting JSON value into a strict value of a specific type, indicating the path to
+/// the JSON node which has failed the conversion.
+#[derive(Clone, Eq, PartialEq, Debug, Error)]
+pub struct ImportError {
+    pub path: Path,
+    pub kind: ImportErrorKind,
+}
+
+impl ImportError {
+    fn at(path: &Path, kind: impl Into<ImportErrorKind>) -> Self {
+        ImportError {
+            path: path.clone(),
+            kind: kind.into(),
         }
     }
 }
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            Display::fmt(&self.kind, f)
+        } else {
+            write!(f, "value at `{}`: {}", self.path, self.kind)
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum ImportErrorKind {
+    /// unknown type `{0}`.
+    TypeAbsent(TypeSpec),
+
+    #[display(inner)]
+    #[from]
+    Spec(SpecError),
+
+    /// expected {expected}, but {found} was found.
+    Mismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+
+    /// invalid number `{0}`.
+    InvalidNumber(String),
+
+    /// string `{0}` is neither hex- nor base64-encoded byte string.
+    InvalidBytes(String),
+
+    /// collection has size {0} which is out of type required bounds {1}.
+    OutOfBounds(usize, Sizing),
+
+    /// type requires {expected} fields, while {found} fields were given.
+    FieldNumberMismatch { expected: usize, found: usize },
+
+    /// missing field `{0}`.
+    MissingField(FieldName),
+
+    /// unexpected field `{0}`.
+    ExtraField(String),
+
+    /// unknown union variant `{0}`.
+    UnknownVariant(String),
+
+    /// union value must be either a unit variant name or an object with a single variant name
+    /// key.
+    InvalidUnion,
+
+    /// map entry must be a two-element array of a key and a value.
+    InvalidMapEntry,
+
+    /// repeated value in a set.
+    RepeatedSetValue,
+
+    /// repeated map key.
+    RepeatedKeyValue,
+
+    #[display(inner)]
+    #[from]
+    Typify(typify::Error),
+}
+
+fn json_kind(json: &serde_json::Value) -> &'static str {
+    use serde_json::Value;
+
+    match json {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn ty_kind(ty: &Ty<SemId>) -> &'static str {
+    match ty {
+        Ty::Primitive(_) => "number",
+        Ty::UnicodeChar => "string",
+        Ty::Enum(_) => "enum variant name",
+        Ty::Union(_) => "union variant",
+        Ty::Tuple(_) | Ty::Array(_, _) | Ty::List(_, _) | Ty::Set(_, _) => "array",
+        Ty::Struct(_) => "object",
+        Ty::Map(_, _, _) => "object or array of key-value pairs",
+    }
+}
+
+fn decode_bytes(s: &str) -> Option<Vec<u8>> {
+    let hex = s.strip_prefix("0x").unwrap_or(s);
+    Vec::<u8>::from_hex(hex)
+        .ok()
+        .or_else(|| base64::engine::general_purpose::STANDARD.decode(s).ok())
+}
+
+fn check_len(path: &Path, len: usize, sizing: Sizing) -> Result<(), ImportError> {
+    if !sizing.check(len) {
+        return Err(ImportError::at(path, ImportErrorKind::OutOfBounds(len, sizing)));
+    }
+    Ok(())
+}
+
+impl SymbolicSys {
+    /// Converts JSON value into a strict value of the provided type, using the type information
+    /// to interpret each of the JSON nodes.
+    ///
+    /// Byte arrays and lists may be given as hex or base64-encoded strings; unions - as a name
+    /// of a unit variant or an object with a single variant name key; options - as `null` or a
+    /// value; maps - as objects or arrays of key-value pairs.
+    pub fn from_json(
+        &self,
+        spec: impl Into<TypeSpec>,
+        json: &serde_json::Value,
+    ) -> Result<TypedVal, ImportError> {
+        let mut path = Path::new();
+        let sem_id = self
+            .resolve_spec(spec)
+            .map_err(|err| ImportError::at(&path, err.report(ImportErrorKind::TypeAbsent)))?;
+        let val = self.as_types().import_json(sem_id, json, &mut path)?;
+        Ok(TypedVal {
+            orig: TypeSymbol::unnamed(sem_id),
+            val,
+        })
+    }
+}
+
+impl TypeSystem {
+    fn import_nested(
+        &self,
+        sem_id: SemId,
+        json: &serde_json::Value,
+        path: &mut Path,
+        step: Step,
+    ) -> Result<StrictVal, ImportError> {
+        let pushed = path.push(step).is_ok();
+        let val = self.import_json(sem_id, json, path)?;
+        if pushed {
+            path.pop();
+        }
+        Ok(val)
+    }
+
+    fn import_leaf(
+        &self,
+        sem_id: SemId,
+        json: &serde_json::Value,
+        path: &Path,
+    ) -> Result<StrictVal, ImportError> {
+        let val = StrictVal::try_from(json.clone())
+            .map_err(|_| ImportError::at(path, ImportErrorKind::InvalidNumber(json.to_string())))?;
+        self.typify(val, sem_id).map(TypedVal::unbox).map_err(|err| ImportError::at(path, err))
+    }
+
+    fn import_items(
+        &self,
+        sem_id: SemId,
+        items: &[serde_json::Value],
+        path: &mut Path,
+    ) -> Result<Vec<StrictVal>, ImportError> {
+        items
+            .iter()
+            .enumerate()
+            .map(|(no, item)| self.import_nested(sem_id, item, path, Step::Index(no as u32)))
+            .collect()
+    }
+
+    fn import_bytes(
+        &self,
+        sem_id: SemId,
+        items: &[serde_json::Value],
+        path: &mut Path,
+    ) -> Result<StrictVal, ImportError> {
+        let mut bytes = Vec::with_capacity(items.len());
+        for (no, item) in items.iter().enumerate() {
+            let byte = item.as_u64().and_then(|byte| u8::try_from(byte).ok()).ok_or_else(|| {
+                let _ = path.push(Step::Index(no as u32));
+                ImportError::at(path, ImportErrorKind::InvalidNumber(item.to_string()))
+            })?;
+            bytes.push(byte);
+        }
+        self.typify(StrictVal::bytes(bytes), sem_id)
+            .map(TypedVal::unbox)
+            .map_err(|err| ImportError::at(path, err))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn import_map_entry(
+        &self,
+        key_id: SemId,
+        val_id: SemId,
+        key: &serde_json::Value,
+        val: &serde_json::Value,
+        path: &mut Path,
+        step: Step,
+        entries: &mut Vec<(StrictVal, StrictVal)>,
+    ) -> Result<(), ImportError> {
+        let key = self.import_nested(key_id, key, path, step.clone())?;
+        let Err(pos) = entries.binary_search_by(|(probe, _)| self.cmp_vals(probe, &key, key_id))
+        else {
+            let _ = path.push(step);
+            return Err(ImportError::at(path, ImportErrorKind::RepeatedKeyValue));
+        };
+        let step = KeyStep::with_val(&key).map(Step::Key).unwrap_or(step);
+        let val = self.import_nested(val_id, val, path, step)?;
+        entries.insert(pos, (key, val));
+        Ok(())
+    }
+
+    fn import_json(
+        &self,
+        sem_id: SemId,
+        json: &serde_json::Value,
+        path: &mut Path,
+    ) -> Result<StrictVal, ImportError> {
+        use serde_json::Value;
+
+        let ty = self
+            .find(sem_id)
+            .ok_or_else(|| ImportError::at(path, ImportErrorKind::TypeAbsent(sem_id.into())))?;
+        let mismatch = |path: &Path| {
+            ImportError::at(path, ImportErrorKind::Mismatch {
+                expected: ty_kind(ty),
+                found: json_kind(json),
+            })
+        };
+
+        Ok(match (ty, json) {
+            (Ty::Primitive(_), Value::Null) => self.import_leaf(sem_id, json, path)?,
+            // Large numbers which can't be represented by JSON numbers
+            (Ty::Primitive(_), Value::String(s)) => match StrictVal::from_str(s) {
+                // `inf` and `nan` are parsed as enum variant names
+                Ok(val @ (StrictVal::Number(_) | StrictVal::Enum(_))) => self
+                    .typify(val, sem_id)
+                    .map(TypedVal::unbox)
+                    .map_err(|err| ImportError::at(path, err))?,
+                _ => return Err(ImportError::at(path, ImportErrorKind::InvalidNumber(s.clone()))),
+            },
+            (Ty::Primitive(_), Value::Number(_))
+            | (Ty::UnicodeChar, Value::String(_))
+            | (Ty::Enum(_), Value::String(_) | Value::Number(_) | Value::Bool(_)) => {
+                self.import_leaf(sem_id, json, path)?
+            }
+
+            (Ty::Union(_), Value::Null) if ty.is_option() => StrictVal::none(),
+            (Ty::Union(_), _) if ty.is_option() => {
+                let inner = *ty.as_some().expect("checked option");
+                StrictVal::some(self.import_json(inner, json, path)?)
+            }
+            (Ty::Union(variants), Value::String(name)) => {
+                let vname = VariantName::try_from(name.clone()).ok();
+                let Some(id) = vname.as_ref().and_then(|vname| variants.ty_by_name(vname)) else {
+                    return Err(ImportError::at(
+                        path,
+                        ImportErrorKind::UnknownVariant(name.clone()),
+                    ));
+                };
+                let content = self.import_json(*id, &Value::Null, path)?;
+                StrictVal::union(vname.expect("checked variant"), content)
+            }
+            (Ty::Union(variants), Value::Object(obj)) if obj.len() == 1 => {
+                let (name, val) = obj.iter().next().expect("checked length");
+                let vname = VariantName::try_from(name.clone()).ok();
+                let Some(id) = vname.as_ref().and_then(|vname| variants.ty_by_name(vname)) else {
+                    return Err(ImportError::at(
+                        path,
+                        ImportErrorKind::UnknownVariant(name.clone()),
+                    ));
+                };
+                let content = self.import_json(*id, val, path)?;
+                StrictVal::union(vname.expect("checked variant"), content)
+            }
+            (Ty::Union(_), _) => return Err(ImportError::at(path, ImportErrorKind::InvalidUnion)),
+
+            (Ty::Tuple(fields), Value::String(_))
+                if self.is_rstring(fields).unwrap_or_default() =>
+            {
+                self.import_leaf(sem_id, json, path)?
+            }
+            (Ty::Tuple(fields), _) if fields.len() == 1 => {
+                StrictVal::Tuple(vec![self.import_nested(
+                    fields[0],
+                    json,
+                    path,
+                    Step::UnnamedField(0),
+                )?])
+            }
+            (Ty::Tuple(fields), Value::Array(items)) => {
+                if fields.len() != items.len() {
+                    return Err(ImportError::at(path, ImportErrorKind::FieldNumberMismatch {
+                        expected: fields.len(),
+                        found: items.len(),
+                    }));
+                }
+                let mut vals = Vec::with_capacity(fields.len());
+                for (no, (id, item)) in fields.iter().zip(items).enumerate() {
+                    vals.push(self.import_nested(*id, item, path, Step::UnnamedField(no as u8))?);
+                }
+                StrictVal::Tuple(vals)
+            }
+            (Ty::Struct(fields), Value::Object(obj)) => {
+                if let Some(extra) =
+                    obj.keys().find(|key| fields.iter().all(|field| field.name.as_str() != *key))
+                {
+                    return Err(ImportError::at(path, ImportErrorKind::ExtraField(extra.clone())));
+                }
+                let mut vals = Vec::with_capacity(fields.len());
+                for field in fields {
+                    let val = match obj.get(field.name.as_str()) {
+                        Some(json) => self.import_nested(
+                            field.ty,
+                            json,
+                            path,
+                            Step::NamedField(field.name.clone()),
+                        )?,
+                        // Absent optional fields are interpreted as `none`
+                        None if self.find(field.ty).map(Ty::is_option).unwrap_or_default() => {
+                            StrictVal::none()
+                        }
+                        None => {
+                            return Err(ImportError::at(
+                                path,
+                                ImportErrorKind::MissingField(field.name.clone()),
+                            ));
+                        }
+                    };
+                    vals.push((field.name.clone(), val));
+                }
+                StrictVal::Struct(vals.into_iter().collect())
+            }
+
+            (Ty::Array(id, len), Value::String(s)) if id.is_byte() => {
+                let bytes = decode_bytes(s).ok_or_else(|| {
+                    ImportError::at(path, ImportErrorKind::InvalidBytes(s.clone()))
+                })?;
+                check_len(path, bytes.len(), Sizing::fixed(*len as u64))?;
+                StrictVal::bytes(bytes)
+            }
+            (Ty::List(id, sizing), Value::String(s)) if id.is_byte() => {
+                let bytes = decode_bytes(s).ok_or_else(|| {
+                    ImportError::at(path, ImportErrorKind::InvalidBytes(s.clone()))
+                })?;
+                check_len(path, bytes.len(), *sizing)?;
+                StrictVal::bytes(bytes)
+            }
+            // Unicode and ASCII strings
+            (Ty::Array(_, _) | Ty::List(_, _), Value::String(_)) => {
+                self.import_leaf(sem_id, json, path)?
+            }
+            (Ty::Array(id, len), Value::Array(items)) => {
+                check_len(path, items.len(), Sizing::fixed(*len as u64))?;
+                if id.is_byte() {
+                    self.import_bytes(sem_id, items, path)?
+                } else {
+                    StrictVal::List(self.import_items(*id, items, path)?)
+                }
+            }
+            (Ty::List(id, sizing), Value::Array(items)) => {
+                check_len(path, items.len(), *sizing)?;
+                if id.is_byte() {
+                    self.import_bytes(sem_id, items, path)?
+                } else {
+                    StrictVal::List(self.import_items(*id, items, path)?)
+                }
+            }
+            (Ty::Set(id, sizing), Value::Array(items)) => {
+                check_len(path, items.len(), *sizing)?;
+                let mut vals = Vec::with_capacity(items.len());
+                for (no, item) in items.iter().enumerate() {
+                    let val = self.import_nested(*id, item, path, Step::Index(no as u32))?;
+                    let Err(pos) = vals.binary_search_by(|probe| self.cmp_vals(probe, &val, *id))
+                    else {
+                        let _ = path.push(Step::Index(no as u32));
+                        return Err(ImportError::at(path, ImportErrorKind::RepeatedSetValue));
+                    };
+                    vals.insert(pos, val);
+                }
+                StrictVal::Set(vals)
+            }
+            (Ty::Map(key_id, val_id, sizing), Value::Object(obj)) => {
+                check_len(path, obj.len(), *sizing)?;
+                let mut entries = Vec::with_capacity(obj.len());
+                for (no, (key, val)) in obj.iter().enumerate() {
+                    let step = TinyString::try_from(key.clone())
+                        .map(|key| Step::Key(KeyStep::TinyString(key)))
+                        .unwrap_or(Step::Index(no as u32));
+                    let key = Value::String(key.clone());
+                    self.import_map_entry(*key_id, *val_id, &key, val, path, step, &mut entries)?;
+                }
+                StrictVal::Map(entries)
+            }
+            (Ty::Map(key_id, val_id, sizing), Value::Array(pairs)) => {
+                check_len(path, pairs.len(), *sizing)?;
+                let mut entries = Vec::with_capacity(pairs.len());
+                for (no, pair) in pairs.iter().enumerate() {
+                    let step = Step::Index(no as u32);
+                    let Some([key, val]) = pair.as_array().map(Vec::as_slice) else {
+                        let _ = path.push(step);
+                        return Err(ImportError::at(path, ImportErrorKind::InvalidMapEntry));
+                    };
+                    self.import_map_entry(*key_id, *val_id, key, val, path, step, &mut entries)?;
+                }
+                StrictVal::Map(entries)
+            }
+
+            _ => return Err(mismatch(path)),
+        })
+    }
+}
+
+/// Error converting a typed strict value into JSON or YAML, indicating the path to the value
+/// which doesn't match its type.
+#[derive(Clone, Eq, PartialEq, Debug, Error)]
+pub struct ExportError {
+    pub path: Path,
+    pub kind: ExportErrorKind,
+}
+
+impl ExportError {
+    fn at(path: &Path, kind: ExportErrorKind) -> Self {
+        ExportError {
+            path: path.clone(),
+            kind,
+        }
+    }
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            Display::fmt(&self.kind, f)
+        } else {
+            write!(f, "value at `{}`: {}", self.path, self.kind)
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ExportErrorKind {
+    /// unknown type `{0}`.
+    TypeAbsent(SemId),
+
+    /// value `{value}` doesn't match type requirements `{expected}`.
+    TypeMismatch {
+        value: StrictVal,
+        expected: Ty<SemId>,
+    },
+}
+
+fn export_num(num: &StrictNum) -> serde_json::Value {
+    use serde_json::{Number, Value};
+
+    match num {
+        StrictNum::Uint(n) => Value::Number((*n).into()),
+        StrictNum::Int(n) => Value::Number((*n).into()),
+        // Floats which can't be exactly represented by JSON numbers
+        StrictNum::Float(f) => float::round(Primitive::F64, *f)
+            .filter(|rounded| rounded.status == Status::OK)
+            .and_then(|rounded| float::to_bits(Primitive::F64, rounded.value))
+            .and_then(|bits| Number::from_f64(f64::from_bits(bits.low_u64())))
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(num.to_string())),
+        // Large numbers which can't be represented by JSON numbers
+        StrictNum::BigUint(_) | StrictNum::BigInt(_) => Value::String(num.to_string()),
+    }
+}
+
+impl SymbolicSys {
+    /// Converts typed strict value into JSON, using the value type to produce a canonical
+    /// representation which can be read back with [`SymbolicSys::from_json`].
+    ///
+    /// The mapping is the following:
+    /// - unit values are `null`s;
+    /// - numbers are JSON numbers, unless they can't be represented by them exactly, in which case
+    ///   they are decimal strings (`inf`, `-inf` and `nan` for special float values);
+    /// - characters, strings and enums are JSON strings (enums use variant names);
+    /// - options are `null` for `none` and the wrapped value for `some`;
+    /// - other unions are the name of the variant for unit variants, and an object with a single
+    ///   variant name key otherwise;
+    /// - newtypes are represented by their wrapped value, while other tuples are JSON arrays;
+    /// - structures are JSON objects;
+    /// - byte arrays and lists are lowercase hex strings;
+    /// - other arrays, lists and sets are JSON arrays;
+    /// - maps with string or enum keys are JSON objects, other maps are arrays of two-element
+    ///   `[key, value]` arrays.
+    pub fn to_json(&self, val: &TypedVal) -> Result<serde_json::Value, ExportError> {
+        self.as_types().export_json(val.as_orig().id, val.as_val(), &mut Path::new())
+    }
+
+    /// Converts typed strict value into YAML, using the same mapping as [`SymbolicSys::to_json`].
+    pub fn to_yaml(&self, val: &TypedVal) -> Result<serde_yaml::Value, ExportError> {
+        let json = self.to_json(val)?;
+        Ok(serde_yaml::to_value(json).expect("JSON values are always representable in YAML"))
+    }
+}
+
+impl TypeSystem {
+    fn export_nested(
+        &self,
+        sem_id: SemId,
+        val: &StrictVal,
+        path: &mut Path,
+        step: Step,
+    ) -> Result<serde_json::Value, ExportError> {
+        let pushed = path.push(step).is_ok();
+        let json = self.export_json(sem_id, val, path)?;
+        if pushed {
+            path.pop();
+        }
+        Ok(json)
+    }
+
+    /// Detects types whose values are exported as JSON strings: unicode characters, unicode and
+    /// ASCII strings and restricted strings.
+    fn is_string(&self, ty: &Ty<SemId>) -> bool {
+        match ty {
+            Ty::UnicodeChar => true,
+            Ty::Array(id, _) | Ty::List(id, _) => {
+                id.is_unicode_char() || self.find(*id).is_some_and(Ty::is_char_enum)
+            }
+            Ty::Tuple(fields) => self.is_rstring(fields).unwrap_or_default(),
+            _ => false,
+        }
+    }
+
+    fn export_items<'a>(
+        &self,
+        sem_id: SemId,
+        items: impl IntoIterator<Item = &'a StrictVal>,
+        path: &mut Path,
+    ) -> Result<serde_json::Value, ExportError> {
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(no, item)| self.export_nested(sem_id, item, path, Step::Index(no as u32)))
+            .collect::<Result<_, _>>()
+            .map(serde_json::Value::Array)
+    }
+
+    fn export_json(
+        &self,
+        sem_id: SemId,
+        val: &StrictVal,
+        path: &mut Path,
+    ) -> Result<serde_json::Value, ExportError> {
+        use serde_json::{Map, Value};
+
+        let ty = self
+            .find(sem_id)
+            .ok_or_else(|| ExportError::at(path, ExportErrorKind::TypeAbsent(sem_id)))?;
+        let mismatch = |path: &Path| {
+            ExportError::at(path, ExportErrorKind::TypeMismatch {
+                value: val.clone(),
+                expected: ty.clone(),
+            })
+        };
+
+        Ok(match (val, ty) {
+            (StrictVal::Unit, ty) if *ty == Ty::UNIT => Value::Null,
+            (StrictVal::Number(num), Ty::Primitive(_)) => export_num(num),
+            (StrictVal::String(s), ty) if self.is_string(ty) => Value::String(s.clone()),
+            (StrictVal::Bytes(bytes), Ty::Array(_, _) | Ty::List(_, _)) => {
+                Value::String(bytes.0.to_hex())
+            }
+
+            (StrictVal::Enum(tag), Ty::Enum(variants)) => {
+                let name = match tag {
+                    EnumTag::Name(name) => Some(name),
+                    EnumTag::Ord(ord) => variants.name_by_tag(*ord),
+                };
+                Value::String(name.ok_or_else(|| mismatch(path))?.to_string())
+            }
+            (StrictVal::Union(tag, content), Ty::Union(variants)) => {
+                let variant = match tag {
+                    EnumTag::Name(name) => variants.by_name(name),
+                    EnumTag::Ord(ord) => variants.by_tag(*ord),
+                };
+                let (variant, id) = variant.ok_or_else(|| mismatch(path))?;
+                match (ty.as_some(), content.as_ref()) {
+                    (Some(_), _) if variant.tag == 0 => Value::Null,
+                    (Some(_), content) => self.export_json(*id, content, path)?,
+                    (None, StrictVal::Unit) => Value::String(variant.name.to_string()),
+                    (None, StrictVal::Tuple(fields)) if fields.is_empty() => {
+                        Value::String(variant.name.to_string())
+                    }
+                    (None, content) => {
+                        let mut obj = Map::new();
+                        obj.insert(variant.name.to_string(), self.export_json(*id, content, path)?);
+                        Value::Object(obj)
+                    }
+                }
+            }
+
+            (StrictVal::Tuple(fields), Ty::Tuple(tys)) if fields.len() == tys.len() => {
+                if let [field] = fields.as_slice() {
+                    return self.export_nested(tys[0], field, path, Step::UnnamedField(0));
+                }
+                let mut items = Vec::with_capacity(fields.len());
+                for (no, (field, id)) in fields.iter().zip(tys).enumerate() {
+                    items.push(self.export_nested(
+                        *id,
+                        field,
+                        path,
+                        Step::UnnamedField(no as u8),
+                    )?);
+                }
+                Value::Array(items)
+            }
+            (StrictVal::Struct(fields), Ty::Struct(tys)) if fields.len() == tys.len() => {
+                let mut obj = Map::new();
+                for field in tys {
+                    let val = fields.get(&field.name).ok_or_else(|| mismatch(path))?;
+                    let json = self.export_nested(
+                        field.ty,
+                        val,
+                        path,
+                        Step::NamedField(field.name.clone()),
+                    )?;
+                    obj.insert(field.name.to_string(), json);
+                }
+                Value::Object(obj)
+            }
+
+            (StrictVal::List(items), Ty::Array(id, _) | Ty::List(id, _))
+            | (StrictVal::Set(items), Ty::Set(id, _)) => self.export_items(*id, items, path)?,
+            (StrictVal::Map(entries), Ty::Map(key_id, val_id, _)) => {
+                let mut keys = Vec::with_capacity(entries.len());
+                let mut vals = Vec::with_capacity(entries.len());
+                for (no, (key, val)) in entries.iter().enumerate() {
+                    let step = Step::Index(no as u32);
+                    let key = self.export_nested(*key_id, key, path, step.clone())?;
+                    let step = KeyStep::with_val(&entries[no].0).map(Step::Key).unwrap_or(step);
+                    vals.push(self.export_nested(*val_id, val, path, step)?);
+                    keys.push(key);
+                }
+                if keys.iter().all(Value::is_string) {
+                    let obj = keys
+                        .into_iter()
+                        .zip(vals)
+                        .map(|(key, val)| match key {
+                            Value::String(key) => (key, val),
+                            _ => unreachable!("checked key types"),
+                        })
+                        .collect();
+                    Value::Object(obj)
+                } else {
+                    keys.into_iter()
+                        .zip(vals)
+                        .map(|(key, val)| Value::Array(vec![key, val]))
+                        .collect()
+                }
+            }
+
+            _ => return Err(mismatch(path)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::num::u1024;
+    use serde_json::json;
+
+    use super::super::test_helpers::*;
+    use super::*;
+
+    fn json_system() -> SymbolicSys {
+        let src = "typelib Json
+data Hash : [Byte ^ 32]
+data Kind : alpha | beta#5 | gamma
+data Shape : circle U16 | rect (w U16, h U16) | empty ()
+data Item : id U64, big U256, name [Unicode ^ ..0xff], kind Kind, shape Shape, note Hash?, blob \
+                   [Byte], nums {U8}, index {Kind -> U32}, pair (I8, F32), wrapped Wrapped
+data Wrapped : Hash
+data Extreme : F80, F64, F64, I128
+data Order : zed | alpha
+data Ranks : {Order -> U8}
+";
+        system_from_source(src)
+    }
+
+    #[test]
+    fn from_json() {
+        let sys = json_system();
+        let hash = [0xA5u8; 32];
+        let json = json!({
+            "id": 5,
+            "big": "87112285931760246646623899502532662132736",
+            "name": "Some name",
+            "kind": "beta",
+            "shape": { "rect": { "w": 4, "h": 8 } },
+            "blob": "AQID",
+            "nums": [3, 1, 2],
+            "index": { "alpha": 2, "gamma": 1 },
+            "pair": [-1, 0.5],
+            "wrapped": format!("0x{}", "a5".repeat(32)),
+        });
+        let val = sys.from_json("Json.Item", &json).unwrap();
+        let expected = sys
+            .typify(
+                ston!(
+                    id 5u64,
+                    big StrictVal::num(u1024::from(1u64) << 136),
+                    name "Some name",
+                    kind svenum!(beta),
+                    shape sv!(StrictVal::union("rect", ston!(w 4u16, h 8u16))),
+                    note svnone!(),
+                    blob svbytes!([1u8, 2, 3]),
+                    nums sv!(StrictVal::set([1u8, 2, 3])),
+                    index sv!(StrictVal::map([(svenum!(alpha), 2u32), (svenum!(gamma), 1u32)])),
+                    pair sv!(StrictVal::tuple([sv!(-1i64), sv!(0.5f64)])),
+                    wrapped sv!(StrictVal::newtype(svbytes!(hash)))
+                ),
+                "Json.Item",
+            )
+            .unwrap();
+        assert_eq!(val.as_val(), expected.as_val());
+        assert_eq!(val.as_val().unwrap_struct("nums"), &StrictVal::set([1u8, 2, 3]));
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&val).unwrap();
+        let decoded = sys.strict_deserialize_type("Json.Item", &data).unwrap();
+        assert_eq!(decoded.as_val(), val.as_val());
+
+        let shape = sys.from_json("Json.Shape", &json!("empty")).unwrap();
+        assert_eq!(shape.unbox(), StrictVal::union("empty", ()));
+        let shape = sys.from_json("Json.Shape", &json!({"circle": 3})).unwrap();
+        assert_eq!(shape.unbox(), StrictVal::union("circle", StrictVal::newtype(3u16)));
+
+        let index = sys.from_json("Json.Item", &json!([[1, 2]])).unwrap_err();
+        assert_eq!(index.kind, ImportErrorKind::Mismatch {
+            expected: "object",
+            found: "array"
+        });
+    }
+
+    #[test]
+    fn to_json() {
+        let sys = json_system();
+        let json = json!({
+            "id": 5,
+            "big": "87112285931760246646623899502532662132736",
+            "name": "Some name",
+            "kind": "beta",
+            "shape": { "rect": { "h": 8, "w": 4 } },
+            "note": null,
+            "blob": "010203",
+            "nums": [1, 2, 3],
+            "index": { "alpha": 2, "gamma": 1 },
+            "pair": [-1, 0.5],
+            "wrapped": "a5".repeat(32),
+        });
+        let val = sys.from_json("Json.Item", &json).unwrap();
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&val).unwrap();
+        let decoded = sys.strict_deserialize_type("Json.Item", &data).unwrap();
+        assert_eq!(sys.to_json(&decoded).unwrap(), json);
+        assert_eq!(sys.from_json("Json.Item", &sys.to_json(&decoded).unwrap()).unwrap(), decoded);
+
+        let yaml = sys.to_yaml(&decoded).unwrap();
+        assert_eq!(yaml["kind"], serde_yaml::Value::from("beta"));
+        assert_eq!(yaml["shape"]["rect"]["w"], serde_yaml::Value::from(4));
+
+        let shape = sys.from_json("Json.Shape", &json!({"circle": 3})).unwrap();
+        assert_eq!(sys.to_json(&shape).unwrap(), json!({"circle": 3}));
+        let shape = sys.from_json("Json.Shape", &json!("empty")).unwrap();
+        assert_eq!(sys.to_json(&shape).unwrap(), json!("empty"));
+
+        let json = json!(["0.1", "inf", "nan", "-170141183460469231731687303715884105728"]);
+        let val = sys.from_json("Json.Extreme", &json).unwrap();
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&val).unwrap();
+        let decoded = sys.strict_deserialize_type("Json.Extreme", &data).unwrap();
+        assert_eq!(
+            sys.to_json(&decoded).unwrap(),
+            json!([
+                "0.1000000000000000000013552527156068805425093160010874271392822265625",
+                "inf",
+                "nan",
+                "-170141183460469231731687303715884105728"
+            ])
+        );
+        assert_eq!(
+            sys.from_json("Json.Extreme", &sys.to_json(&decoded).unwrap()).unwrap(),
+            decoded
+        );
+
+        let invalid = TypedVal {
+            orig: TypeSymbol::unnamed(sys.to_sem_id("Json.Kind").unwrap()),
+            val: StrictVal::num(5u8),
+        };
+        let err = sys.to_json(&invalid).unwrap_err();
+        assert!(matches!(err.kind, ExportErrorKind::TypeMismatch { .. }));
+        for val in [StrictVal::Unit, StrictVal::String(s!("alpha"))] {
+            let invalid = TypedVal {
+                orig: TypeSymbol::unnamed(sys.to_sem_id("Json.Kind").unwrap()),
+                val,
+            };
+            let err = sys.to_json(&invalid).unwrap_err();
+            assert!(matches!(err.kind, ExportErrorKind::TypeMismatch { .. }));
+        }
+    }
+
+    #[test]
+    fn from_json_order() {
+        let sys = json_system();
+        let hash = [0u8; 32];
+        let ranks = sys.from_json("Json.Ranks", &json!({"zed": 1, "alpha": 2})).unwrap();
+        let expected = StrictVal::map([(svenum!(zed), 1u8), (svenum!(alpha), 2u8)]);
+        assert_eq!(ranks.as_val(), &StrictVal::newtype(expected));
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&ranks).unwrap();
+        assert_eq!(data.as_slice(), &[2, 0, 0, 1, 1, 2]);
+        assert_eq!(sys.strict_deserialize_type("Json.Ranks", &data).unwrap().val, ranks.val);
+
+        let ranks = sys.from_json("Json.Ranks", &json!([["alpha", 2], ["zed", 1], ["alpha", 3]]));
+        let err = ranks.unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::RepeatedKeyValue);
+        assert_eq!(err.path.to_string(), ".0[2]");
+
+        let item = sys.from_json(
+            "Json.Item",
+            &json!({
+                "id": 0, "big": 0, "name": "", "kind": "alpha", "shape": "empty", "blob": "",
+                "nums": [3, 1], "index": {}, "pair": [0, 0], "wrapped": hash,
+            }),
+        );
+        assert_eq!(item.unwrap().as_val().unwrap_struct("nums"), &StrictVal::set([1u8, 3]));
+    }
+
+    #[test]
+    fn to_json_order() {
+        let sys = json_system();
+        let data = [2, 0, 0, 1, 1, 2];
+        let decoded = sys.strict_deserialize_type("Json.Ranks", &data).unwrap();
+        let json = sys.to_json(&decoded).unwrap();
+        assert_eq!(json, json!({"zed": 1, "alpha": 2}));
+
+        let imported = sys.from_json("Json.Ranks", &json).unwrap();
+        assert_eq!(imported, decoded);
+        let encoded = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&imported).unwrap();
+        assert_eq!(encoded.as_slice(), &data);
+        assert_eq!(sys.strict_deserialize_type("Json.Ranks", &encoded).unwrap(), decoded);
+    }
+
+    #[test]
+    fn from_json_errors() {
+        let sys = json_system();
+        let hash = [0xA5u8; 32];
+        let mut json = json!({
+            "id": 5,
+            "big": 1,
+            "name": "",
+            "kind": 0,
+            "shape": "empty",
+            "note": hash,
+            "blob": [],
+            "nums": [],
+            "index": [["alpha", 1], [5, 2]],
+            "pair": [0, 0],
+            "wrapped": hash,
+        });
+        let val = sys.from_json("Json.Item", &json).unwrap();
+        assert_eq!(
+            val.as_val().unwrap_struct("index"),
+            &StrictVal::map([(svenum!(alpha), 1u32), (svenum!(beta), 2u32)])
+        );
+
+        json["index"] = json!([["alpha", 1], ["alpha", 2]]);
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::RepeatedKeyValue);
+        assert_eq!(err.path.to_string(), ".index[1]");
+
+        json["index"] = json!({"alpha": 1, "beta": -2});
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.path.to_string(), ".index{\"beta\"}");
+        assert!(matches!(err.kind, ImportErrorKind::Typify(_)));
+        json["index"] = json!({});
+
+        json["nums"] = json!([1, 2, 1]);
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::RepeatedSetValue);
+        assert_eq!(err.path.to_string(), ".nums[2]");
+        json["nums"] = json!([]);
+
+        json["note"] = json!("abc");
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::InvalidBytes(s!("abc")));
+        assert_eq!(
+            err.to_string(),
+            "value at `.note.0.0`: string `abc` is neither hex- nor base64-encoded byte string."
+        );
+        json["note"] = json!("0xA5A5");
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::OutOfBounds(2, Sizing::fixed(32)));
+        json["note"] = json!(null);
+
+        json["wrapped"][3] = json!(256);
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.path.to_string(), ".wrapped.0.0[3]");
+        assert_eq!(err.kind, ImportErrorKind::InvalidNumber(s!("256")));
+        json["wrapped"] = json!(hash);
+
+        json["shape"] = json!({"circle": 1, "empty": null});
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::InvalidUnion);
+        json["shape"] = json!({"square": 1});
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::UnknownVariant(s!("square")));
+        json["shape"] = json!({"rect": {"w": 1}});
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::MissingField(fname!("h")));
+        assert_eq!(err.path.to_string(), ".shape");
+        json["shape"] = json!({"rect": {"w": 1, "h": 2, "d": 3}});
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::ExtraField(s!("d")));
+        json["shape"] = json!({"rect": {"w": 1, "h": true}});
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.path.to_string(), ".shape.h");
+        assert_eq!(err.kind, ImportErrorKind::Mismatch {
+            expected: "number",
+            found: "boolean"
+        });
+        json["shape"] = json!("empty");
+
+        json["pair"] = json!([1]);
+        let err = sys.from_json("Json.Item", &json).unwrap_err();
+        assert_eq!(err.kind, ImportErrorKind::FieldNumberMismatch {
+            expected: 2,
+            found: 1
+        });
+        assert_eq!(err.path.to_string(), ".pair");
+
+        let err = sys.from_json("Json.Absent", &json).unwrap_err();
+        assert_eq!(err.to_string(), "unknown type `Json.Absent`.");
+    }
+
+    #[test]
+    fn floats() {
+        let json =
+            serde_json::from_str::<serde_json::Value>(r#"{"a": 1.5, "b": [-2, 3]}"#).unwrap();
+        assert_eq!(
+            StrictVal::try_from(json).unwrap(),
+            StrictVal::map([(sv!("a"), sv!(1.5f64)), (sv!("b"), svlist!([sv!(-2i64), sv!(3u64)]))])
+        );
+
+        let yaml = serde_yaml::from_str::<serde_yaml::Value>("a: -0.25\nb: .inf").unwrap();
+        assert_eq!(
+            StrictVal::try_from(yaml).unwrap(),
+            StrictVal::map([(sv!("a"), sv!(-0.25f64)), (sv!("b"), sv!(f64::INFINITY))])
+        );
+
+        let toml = toml::from_str::<toml::Value>("a = 2.5\nb = 1979-05-27T07:32:00Z").unwrap();
+        assert_eq!(StrictVal::try_from(toml), Err(Error::Datetime(s!("1979-05-27T07:32:00Z"))));
+    }
+}
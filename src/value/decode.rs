@@ -22,27 +22,86 @@
 
 //! Reification module: reads & writes strict values from binary strict encodings.
 
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::mem::size_of;
+
 use amplify::ascii::AsciiString;
-use amplify::confinement::{
-    Confined, LargeAscii, LargeBlob, LargeString, MediumAscii, MediumBlob, MediumString,
-    SmallAscii, SmallBlob, SmallString, TinyAscii, TinyBlob, TinyString, U16 as MAX16,
-    U32 as MAX32,
+use amplify::confinement::U32 as MAX32;
+use amplify::num::{i1024, u1024, u24, u256};
+use encoding::{
+    DecodeError, NumCls, Primitive, ReadRaw, Sizing, StreamReader, StrictDecode, StrictReader,
 };
-use amplify::num::{u24, u40, u48, u56};
-use encoding::{DecodeError, Primitive, ReadRaw, StreamReader, StrictDecode, StrictReader};
 use indexmap::IndexMap;
 
-use crate::typesys::{SymbolicSys, TypeSymbol, UnknownType};
-use crate::typify::{TypeSpec, TypedVal};
-use crate::value::Blob;
+use crate::typesys::{SymbolicSys, TypeFqn, TypeSymbol, UnknownType};
+use crate::typify::{SpecError, TypeSpec, TypedVal};
+use crate::value::dump::{HexDump, Span, SpanKind};
+use crate::value::{float, Blob, EnumTag, KeyStep, Path, Step};
 use crate::{SemId, StrictVal, Ty, TypeRef, TypeSystem};
 
+/// Failure to decode a strict value, located in the decoded data and in the value.
+#[derive(Clone, Eq, PartialEq, Debug, Error)]
+pub struct Error {
+    pub kind: ErrorKind,
+    /// Offset of the first byte of the value which has failed to decode, counting from the start
+    /// of the decoded data.
+    pub offset: u64,
+    /// Path to the value which has failed to decode.
+    pub path: Path,
+    /// Name of the innermost named type containing the failed value. Known only when decoding
+    /// with a [`SymbolicSys`].
+    pub fqn: Option<TypeFqn>,
+    /// Part of the value which was decoded before the failure. Values which were not decoded are
+    /// omitted from collections and structures; a union variant with no decoded content has unit
+    /// content.
+    pub partial: Option<StrictVal>,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "decoding failed at byte {}", self.offset)?;
+        if !self.path.is_empty() {
+            write!(f, " in value `{}`", self.path)?;
+        }
+        if let Some(fqn) = &self.fqn {
+            write!(f, " of type `{fqn}`")?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+impl From<ErrorKind> for Error {
+    /// Constructs an error which is not related to a specific value.
+    fn from(kind: ErrorKind) -> Self {
+        Error {
+            kind,
+            offset: 0,
+            path: Path::new(),
+            fqn: None,
+            partial: None,
+        }
+    }
+}
+
+impl Error {
+    fn nest(mut self, wrap: impl FnOnce(Option<StrictVal>) -> StrictVal) -> Self {
+        self.partial = Some(wrap(self.partial.take()));
+        self
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
 #[display(doc_comments)]
-pub enum Error {
+pub enum ErrorKind {
     /// unknown type `{0}`.
     TypeAbsent(TypeSpec),
 
+    #[display(inner)]
+    #[from]
+    Spec(SpecError),
+
     #[display(inner)]
     #[from]
     UnknownType(UnknownType),
@@ -56,6 +115,139 @@ pub enum Error {
 
     /// data provided to reify operation are not entirely consumed during deserialization.
     NotEntirelyConsumed,
+
+    /// collection length {len} is out of the type bounds {min}..={max}.
+    LengthOutOfBounds { len: u64, min: u64, max: u64 },
+
+    /// value nesting depth exceeds the limit of {0} levels.
+    DepthLimit(usize),
+
+    /// collection with {len} items exceeds the limit of {max} items per collection.
+    LengthLimit { len: u64, max: u64 },
+
+    /// total number of collection items exceeds the limit of {0}.
+    ItemsLimit(u64),
+
+    /// decoded value requires more than {0} bytes of memory.
+    AllocLimit(u64),
+}
+
+/// Checks that the decoded collection length fits the type sizing.
+pub(super) fn check_len(len: u64, sizing: Sizing) -> Result<(), ErrorKind> {
+    if len < sizing.min || len > sizing.max {
+        return Err(ErrorKind::LengthOutOfBounds {
+            len,
+            min: sizing.min,
+            max: sizing.max,
+        });
+    }
+    Ok(())
+}
+
+/// Size of the chunks in which byte and character strings are read.
+pub(super) const READ_CHUNK: usize = 0x10000;
+
+/// Limits on the resources used when decoding values from untrusted data.
+///
+/// Each limit is reported by its own error once exceeded: [`ErrorKind::DepthLimit`],
+/// [`ErrorKind::LengthLimit`], [`ErrorKind::ItemsLimit`] and [`ErrorKind::AllocLimit`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct DecodeLimits {
+    /// Maximal nesting depth of the value, which also limits the recursion of the decoder.
+    pub max_depth: usize,
+    /// Maximal number of items in a single collection; for byte and character strings the
+    /// number of bytes. The limit applies on top of the type sizing.
+    pub max_len: u64,
+    /// Maximal total number of items in all collections of the value.
+    pub max_items: u64,
+    /// Maximal number of bytes allocated for the collections and strings of the value.
+    pub max_alloc: u64,
+}
+
+impl Default for DecodeLimits {
+    /// Limits suitable for the data received from untrusted parties: 64 nesting levels, 64k
+    /// items per collection, 1M items in total and 64 MiB of memory.
+    fn default() -> Self {
+        DecodeLimits {
+            max_depth: 64,
+            max_len: 0x1_0000,
+            max_items: 0x10_0000,
+            max_alloc: 0x400_0000,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// Limits which do not restrict decoding.
+    pub const fn unlimited() -> Self {
+        DecodeLimits {
+            max_depth: usize::MAX,
+            max_len: u64::MAX,
+            max_items: u64::MAX,
+            max_alloc: u64::MAX,
+        }
+    }
+}
+
+/// Resources consumed during a decoding operation.
+pub(super) struct Budget {
+    limits: DecodeLimits,
+    depth: usize,
+    items: u64,
+    alloc: u64,
+}
+
+impl Budget {
+    pub(super) fn new(limits: DecodeLimits) -> Self {
+        Budget {
+            limits,
+            depth: 0,
+            items: 0,
+            alloc: 0,
+        }
+    }
+
+    pub(super) fn enter(&mut self) -> Result<(), ErrorKind> {
+        if self.depth >= self.limits.max_depth {
+            return Err(ErrorKind::DepthLimit(self.limits.max_depth));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    pub(super) fn leave(&mut self) { self.depth -= 1; }
+
+    pub(super) fn len(&self, len: u64) -> Result<(), ErrorKind> {
+        if len > self.limits.max_len {
+            return Err(ErrorKind::LengthLimit {
+                len,
+                max: self.limits.max_len,
+            });
+        }
+        Ok(())
+    }
+
+    pub(super) fn alloc(&mut self, size: u64) -> Result<(), ErrorKind> {
+        self.alloc = self.alloc.saturating_add(size);
+        if self.alloc > self.limits.max_alloc {
+            return Err(ErrorKind::AllocLimit(self.limits.max_alloc));
+        }
+        Ok(())
+    }
+
+    fn bytes(&mut self, len: u64) -> Result<(), ErrorKind> {
+        self.len(len)?;
+        self.alloc(len)
+    }
+
+    pub(super) fn items(&mut self, len: u64, item_size: usize) -> Result<(), ErrorKind> {
+        self.len(len)?;
+        self.items = self.items.saturating_add(len);
+        if self.items > self.limits.max_items {
+            return Err(ErrorKind::ItemsLimit(self.limits.max_items));
+        }
+        self.alloc(len.saturating_mul(item_size as u64))
+    }
 }
 
 impl SymbolicSys {
@@ -64,9 +256,7 @@ impl SymbolicSys {
         spec: impl Into<TypeSpec>,
         data: &[u8],
     ) -> Result<TypedVal, Error> {
-        let spec = spec.into();
-        let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
-        self.as_types().strict_deserialize_type(sem_id, data)
+        self.strict_deserialize_type_limited(spec, data, DecodeLimits::unlimited())
     }
 
     pub fn strict_read_type(
@@ -74,329 +264,920 @@ impl SymbolicSys {
         spec: impl Into<TypeSpec>,
         d: &mut impl ReadRaw,
     ) -> Result<TypedVal, Error> {
-        let spec = spec.into();
-        let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
-        self.as_types().strict_read_type(sem_id, d)
+        self.strict_read_type_limited(spec, d, DecodeLimits::unlimited())
+    }
+
+    pub fn strict_deserialize_type_limited(
+        &self,
+        spec: impl Into<TypeSpec>,
+        data: &[u8],
+        limits: DecodeLimits,
+    ) -> Result<TypedVal, Error> {
+        let sem_id = self.resolve_spec(spec).map_err(|err| err.report(ErrorKind::TypeAbsent))?;
+        Decoder::new(self.as_types(), Some(self), limits).deserialize(sem_id, data)
+    }
+
+    pub fn strict_read_type_limited(
+        &self,
+        spec: impl Into<TypeSpec>,
+        d: &mut impl ReadRaw,
+        limits: DecodeLimits,
+    ) -> Result<TypedVal, Error> {
+        let sem_id = self.resolve_spec(spec).map_err(|err| err.report(ErrorKind::TypeAbsent))?;
+        Decoder::new(self.as_types(), Some(self), limits).read(sem_id, d)
     }
 }
 
 impl TypeSystem {
-    fn strict_read_list(
+    /// Deserializes a value of the given type from the data without restricting the resources
+    /// used by the decoder. Use [`Self::strict_deserialize_type_limited`] for untrusted data.
+    pub fn strict_deserialize_type(&self, sem_id: SemId, data: &[u8]) -> Result<TypedVal, Error> {
+        self.strict_deserialize_type_limited(sem_id, data, DecodeLimits::unlimited())
+    }
+
+    pub fn strict_deserialize_type_limited(
         &self,
-        len: usize,
-        ty: SemId,
-        d: &mut impl ReadRaw,
-    ) -> Result<Vec<StrictVal>, Error> {
-        let mut list = Vec::with_capacity(len);
-        for _ in 0..len {
-            let item = self.strict_read_type(ty, d)?;
-            list.push(item.val);
-        }
-        Ok(list)
+        sem_id: SemId,
+        data: &[u8],
+        limits: DecodeLimits,
+    ) -> Result<TypedVal, Error> {
+        Decoder::new(self, None, limits).deserialize(sem_id, data)
+    }
+
+    /// Reads a value of the given type without restricting the resources used by the decoder.
+    /// Use [`Self::strict_read_type_limited`] for untrusted data.
+    pub fn strict_read_type(&self, sem_id: SemId, d: &mut impl ReadRaw) -> Result<TypedVal, Error> {
+        self.strict_read_type_limited(sem_id, d, DecodeLimits::unlimited())
     }
 
-    fn strict_read_map(
+    pub fn strict_read_type_limited(
         &self,
-        len: usize,
-        key_ty: SemId,
-        ty: SemId,
+        sem_id: SemId,
+        d: &mut impl ReadRaw,
+        limits: DecodeLimits,
+    ) -> Result<TypedVal, Error> {
+        Decoder::new(self, None, limits).read(sem_id, d)
+    }
+
+    pub(super) fn strict_read_primitive(
+        prim: Primitive,
         d: &mut impl ReadRaw,
-    ) -> Result<Vec<(StrictVal, StrictVal)>, Error> {
-        let mut list = Vec::with_capacity(len);
-        for _ in 0..len {
-            let key = self.strict_read_type(key_ty, d)?;
-            let item = self.strict_read_type(ty, d)?;
-            list.push((key.val, item.val));
+    ) -> Result<StrictVal, ErrorKind> {
+        if prim == Primitive::UNIT {
+            return Ok(StrictVal::Unit);
+        }
+        let info = prim.info();
+        let len = prim.byte_size() as usize;
+        if info.ty == NumCls::Float {
+            let unsupported = || {
+                ErrorKind::NotImplemented(format!(
+                    "loading float primitive with code {:#04x} into a typed value",
+                    prim.into_code()
+                ))
+            };
+            if len > 32 {
+                return Err(unsupported());
+            }
+            let bytes = d.read_raw::<32>(len).map_err(DecodeError::from)?;
+            let mut buf = [0u8; 32];
+            buf[..len].copy_from_slice(&bytes);
+            let f = float::from_bits(prim, u256::from_le_bytes(buf)).ok_or_else(unsupported)?;
+            return Ok(StrictVal::num(f));
         }
-        Ok(list)
+        if len == 0 || len > 128 {
+            return Err(ErrorKind::NotImplemented(format!(
+                "loading primitive with code {:#04x} into a typed value",
+                prim.into_code()
+            )));
+        }
+
+        let bytes = d.read_raw::<128>(len).map_err(DecodeError::from)?;
+        if info.ty == NumCls::NonZero && bytes.iter().all(|byte| *byte == 0) {
+            return Err(DecodeError::ZeroNatural.into());
+        }
+        let signed = info.ty == NumCls::Signed && prim != Primitive::BYTE;
+        let fill = if signed && bytes[len - 1] & 0x80 != 0 { 0xFF } else { 0x00 };
+        let mut buf = [fill; 128];
+        buf[..len].copy_from_slice(&bytes);
+
+        let mut small = [0u8; 8];
+        small.copy_from_slice(&buf[..8]);
+        Ok(match (signed, len) {
+            (false, ..=8) => StrictVal::num(u64::from_le_bytes(small)),
+            (true, ..=8) => StrictVal::num(i64::from_le_bytes(small)),
+            (false, _) => StrictVal::num(u1024::from_le_bytes(buf)),
+            (true, _) => StrictVal::num(i1024::from_le_bytes(buf)),
+        })
     }
 
-    pub fn strict_deserialize_type(&self, sem_id: SemId, data: &[u8]) -> Result<TypedVal, Error> {
+    /// Compares two values of the same type in the order used by the strict encoding for set
+    /// elements and map keys, matching the `Ord` implementation of the original types.
+    ///
+    /// Numbers are compared by value, strings and byte strings - lexicographically by bytes, enums
+    /// and unions - by their tags (unions then by their content), tuples, structures and
+    /// collections - lexicographically by their items.
+    pub(crate) fn cmp_vals(&self, a: &StrictVal, b: &StrictVal, sem_id: SemId) -> Ordering {
+        let Some(ty) = self.find(sem_id) else {
+            return Ordering::Equal;
+        };
+        match (a, b, ty) {
+            (StrictVal::Number(a), StrictVal::Number(b), _) => a.cmp(b),
+            (StrictVal::String(a), StrictVal::String(b), _) => a.cmp(b),
+            (StrictVal::Bytes(a), StrictVal::Bytes(b), _) => a.0.cmp(&b.0),
+            (StrictVal::Enum(a), StrictVal::Enum(b), Ty::Enum(variants)) => {
+                let tag = |tag: &EnumTag| match tag {
+                    EnumTag::Ord(ord) => Some(*ord),
+                    EnumTag::Name(name) => variants.tag_by_name(name),
+                };
+                tag(a).cmp(&tag(b))
+            }
+            (StrictVal::Union(a, a_val), StrictVal::Union(b, b_val), Ty::Union(variants)) => {
+                let variant = |tag: &EnumTag| match tag {
+                    EnumTag::Ord(ord) => variants.by_tag(*ord),
+                    EnumTag::Name(name) => variants.by_name(name),
+                };
+                match (variant(a), variant(b)) {
+                    (Some((a, ty)), Some((b, _))) if a.tag == b.tag => {
+                        self.cmp_vals(a_val, b_val, *ty)
+                    }
+                    (a, b) => a.map(|(a, _)| a.tag).cmp(&b.map(|(b, _)| b.tag)),
+                }
+            }
+            (StrictVal::Tuple(a), StrictVal::Tuple(b), Ty::Tuple(fields)) => a
+                .iter()
+                .zip(b)
+                .zip(fields)
+                .map(|((a, b), ty)| self.cmp_vals(a, b, *ty))
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (StrictVal::Struct(a), StrictVal::Struct(b), Ty::Struct(fields)) => fields
+                .iter()
+                .map(|field| match (a.get(&field.name), b.get(&field.name)) {
+                    (Some(a), Some(b)) => self.cmp_vals(a, b, field.ty),
+                    (a, b) => a.is_some().cmp(&b.is_some()),
+                })
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Ordering::Equal),
+            (
+                StrictVal::List(a) | StrictVal::Set(a),
+                StrictVal::List(b) | StrictVal::Set(b),
+                Ty::Array(ty, _) | Ty::List(ty, _) | Ty::Set(ty, _),
+            ) => a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| self.cmp_vals(a, b, *ty))
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (StrictVal::Map(a), StrictVal::Map(b), Ty::Map(key_ty, ty, _)) => a
+                .iter()
+                .zip(b)
+                .map(|((a_key, a), (b_key, b))| {
+                    self.cmp_vals(a_key, b_key, *key_ty).then_with(|| self.cmp_vals(a, b, *ty))
+                })
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+/// Reader counting the number of consumed bytes.
+struct Tracked<'r, R: ReadRaw> {
+    reader: &'r mut R,
+    pos: u64,
+}
+
+impl<R: ReadRaw> ReadRaw for Tracked<'_, R> {
+    fn read_raw<const MAX_LEN: usize>(&mut self, len: usize) -> io::Result<Vec<u8>> {
+        let data = self.reader.read_raw::<MAX_LEN>(len)?;
+        self.pos += len as u64;
+        Ok(data)
+    }
+
+    fn read_raw_array<const LEN: usize>(&mut self) -> io::Result<[u8; LEN]> {
+        let data = self.reader.read_raw_array::<LEN>()?;
+        self.pos += LEN as u64;
+        Ok(data)
+    }
+}
+
+/// Failure inside a decoded value, which is either not yet located or is already located by a
+/// nested value.
+#[derive(From)]
+enum Failure {
+    #[from]
+    #[from(DecodeError)]
+    #[from(UnknownType)]
+    Kind(ErrorKind),
+
+    Nested(Box<Error>),
+}
+
+impl From<Error> for Failure {
+    fn from(err: Error) -> Self { Failure::Nested(Box::new(err)) }
+}
+
+struct Decoder<'a> {
+    types: &'a TypeSystem,
+    symbols: Option<&'a SymbolicSys>,
+    budget: Budget,
+    path: Path,
+    /// Types of the values which are being decoded, from the outermost to the innermost one.
+    stack: Vec<SemId>,
+    /// Annotations of the decoded bytes, collected only for hex dumps.
+    spans: Option<Vec<Span>>,
+}
+
+pub(super) fn hex_dump<'data>(
+    types: &TypeSystem,
+    symbols: Option<&SymbolicSys>,
+    sem_id: SemId,
+    data: &'data [u8],
+) -> HexDump<'data> {
+    let mut decoder = Decoder::new(types, symbols, DecodeLimits::default());
+    decoder.spans = Some(vec![]);
+    let error = decoder.deserialize(sem_id, data).err();
+    HexDump {
+        data,
+        spans: decoder.spans.unwrap_or_default(),
+        error,
+    }
+}
+
+impl<'a> Decoder<'a> {
+    fn new(types: &'a TypeSystem, symbols: Option<&'a SymbolicSys>, limits: DecodeLimits) -> Self {
+        Decoder {
+            types,
+            symbols,
+            budget: Budget::new(limits),
+            path: Path::new(),
+            stack: vec![],
+            spans: None,
+        }
+    }
+
+    fn deserialize(&mut self, sem_id: SemId, data: &[u8]) -> Result<TypedVal, Error> {
         let mut cursor = StreamReader::cursor::<MAX32>(data);
-        let ty = self.strict_read_type(sem_id, &mut cursor)?;
-        if cursor.unconfine().position() as usize != data.len() {
-            return Err(Error::NotEntirelyConsumed);
+        let typed = self.read_val(sem_id, &mut Tracked {
+            reader: &mut cursor,
+            pos: 0,
+        })?;
+        let pos = cursor.unconfine().position();
+        if pos as usize != data.len() {
+            self.stack.push(sem_id);
+            let mut err = self.error(ErrorKind::NotEntirelyConsumed, pos);
+            err.partial = Some(typed);
+            return Err(err);
         }
-        Ok(ty)
+        Ok(TypedVal {
+            val: typed,
+            orig: TypeSymbol::unnamed(sem_id),
+        })
     }
 
-    pub fn strict_read_type(
-        &self,
+    fn read(mut self, sem_id: SemId, d: &mut impl ReadRaw) -> Result<TypedVal, Error> {
+        let val = self.read_val(sem_id, &mut Tracked { reader: d, pos: 0 })?;
+        Ok(TypedVal {
+            val,
+            orig: TypeSymbol::unnamed(sem_id),
+        })
+    }
+
+    fn annotate(&mut self, start: u64, end: u64, kind: SpanKind, val: impl FnOnce() -> StrictVal) {
+        if let Some(spans) = &mut self.spans {
+            if end > start {
+                spans.push(Span {
+                    offset: start,
+                    len: end - start,
+                    path: self.path.clone(),
+                    kind,
+                    val: val(),
+                });
+            }
+        }
+    }
+
+    fn error(&self, kind: ErrorKind, offset: u64) -> Error {
+        let fqn = self
+            .symbols
+            .and_then(|sys| self.stack.iter().rev().find_map(|id| sys.lookup(*id)))
+            .cloned();
+        Error {
+            kind,
+            offset,
+            path: self.path.clone(),
+            fqn,
+            partial: None,
+        }
+    }
+
+    fn error_at(&mut self, kind: ErrorKind, offset: u64, step: Step) -> Error {
+        let pushed = self.path.push(step).is_ok();
+        let err = self.error(kind, offset);
+        if pushed {
+            self.path.pop();
+        }
+        err
+    }
+
+    fn read_nested<R: ReadRaw>(
+        &mut self,
         sem_id: SemId,
-        mut d: &mut impl ReadRaw,
-    ) -> Result<TypedVal, Error> {
-        let spec = TypeSpec::from(sem_id);
-        let ty = self.find(sem_id).ok_or_else(|| Error::TypeAbsent(spec.clone()))?;
+        d: &mut Tracked<R>,
+        step: Step,
+    ) -> Result<StrictVal, Error> {
+        let pushed = self.path.push(step).is_ok();
+        let val = self.read_val(sem_id, d)?;
+        if pushed {
+            self.path.pop();
+        }
+        Ok(val)
+    }
 
-        let mut reader = StrictReader::with(d);
+    fn read_val<R: ReadRaw>(
+        &mut self,
+        sem_id: SemId,
+        d: &mut Tracked<R>,
+    ) -> Result<StrictVal, Error> {
+        let start = d.pos;
+        self.stack.push(sem_id);
+        let val = self
+            .budget
+            .enter()
+            .map_err(Failure::from)
+            .and_then(|_| self.read_ty(sem_id, d))
+            .map_err(|failure| match failure {
+                Failure::Kind(kind) => self.error(kind, start),
+                Failure::Nested(err) => *err,
+            })?;
+        self.budget.leave();
+        self.stack.pop();
+        Ok(val)
+    }
 
-        let val = match ty {
-            Ty::Primitive(prim) => {
-                match *prim {
-                    Primitive::UNIT => StrictVal::Unit,
-                    Primitive::BYTE => StrictVal::num(u8::strict_decode(&mut reader)?),
-                    Primitive::U8 => StrictVal::num(u8::strict_decode(&mut reader)?),
-                    Primitive::U16 => StrictVal::num(u16::strict_decode(&mut reader)?),
-                    Primitive::U24 => StrictVal::num(u24::strict_decode(&mut reader)?.into_u32()),
-                    Primitive::U32 => StrictVal::num(u32::strict_decode(&mut reader)?),
-                    Primitive::U40 => StrictVal::num(u40::strict_decode(&mut reader)?),
-                    Primitive::U48 => StrictVal::num(u48::strict_decode(&mut reader)?),
-                    Primitive::U56 => StrictVal::num(u56::strict_decode(&mut reader)?),
-                    Primitive::U64 => StrictVal::num(u64::strict_decode(&mut reader)?),
-                    // Primitive::U128 => StrictVal::num(u128::strict_decode(&mut reader)?),
-                    Primitive::I8 => StrictVal::num(i8::strict_decode(&mut reader)?),
-                    Primitive::I16 => StrictVal::num(i16::strict_decode(&mut reader)?),
-                    // I24 => StrictVal::num(i24::strict_decode(&mut reader)?),
-                    Primitive::I32 => StrictVal::num(i32::strict_decode(&mut reader)?),
-                    Primitive::I64 => StrictVal::num(i64::strict_decode(&mut reader)?),
-                    // Primitive::I128 => StrictVal::num(i128::strict_decode(&mut reader)?),
-                    other => {
-                        return Err(Error::NotImplemented(format!(
-                            "loading {other} into a typed value is not yet implemented"
-                        )));
-                    }
+    fn read_len<R: ReadRaw>(&mut self, d: &mut Tracked<R>, sizing: Sizing) -> Result<u64, Failure> {
+        let start = d.pos;
+        let mut reader = StrictReader::with(&mut *d);
+        let len = if sizing.max <= u8::MAX as u64 {
+            u8::strict_decode(&mut reader)? as u64
+        } else if sizing.max <= u16::MAX as u64 {
+            u16::strict_decode(&mut reader)? as u64
+        } else if sizing.max <= u24::MAX.into_u64() {
+            u24::strict_decode(&mut reader)?.into_u64()
+        } else if sizing.max <= u32::MAX as u64 {
+            u32::strict_decode(&mut reader)? as u64
+        } else {
+            u64::strict_decode(&mut reader)?
+        };
+        self.annotate(start, d.pos, SpanKind::Len, || StrictVal::num(len));
+        check_len(len, sizing)?;
+        Ok(len)
+    }
+
+    fn read_bytes<R: ReadRaw>(&mut self, len: u64, d: &mut Tracked<R>) -> Result<Vec<u8>, Failure> {
+        self.budget.bytes(len)?;
+        // Data are read by chunks, such that the memory is not allocated before the data really
+        // arrive.
+        let mut bytes = Vec::new();
+        while (bytes.len() as u64) < len {
+            let chunk = (len - bytes.len() as u64).min(READ_CHUNK as u64) as usize;
+            bytes.extend(d.read_raw::<READ_CHUNK>(chunk).map_err(DecodeError::from)?);
+        }
+        Ok(bytes)
+    }
+
+    /// Reads byte or character string content, converting it into a value.
+    fn read_data<R: ReadRaw>(
+        &mut self,
+        len: u64,
+        d: &mut Tracked<R>,
+        convert: impl FnOnce(Vec<u8>) -> Result<StrictVal, DecodeError>,
+    ) -> Result<StrictVal, Failure> {
+        let start = d.pos;
+        let val = convert(self.read_bytes(len, d)?)?;
+        self.annotate(start, d.pos, SpanKind::Data, || val.clone());
+        Ok(val)
+    }
+
+    fn read_ascii<R: ReadRaw>(
+        &mut self,
+        sizing: Sizing,
+        d: &mut Tracked<R>,
+    ) -> Result<StrictVal, Failure> {
+        let len = self.read_len(d, sizing)?;
+        self.read_data(len, d, |bytes| {
+            let s = AsciiString::from_ascii(bytes).map_err(|err| err.ascii_error())?;
+            Ok(StrictVal::String(s.to_string()))
+        })
+    }
+
+    fn read_items<R: ReadRaw>(
+        &mut self,
+        len: u64,
+        ty: SemId,
+        d: &mut Tracked<R>,
+        set: bool,
+    ) -> Result<StrictVal, Failure> {
+        let wrap = if set { StrictVal::Set } else { StrictVal::List };
+        self.budget.items(len, size_of::<StrictVal>())?;
+        let mut items = Vec::<StrictVal>::new();
+        for no in 0..len {
+            let start = d.pos;
+            let step = Step::Index(no as u32);
+            let item = match self.read_nested(ty, d, step.clone()) {
+                Ok(item) => item,
+                Err(err) => {
+                    return Err(err
+                        .nest(|last| {
+                            items.extend(last);
+                            wrap(items)
+                        })
+                        .into())
                 }
+            };
+            let broken = match items.last() {
+                Some(last) if set => match self.types.cmp_vals(last, &item, ty) {
+                    Ordering::Less => None,
+                    Ordering::Equal => Some(DecodeError::RepeatedSetValue),
+                    Ordering::Greater => Some(DecodeError::BrokenSetOrder),
+                },
+                _ => None,
+            };
+            items.push(item);
+            if let Some(kind) = broken {
+                let mut err = self.error_at(kind.into(), start, step);
+                err.partial = Some(wrap(items));
+                return Err(err.into());
+            }
+        }
+        Ok(wrap(items))
+    }
+
+    fn read_map<R: ReadRaw>(
+        &mut self,
+        len: u64,
+        key_ty: SemId,
+        ty: SemId,
+        d: &mut Tracked<R>,
+    ) -> Result<StrictVal, Failure> {
+        self.budget.items(len, size_of::<(StrictVal, StrictVal)>())?;
+        let mut map = Vec::<(StrictVal, StrictVal)>::new();
+        for no in 0..len {
+            let start = d.pos;
+            let step = Step::Index(no as u32);
+            let key = match self.read_nested(key_ty, d, step.clone()) {
+                Ok(key) => key,
+                Err(err) => return Err(err.nest(|_| StrictVal::Map(map)).into()),
+            };
+            let broken = match map.last() {
+                Some((last, _)) => match self.types.cmp_vals(last, &key, key_ty) {
+                    Ordering::Less => None,
+                    Ordering::Equal => Some(DecodeError::RepeatedMapValue),
+                    Ordering::Greater => Some(DecodeError::BrokenMapOrder),
+                },
+                None => None,
+            };
+            if let Some(kind) = broken {
+                let mut err = self.error_at(kind.into(), start, step);
+                err.partial = Some(StrictVal::Map(map));
+                return Err(err.into());
+            }
+            let step = KeyStep::with_val(&key).map(Step::Key).unwrap_or(step);
+            match self.read_nested(ty, d, step) {
+                Ok(val) => map.push((key, val)),
+                Err(err) => {
+                    return Err(err
+                        .nest(|last| {
+                            map.extend(last.map(|val| (key, val)));
+                            StrictVal::Map(map)
+                        })
+                        .into())
+                }
+            }
+        }
+        Ok(StrictVal::Map(map))
+    }
+
+    fn read_ty<R: ReadRaw>(
+        &mut self,
+        sem_id: SemId,
+        d: &mut Tracked<R>,
+    ) -> Result<StrictVal, Failure> {
+        let types = self.types;
+        let spec = TypeSpec::from(sem_id);
+        let ty = types.find(sem_id).ok_or_else(|| ErrorKind::TypeAbsent(spec.clone()))?;
+
+        let start = d.pos;
+        Ok(match ty {
+            Ty::Primitive(prim) => {
+                let val = TypeSystem::strict_read_primitive(*prim, d)?;
+                self.annotate(start, d.pos, SpanKind::Primitive(*prim), || val.clone());
+                val
             }
             Ty::UnicodeChar => {
-                todo!()
+                let [first] = d.read_raw_array::<1>().map_err(DecodeError::from)?;
+                // Invalid leading bytes are reported by the UTF-8 check below
+                let len = match first.leading_ones() {
+                    2..=4 => first.leading_ones() as usize,
+                    _ => 1,
+                };
+                let mut bytes = vec![first];
+                bytes.extend(d.read_raw::<4>(len - 1).map_err(DecodeError::from)?);
+                let val = StrictVal::String(String::from_utf8(bytes).map_err(DecodeError::from)?);
+                self.annotate(start, d.pos, SpanKind::Char, || val.clone());
+                val
             }
 
             // ASCII strings:
             Ty::List(sem_id, sizing)
-                if self
+                if types
                     .find(*sem_id)
-                    .ok_or_else(|| Error::TypeAbsent(spec.clone()))?
+                    .ok_or_else(|| ErrorKind::TypeAbsent(spec.clone()))?
                     .is_char_enum() =>
             {
-                if sizing.max <= u8::MAX as u64 {
-                    StrictVal::String(TinyAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u16::MAX as u64 {
-                    StrictVal::String(SmallAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u24::MAX.into_u64() {
-                    StrictVal::String(MediumAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u32::MAX as u64 {
-                    StrictVal::String(LargeAscii::strict_decode(&mut reader)?.to_string())
-                } else {
-                    StrictVal::String(
-                        Confined::<AsciiString, 0, { u64::MAX as usize }>::strict_decode(
-                            &mut reader,
-                        )?
-                        .to_string(),
-                    )
-                }
+                self.read_ascii(*sizing, d)?
             }
             // Restricted strings:
-            Ty::Tuple(fields) if self.is_rstring(fields)? => {
-                let (_, sizing) = self.rstring_sizing(fields)?.expect("checked in match");
-                if sizing.max <= u8::MAX as u64 {
-                    StrictVal::String(TinyAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u16::MAX as u64 {
-                    StrictVal::String(SmallAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u24::MAX.into_u64() {
-                    StrictVal::String(MediumAscii::strict_decode(&mut reader)?.to_string())
-                } else if sizing.max <= u32::MAX as u64 {
-                    StrictVal::String(LargeAscii::strict_decode(&mut reader)?.to_string())
-                } else {
-                    StrictVal::String(
-                        Confined::<AsciiString, 0, { u64::MAX as usize }>::strict_decode(
-                            &mut reader,
-                        )?
-                        .to_string(),
-                    )
-                }
+            Ty::Tuple(fields) if types.is_rstring(fields)? => {
+                let (_, sizing) = types.rstring_sizing(fields)?.expect("checked in match");
+                self.read_ascii(sizing, d)?
             }
 
             Ty::Enum(variants) => {
-                let tag = u8::strict_decode(&mut reader)?;
+                let [tag] = d.read_raw_array::<1>().map_err(DecodeError::from)?;
                 let Some(name) = variants.name_by_tag(tag) else {
                     return Err(DecodeError::EnumTagNotKnown(spec.to_string(), tag).into());
                 };
+                self.annotate(start, d.pos, SpanKind::Tag, || StrictVal::enumer(name.clone()));
                 StrictVal::enumer(name.clone())
             }
             Ty::Union(variants) => {
-                let tag = u8::strict_decode(&mut reader)?;
+                let [tag] = d.read_raw_array::<1>().map_err(DecodeError::from)?;
                 let Some((variant, ty)) = variants.by_tag(tag) else {
                     return Err(DecodeError::EnumTagNotKnown(spec.to_string(), tag).into());
                 };
-                let fields = self.strict_read_type(*ty, reader.unbox())?;
-                StrictVal::union(variant.name.clone(), fields.val)
+                let name = &variant.name;
+                self.annotate(start, d.pos, SpanKind::Tag, || StrictVal::enumer(name.clone()));
+                self.read_val(*ty, d).map(|val| StrictVal::union(name.clone(), val)).map_err(
+                    |err| {
+                        err.nest(|last| {
+                            StrictVal::union(name.clone(), last.unwrap_or(StrictVal::Unit))
+                        })
+                    },
+                )?
             }
             Ty::Tuple(reqs) => {
                 let mut fields = Vec::with_capacity(reqs.len());
-                let d = reader.unbox();
-                for ty in reqs {
-                    let checked = self.strict_read_type(*ty, d)?;
-                    fields.push(checked.val);
+                for (no, ty) in reqs.iter().enumerate() {
+                    match self.read_nested(*ty, d, Step::UnnamedField(no as u8)) {
+                        Ok(val) => fields.push(val),
+                        Err(err) => {
+                            return Err(err
+                                .nest(|last| {
+                                    fields.extend(last);
+                                    StrictVal::tuple(fields)
+                                })
+                                .into())
+                        }
+                    }
                 }
                 StrictVal::tuple(fields)
             }
             Ty::Struct(reqs) => {
                 let mut fields = IndexMap::with_capacity(reqs.len());
-                let d = reader.unbox();
                 for field in reqs {
-                    let checked = self.strict_read_type(field.ty, d)?;
-                    fields.insert(field.name.clone(), checked.val);
+                    let step = Step::NamedField(field.name.clone());
+                    match self.read_nested(field.ty, d, step) {
+                        Ok(val) => {
+                            fields.insert(field.name.clone(), val);
+                        }
+                        Err(err) => {
+                            return Err(err
+                                .nest(|last| {
+                                    fields.extend(last.map(|val| (field.name.clone(), val)));
+                                    StrictVal::Struct(fields)
+                                })
+                                .into())
+                        }
+                    }
                 }
                 StrictVal::Struct(fields)
             }
 
             // Fixed-size arrays:
             Ty::Array(ty, len) if ty.is_byte() => {
-                let d = reader.unbox();
-                let buf = d.read_raw::<MAX16>(*len as usize).map_err(DecodeError::from)?;
-                StrictVal::Bytes(Blob(buf))
-            }
-            Ty::Array(ty, len) => {
-                let mut list = Vec::<StrictVal>::with_capacity(*len as usize);
-                let d = reader.unbox();
-                for _ in 0..*len {
-                    let checked = self.strict_read_type(*ty, d)?;
-                    list.push(checked.val);
-                }
-                StrictVal::List(list)
+                self.read_data(*len as u64, d, |bytes| Ok(StrictVal::Bytes(Blob(bytes))))?
             }
+            Ty::Array(ty, len) => self.read_items(*len as u64, *ty, d, false)?,
 
             // Byte strings:
-            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u8::MAX as u64 => {
-                let string = TinyBlob::strict_decode(&mut reader)?;
-                StrictVal::Bytes(Blob(string.release()))
-            }
-            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u16::MAX as u64 => {
-                let string = SmallBlob::strict_decode(&mut reader)?;
-                StrictVal::Bytes(Blob(string.release()))
-            }
-            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u24::MAX.into_u64() => {
-                let string = MediumBlob::strict_decode(&mut reader)?;
-                StrictVal::Bytes(Blob(string.release()))
-            }
             Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u32::MAX as u64 => {
-                let string = LargeBlob::strict_decode(&mut reader)?;
-                StrictVal::Bytes(Blob(string.release()))
+                let len = self.read_len(d, *sizing)?;
+                self.read_data(len, d, |bytes| Ok(StrictVal::Bytes(Blob(bytes))))?
             }
 
             // Unicode strings:
-            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u8::MAX as u64 => {
-                let string = TinyString::strict_decode(&mut reader)?;
-                StrictVal::String(string.release())
-            }
-            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u16::MAX as u64 => {
-                let string = SmallString::strict_decode(&mut reader)?;
-                StrictVal::String(string.release())
-            }
-            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u24::MAX.into_u64() => {
-                let string = MediumString::strict_decode(&mut reader)?;
-                StrictVal::String(string.release())
-            }
             Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u32::MAX as u64 => {
-                let string = LargeString::strict_decode(&mut reader)?;
-                StrictVal::String(string.release())
+                let len = self.read_len(d, *sizing)?;
+                self.read_data(len, d, |bytes| Ok(StrictVal::String(String::from_utf8(bytes)?)))?
             }
 
-            // Other lists:
-            Ty::List(ty, sizing) if sizing.max <= u8::MAX as u64 => {
-                let len = u8::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::List(list)
-            }
-            Ty::List(ty, sizing) if sizing.max <= u16::MAX as u64 => {
-                let len = u16::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::List(list)
-            }
-            Ty::List(ty, sizing) if sizing.max <= u24::MAX.into_u64() => {
-                let len = u24::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len.into_usize(), *ty, d)?;
-                StrictVal::List(list)
-            }
-            Ty::List(ty, sizing) if sizing.max <= u32::MAX as u64 => {
-                let len = u32::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::List(list)
-            }
-            Ty::List(ty, _) => {
-                let len = u64::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::List(list)
+            // Other collections:
+            Ty::List(ty, sizing) => {
+                let len = self.read_len(d, *sizing)?;
+                self.read_items(len, *ty, d, false)?
             }
-            // TODO: Find a way to check for the uniqueness of the set values
-            Ty::Set(ty, sizing) if sizing.max <= u8::MAX as u64 => {
-                let len = u8::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::Set(list)
+            Ty::Set(ty, sizing) => {
+                let len = self.read_len(d, *sizing)?;
+                self.read_items(len, *ty, d, true)?
             }
-            Ty::Set(ty, sizing) if sizing.max <= u16::MAX as u64 => {
-                let len = u16::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::Set(list)
+            Ty::Map(key_id, id, sizing) => {
+                let len = self.read_len(d, *sizing)?;
+                self.read_map(len, *key_id, *id, d)?
             }
-            Ty::Set(ty, sizing) if sizing.max <= u24::MAX.into_u64() => {
-                let len = u24::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len.into_usize(), *ty, d)?;
-                StrictVal::Set(list)
-            }
-            Ty::Set(ty, sizing) if sizing.max <= u32::MAX as u64 => {
-                let len = u32::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::Set(list)
-            }
-            Ty::Set(ty, _) => {
-                let len = u64::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_list(len as usize, *ty, d)?;
-                StrictVal::Set(list)
-            }
-            Ty::Map(key_id, id, sizing) if sizing.max <= u8::MAX as u64 => {
-                let len = u8::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_map(len as usize, *key_id, *id, d)?;
-                StrictVal::Map(list)
-            }
-            Ty::Map(key_id, id, sizing) if sizing.max <= u16::MAX as u64 => {
-                let len = u16::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_map(len as usize, *key_id, *id, d)?;
-                StrictVal::Map(list)
-            }
-            Ty::Map(key_id, id, sizing) if sizing.max <= u24::MAX.into_u64() => {
-                let len = u24::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_map(len.into_usize(), *key_id, *id, d)?;
-                StrictVal::Map(list)
-            }
-            Ty::Map(key_id, id, sizing) if sizing.max <= u32::MAX as u64 => {
-                let len = u32::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_map(len as usize, *key_id, *id, d)?;
-                StrictVal::Map(list)
-            }
-            Ty::Map(key_id, id, _sizing) => {
-                let len = u64::strict_decode(&mut reader)?;
-                d = reader.unbox();
-                let list = self.strict_read_map(len as usize, *key_id, *id, d)?;
-                StrictVal::Map(list)
-            }
-        };
-
-        Ok(TypedVal {
-            val,
-            orig: TypeSymbol::unnamed(sem_id),
         })
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
+    use encoding::{FieldName, StrictSerialize};
+
     use super::super::test_helpers::*;
-    // use super::*;
+    use super::*;
+    use crate::stl::strict_types_stl;
+    use crate::typify;
+
+    const PRIMITIVES: [Primitive; 32] = [
+        Primitive::BYTE,
+        Primitive::U8,
+        Primitive::U16,
+        Primitive::U24,
+        Primitive::U32,
+        Primitive::U40,
+        Primitive::U48,
+        Primitive::U56,
+        Primitive::U64,
+        Primitive::U128,
+        Primitive::U160,
+        Primitive::U256,
+        Primitive::U512,
+        Primitive::U1024,
+        Primitive::I8,
+        Primitive::I16,
+        Primitive::I24,
+        Primitive::I32,
+        Primitive::I40,
+        Primitive::I48,
+        Primitive::I56,
+        Primitive::I64,
+        Primitive::I128,
+        Primitive::I256,
+        Primitive::I512,
+        Primitive::I1024,
+        Primitive::N8,
+        Primitive::N16,
+        Primitive::N24,
+        Primitive::N32,
+        Primitive::N64,
+        Primitive::N128,
+    ];
+
+    fn primitive_system() -> SymbolicSys {
+        let mut src = s!("typelib Prims\ndata Char : Unicode\n");
+        src.push_str("data Floats : F16, F16b, F32, F64, F80, F128, F256\n");
+        src.push_str("data Numbers : ");
+        let fields = PRIMITIVES
+            .iter()
+            .enumerate()
+            .map(|(no, prim)| format!("f{no} {prim}"))
+            .collect::<Vec<_>>();
+        src.push_str(&fields.join(", "));
+        system_from_source(&src)
+    }
+
+    fn sample(prim: Primitive) -> StrictVal {
+        let len = prim.byte_size() as usize;
+        let signed = prim.info().ty == NumCls::Signed && prim != Primitive::BYTE;
+        // The most negative value for signed types and `0xFF..FE` for unsigned ones
+        let mut buf = [if signed { 0x00 } else { 0xFF }; 128];
+        if signed {
+            buf[len..].fill(0xFF);
+            buf[len - 1] = 0x80;
+        } else {
+            buf[0] = 0xFE;
+            buf[len..].fill(0x00);
+        }
+        match (signed, len) {
+            (false, ..=8) => StrictVal::num(u1024::from_le_bytes(buf).low_u64()),
+            (true, ..=8) => StrictVal::num(u1024::from_le_bytes(buf).low_u64() as i64),
+            (false, _) => StrictVal::num(u1024::from_le_bytes(buf)),
+            (true, _) => StrictVal::num(i1024::from_le_bytes(buf)),
+        }
+    }
+
+    #[test]
+    fn primitives_roundtrip() {
+        let sys = primitive_system();
+        let val = StrictVal::Struct(
+            PRIMITIVES
+                .iter()
+                .enumerate()
+                .map(|(no, prim)| (FieldName::try_from(format!("f{no}")).unwrap(), sample(*prim)))
+                .collect(),
+        );
+        let typed = sys.typify(val.clone(), "Prims.Numbers").unwrap();
+        assert_eq!(typed.as_val(), &val);
+
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&typed).unwrap();
+        let size = PRIMITIVES.iter().map(|prim| prim.byte_size() as usize).sum::<usize>();
+        assert_eq!(data.len(), size);
+        let decoded = sys.strict_deserialize_type("Prims.Numbers", &data).unwrap();
+        assert_eq!(decoded.val, val);
+    }
+
+    #[test]
+    fn floats_roundtrip() {
+        let sys = primitive_system();
+        let val = ston!(1.5f32, -0.1f64, 0.1f64, 1e300, -1.0f64, f64::INFINITY, -0.0f64);
+        let typed = sys.typify(val, "Prims.Floats").unwrap();
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&typed).unwrap();
+        assert_eq!(data.len(), 2 + 2 + 4 + 8 + 10 + 16 + 32);
+        assert_eq!(&data[..2], &[0x00, 0x3E]);
+        assert_eq!(&data[4..8], &(0.1f32).to_bits().to_le_bytes());
+        assert_eq!(&data[8..16], &(1e300f64).to_bits().to_le_bytes());
+        assert_eq!(&data[16..26], &[0, 0, 0, 0, 0, 0, 0, 0x80, 0xFF, 0xBF]);
+        let decoded = sys.strict_deserialize_type("Prims.Floats", &data).unwrap();
+        assert_eq!(decoded.val, typed.val);
+        let reparsed = StrictVal::from_str(&decoded.val.to_string()).unwrap();
+        assert_eq!(sys.typify(reparsed, "Prims.Floats").unwrap(), typed);
+        assert_eq!(
+            decoded.val.to_string(),
+            "1.5, -0.10009765625, 0.100000001490116119384765625, \
+             1.000000000000000052504760255204420248704468581108159154915854115511802458E+300, \
+             -1.0, inf, -0.0"
+        );
+
+        let err = sys.typify(ston!(1e300, 0, 0, 0, 0, 0, 0), "Prims.Floats").unwrap_err();
+        assert!(matches!(err, typify::Error::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn unicode_char() {
+        let sys = primitive_system();
+        for c in ["a", "ß", "€", "🦀"] {
+            let typed = sys.typify(svstr!(c), "Prims.Char").unwrap();
+            let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&typed).unwrap();
+            assert_eq!(data.as_slice(), c.as_bytes());
+            let decoded = sys.strict_deserialize_type("Prims.Char", &data).unwrap();
+            assert_eq!(decoded.val, svnewtype!(c));
+        }
+        assert!(sys.typify(svstr!("ab"), "Prims.Char").is_err());
+        assert!(matches!(
+            sys.strict_deserialize_type("Prims.Char", &[0xE2, 0x28, 0xA1]).map_err(|err| err.kind),
+            Err(ErrorKind::Decode(DecodeError::Utf8(_)))
+        ));
+        assert!(matches!(
+            sys.strict_deserialize_type("Prims.Char", &[0x80]).map_err(|err| err.kind),
+            Err(ErrorKind::Decode(DecodeError::Utf8(_)))
+        ));
+    }
+
+    #[test]
+    fn non_zero() {
+        let sys = primitive_system();
+        let mut data = vec![0xFFu8; 1000];
+        // `f26` is `N8`
+        let offset = PRIMITIVES[..26].iter().map(|prim| prim.byte_size() as usize).sum::<usize>();
+        data[offset] = 0;
+        let err = sys
+            .strict_read_type("Prims.Numbers", &mut StreamReader::cursor::<MAX32>(data))
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Decode(DecodeError::ZeroNatural));
+        assert_eq!(err.offset, offset as u64);
+        assert_eq!(err.path.to_string(), ".f26");
+        assert_eq!(err.fqn, Some(TypeFqn::from("Prims.Numbers")));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "decoding failed at byte {offset} in value `.f26` of type `Prims.Numbers`: {}",
+                DecodeError::ZeroNatural
+            )
+        );
+        let StrictVal::Struct(partial) = err.partial.unwrap() else {
+            panic!("partial value is not a structure")
+        };
+        assert_eq!(partial.len(), 26);
+    }
+
+    #[test]
+    fn set_order() {
+        let src = "typelib Sets
+data Kind : alpha#2 | beta#1
+data Nums : {U16 ^ ..0xff}
+data Index : {Kind -> ^ ..0xff U8}
+";
+        let sys = system_from_source(src);
+
+        let nums = sys.strict_deserialize_type("Sets.Nums", &[2, 1, 0, 0, 1]).unwrap();
+        assert_eq!(nums.val, StrictVal::newtype(StrictVal::set([1u16, 0x100])));
+        let err = sys.strict_deserialize_type("Sets.Nums", &[2, 0, 1, 1, 0]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Decode(DecodeError::BrokenSetOrder));
+        assert_eq!((err.offset, err.path.to_string()), (3, s!(".0[1]")));
+        assert_eq!(err.partial, Some(StrictVal::newtype(StrictVal::set([0x100u16, 1]))));
+        let err = sys.strict_deserialize_type("Sets.Nums", &[2, 1, 0, 1, 0]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Decode(DecodeError::RepeatedSetValue));
+
+        let index = sys.strict_deserialize_type("Sets.Index", &[2, 1, 5, 2, 6]).unwrap();
+        assert_eq!(
+            index.val,
+            StrictVal::newtype(StrictVal::map([(svenum!(beta), 5u8), (svenum!(alpha), 6u8)]))
+        );
+        let err = sys.strict_deserialize_type("Sets.Index", &[2, 2, 5, 1, 6]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Decode(DecodeError::BrokenMapOrder));
+        assert_eq!((err.offset, err.path.to_string()), (3, s!(".0[1]")));
+        assert_eq!(err.partial, Some(StrictVal::newtype(StrictVal::map([(svenum!(alpha), 5u8)]))));
+        let err = sys.strict_deserialize_type("Sets.Index", &[2, 1, 5, 1, 6]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Decode(DecodeError::RepeatedMapValue));
+    }
+
+    #[test]
+    fn limits() {
+        let src = "typelib Limits
+data Nums : [U16 ^ ..0xff]
+data Data : [Byte ^ ..0xffffffff]
+data Name : [Unicode ^ 1..0x10]
+";
+        let sys = system_from_source(src);
+        let err = sys.strict_deserialize_type("Limits.Name", &[0]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::LengthOutOfBounds {
+            len: 0,
+            min: 1,
+            max: 16
+        });
+        assert_eq!((err.offset, err.path.to_string()), (0, s!(".0")));
+        let err = sys.strict_deserialize_type("Limits.Name", &[0x11; 18]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::LengthOutOfBounds {
+            len: 0x11,
+            min: 1,
+            max: 16
+        });
+
+        let nums = [2, 1, 0, 2, 0];
+        let limited = |limits| sys.strict_deserialize_type_limited("Limits.Nums", &nums, limits);
+
+        assert!(limited(DecodeLimits::default()).is_ok());
+        let limits = DecodeLimits {
+            max_depth: 2,
+            ..default!()
+        };
+        assert_eq!(limited(limits).unwrap_err().kind, ErrorKind::DepthLimit(2));
+        let limits = DecodeLimits {
+            max_len: 1,
+            ..default!()
+        };
+        assert_eq!(limited(limits).unwrap_err().kind, ErrorKind::LengthLimit { len: 2, max: 1 });
+        let limits = DecodeLimits {
+            max_items: 1,
+            ..default!()
+        };
+        assert_eq!(limited(limits).unwrap_err().kind, ErrorKind::ItemsLimit(1));
+        let limits = DecodeLimits {
+            max_alloc: 16,
+            ..default!()
+        };
+        assert_eq!(limited(limits).unwrap_err().kind, ErrorKind::AllocLimit(16));
+
+        // The length is not trusted to allocate memory even when decoding is unlimited
+        let data = [0xFF; 4];
+        assert!(matches!(
+            sys.strict_deserialize_type("Limits.Data", &data).map_err(|err| err.kind),
+            Err(ErrorKind::Decode(DecodeError::Io(_)))
+        ));
+        assert_eq!(
+            sys.strict_deserialize_type_limited("Limits.Data", &data, default!())
+                .map_err(|err| err.kind),
+            Err(ErrorKind::LengthLimit {
+                len: u32::MAX as u64,
+                max: 0x1_0000
+            })
+        );
+
+        let lib = strict_types_stl().to_strict_serialized::<{ usize::MAX }>().unwrap();
+        test_system()
+            .strict_deserialize_type_limited("StrictTypes.TypeLib", &lib, default!())
+            .unwrap();
+    }
+
+    #[test]
+    fn compiled_set_order() {
+        let sys = test_system();
+        let lib = strict_types_stl();
+        let data = lib.to_strict_serialized::<{ usize::MAX }>().unwrap();
+        sys.strict_deserialize_type("StrictTypes.TypeLib", &data).unwrap();
+    }
 
     #[test]
     fn typify() {
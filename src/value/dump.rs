@@ -0,0 +1,239 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Annotated hex dumps of strict encoded data, overlaying the bytes with the decoded type
+//! structure.
+//!
+//! The dump is produced by the same traversal as used by [`TypeSystem::strict_read_type`], such
+//! that each byte of the data is attributed to a primitive value, a collection length prefix, an
+//! enum or union tag or to the content of a byte or character string.
+
+use std::fmt::{self, Display, Formatter};
+
+use encoding::Primitive;
+
+use super::decode::{self, Error};
+use super::typify::TypeSpec;
+use super::{Path, StrictVal};
+use crate::{SemId, SymbolicSys, TypeSystem};
+
+/// Number of bytes in a line of a hex dump.
+const LINE_LEN: usize = 16;
+
+/// Kind of the bytes in an annotated span.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display)]
+pub enum SpanKind {
+    /// Primitive value.
+    #[display(inner)]
+    Primitive(Primitive),
+
+    /// Unicode character.
+    #[display("Unicode")]
+    Char,
+
+    /// Length prefix of a collection or a string.
+    #[display("len")]
+    Len,
+
+    /// Tag of an enum or union variant.
+    #[display("tag")]
+    Tag,
+
+    /// Content of a byte or character string.
+    #[display("data")]
+    Data,
+}
+
+/// Range of the encoded data attributed to a single item of the decoded value.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Span {
+    pub offset: u64,
+    pub len: u64,
+    /// Path to the value to which the bytes belong.
+    pub path: Path,
+    pub kind: SpanKind,
+    /// Value decoded from the bytes: a number for primitives and length prefixes, a variant
+    /// name for tags and a string or bytes for the string content.
+    pub val: StrictVal,
+}
+
+/// Annotated hex dump of strict encoded data.
+///
+/// The dump is displayed in the `xxd` style, with each line starting with the offset, followed by
+/// the bytes of a single span and its annotation. Bytes following the decoding failure or not
+/// consumed by the decoder are displayed at the end without annotations, followed by the error.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct HexDump<'data> {
+    pub data: &'data [u8],
+    pub spans: Vec<Span>,
+    /// Decoding failure, if the data doesn't match the type.
+    pub error: Option<Error>,
+}
+
+impl SymbolicSys {
+    pub fn hex_dump<'data>(&self, spec: impl Into<TypeSpec>, data: &'data [u8]) -> HexDump<'data> {
+        match self.resolve_spec(spec) {
+            Ok(sem_id) => decode::hex_dump(self.as_types(), Some(self), sem_id, data),
+            Err(err) => HexDump {
+                data,
+                spans: vec![],
+                error: Some(err.report(decode::ErrorKind::TypeAbsent).into()),
+            },
+        }
+    }
+}
+
+impl TypeSystem {
+    /// Decodes the data as a value of the given type, annotating each range of bytes with the
+    /// part of the value it encodes. Decoding is restricted by the default
+    /// [`DecodeLimits`](decode::DecodeLimits).
+    pub fn hex_dump<'data>(&self, sem_id: SemId, data: &'data [u8]) -> HexDump<'data> {
+        decode::hex_dump(self, None, sem_id, data)
+    }
+}
+
+impl HexDump<'_> {
+    /// Checks whether the data were entirely decoded.
+    pub fn is_complete(&self) -> bool { self.error.is_none() }
+
+    fn fmt_bytes(
+        &self,
+        f: &mut Formatter<'_>,
+        offset: usize,
+        len: usize,
+        label: Option<String>,
+    ) -> fmt::Result {
+        let bytes = &self.data[offset..offset + len];
+        let mut label = label;
+        for (no, line) in bytes.chunks(LINE_LEN).enumerate() {
+            let hex = line.iter().map(|byte| format!("{byte:02x}")).collect::<Vec<_>>().join(" ");
+            write!(f, "{:08x}  ", offset + no * LINE_LEN)?;
+            match label.take() {
+                Some(label) => writeln!(f, "{hex:<width$}  {label}", width = LINE_LEN * 3 - 1)?,
+                None => writeln!(f, "{hex}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut end = 0usize;
+        for span in &self.spans {
+            let mut label = format!("{:<7}", span.kind.to_string());
+            if !span.path.is_empty() {
+                label.push(' ');
+                label.push_str(&span.path.to_string());
+            }
+            // Byte content is already displayed in the hex part of the dump
+            if !matches!(span.val, StrictVal::Bytes(_)) {
+                label.push_str(&format!(" = {}", span.val));
+            }
+            end = (span.offset + span.len) as usize;
+            self.fmt_bytes(f, span.offset as usize, span.len as usize, Some(label))?;
+        }
+        if end < self.data.len() {
+            self.fmt_bytes(f, end, self.data.len() - end, None)?;
+        }
+        if let Some(err) = &self.error {
+            writeln!(f, "{err}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_helpers::*;
+    use super::*;
+    use crate::typesys::TypeFqn;
+
+    fn system() -> SymbolicSys {
+        events_system(
+            "data Batch : name [Unicode ^ ..0xff], tag [Byte ^ 4], events [Event ^ ..0xff]\n",
+        )
+    }
+
+    #[test]
+    fn spans() {
+        let sys = system();
+        let data = [0x02, 0x41, 0x42, 0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x00, 0x01, 0x00, 0x02, 0, 0];
+        let dump = sys.hex_dump("Events.Batch", &data);
+        assert!(dump.is_complete());
+        assert_eq!(dump.spans.len(), 7);
+        assert_eq!(dump.spans.iter().map(|span| span.len).sum::<u64>(), data.len() as u64);
+        let span = &dump.spans[5];
+        assert_eq!((span.offset, span.len), (9, 2));
+        assert_eq!(span.path.to_string(), ".events[0].0");
+        assert_eq!(span.kind, SpanKind::Primitive(Primitive::U16));
+        assert_eq!(span.val, StrictVal::num(1u64));
+        assert_eq!(
+            dump.to_string(),
+            "\
+00000000  02                                               len     .name = 2
+00000001  41 42                                            data    .name = \"AB\"
+00000003  de ad be ef                                      data    .tag
+00000007  01                                               len     .events = 1
+00000008  00                                               tag     .events[0] = transfer
+00000009  01 00                                            U16     .events[0].0 = 1
+0000000b  02 00 00                                         U24     .events[0].1 = 2
+"
+        );
+    }
+
+    #[test]
+    fn failure() {
+        let sys = system();
+        let mut data = vec![0x14];
+        data.extend(b"0123456789abcdefghij");
+        data.extend([0, 0, 0, 0, 0x01, 0x01, 0x2A, 0, 0, 0, 0, 0, 0, 0, 0x03]);
+        let dump = sys.hex_dump("Events.Batch", &data);
+        assert!(!dump.is_complete());
+        let err = dump.error.as_ref().unwrap();
+        assert_eq!(err.offset, 35);
+        assert_eq!(err.path.to_string(), ".events[0].kind");
+        assert_eq!(err.fqn.as_ref().map(TypeFqn::to_string).as_deref(), Some("Events.Kind"));
+        let out = dump.to_string();
+        let (lines, _) = out.rsplit_once("decoding failed").unwrap();
+        assert_eq!(
+            lines,
+            "\
+00000000  14                                               len     .name = 20
+00000001  30 31 32 33 34 35 36 37 38 39 61 62 63 64 65 66  data    .name = \"0123456789abcdefghij\"
+00000011  67 68 69 6a
+00000015  00 00 00 00                                      data    .tag
+00000019  01                                               len     .events = 1
+0000001a  01                                               tag     .events[0] = settled
+0000001b  2a 00 00 00 00 00 00 00                          U64     .events[0].at = 42
+00000023  03
+"
+        );
+
+        let dump = sys.hex_dump("Events.Absent", &data);
+        assert!(dump.spans.is_empty());
+        assert_eq!(
+            dump.error.map(|err| err.kind),
+            Some(decode::ErrorKind::TypeAbsent(TypeSpec::from("Events.Absent")))
+        );
+    }
+}
@@ -20,18 +20,77 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Serializes strict values into binary strict encoding, checking them against the type system.
+
+use std::cmp::Ordering;
 use std::io;
 
-use amplify::confinement::{Confined, ConfinedBlob};
-use amplify::num::u24;
+use amplify::confinement::{self, Confined, ConfinedBlob};
+use amplify::num::{i1024, u1024};
 use encoding::{
-    Primitive, SerializeError, Sizing, StrictEncode, StrictSerialize, StrictType, TypeName,
+    NumCls, Primitive, SerializeError, Sizing, StrictEncode, StrictSerialize, StrictType, TypeName,
     TypedWrite, WriteRaw,
 };
 
 use crate::typify::TypedVal;
-use crate::value::{EnumTag, StrictNum};
-use crate::{SemId, StrictVal, Ty, TypeSystem};
+use crate::util::SizingExt;
+use crate::value::{float, EnumTag, KeyStep, Path, Step, StrictNum};
+use crate::{SemId, StrictVal, Ty, TypeRef, TypeSystem};
+
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum Error {
+    /// unknown type `{id}` at `{path}`.
+    TypeAbsent { path: Path, id: SemId },
+
+    /// value `{value}` at `{path}` doesn't match type requirements `{expected}`.
+    TypeMismatch {
+        path: Path,
+        value: StrictVal,
+        expected: Ty<SemId>,
+    },
+
+    /// collection at `{path}` has size {len} which is out of bounds of type `{expected}`.
+    OutOfBounds {
+        path: Path,
+        len: usize,
+        expected: Ty<SemId>,
+    },
+
+    /// set element or map key at `{path}` is repeated or breaks the canonical order.
+    BrokenOrder { path: Path },
+
+    #[display(inner)]
+    #[from]
+    Confinement(confinement::Error),
+
+    #[display(inner)]
+    #[from]
+    Io(io::Error),
+}
+
+impl Error {
+    /// Returns path to the value which has failed the serialization, if the error is related to
+    /// a specific value.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::TypeAbsent { path, .. }
+            | Error::TypeMismatch { path, .. }
+            | Error::OutOfBounds { path, .. }
+            | Error::BrokenOrder { path } => Some(path),
+            Error::Confinement(_) | Error::Io(_) => None,
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Io(err) => err,
+            err => io::Error::new(io::ErrorKind::InvalidData, err),
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct SerializedType<const MAX_LEN: usize>(Confined<Vec<u8>, 0, MAX_LEN>);
@@ -54,10 +113,10 @@ impl TypeSystem {
     pub fn strict_serialize_value<const MAX_LEN: usize>(
         &self,
         typed: &TypedVal,
-    ) -> Result<ConfinedBlob<0, MAX_LEN>, SerializeError> {
+    ) -> Result<ConfinedBlob<0, MAX_LEN>, Error> {
         let mut buf = Vec::new();
         self.strict_write_value(typed, &mut buf)?;
-        Confined::try_from(buf).map_err(SerializeError::from)
+        Confined::try_from(buf).map_err(Error::from)
     }
 
     #[deprecated(since = "2.7.2", note = "use strict_serialize_value instead")]
@@ -66,7 +125,7 @@ impl TypeSystem {
         typed: &TypedVal,
     ) -> Result<SerializedType<MAX_LEN>, SerializeError> {
         let mut buf = Vec::new();
-        self.strict_write_value(typed, &mut buf)?;
+        self.strict_write_value(typed, &mut buf).map_err(io::Error::from)?;
         Confined::try_from(buf).map(SerializedType).map_err(SerializeError::from)
     }
 
@@ -74,8 +133,8 @@ impl TypeSystem {
         &self,
         typed: &TypedVal,
         writer: &mut impl io::Write,
-    ) -> Result<(), io::Error> {
-        self.strict_write_val(&typed.val, typed.orig.id, writer)
+    ) -> Result<(), Error> {
+        self.strict_write_val(&typed.val, typed.orig.id, writer, &mut Path::new())
     }
 
     #[deprecated(since = "2.7.2", note = "use strict_write_value instead")]
@@ -84,7 +143,7 @@ impl TypeSystem {
         typed: &TypedVal,
         writer: &mut impl io::Write,
     ) -> Result<(), io::Error> {
-        self.strict_write_val(&typed.val, typed.orig.id, writer)
+        self.strict_write_value(typed, writer).map_err(io::Error::from)
     }
 
     fn strict_write_val(
@@ -92,9 +151,54 @@ impl TypeSystem {
         val: &StrictVal,
         sem_id: SemId,
         writer: &mut impl io::Write,
-    ) -> Result<(), io::Error> {
-        let ty = self.find(sem_id).expect("typified with some other TypeSystem");
-        self.strict_write_ty(val, ty, writer)
+        path: &mut Path,
+    ) -> Result<(), Error> {
+        let ty = self.find(sem_id).ok_or_else(|| Error::TypeAbsent {
+            path: path.clone(),
+            id: sem_id,
+        })?;
+        self.strict_write_ty(val, ty, writer, path)
+    }
+
+    fn strict_write_nested(
+        &self,
+        val: &StrictVal,
+        sem_id: SemId,
+        writer: &mut impl io::Write,
+        path: &mut Path,
+        step: Step,
+    ) -> Result<(), Error> {
+        let pushed = path.push(step).is_ok();
+        self.strict_write_val(val, sem_id, writer, path)?;
+        if pushed {
+            path.pop();
+        }
+        Ok(())
+    }
+
+    /// Checks whether a string may be written as a collection of `item` elements: either as
+    /// unicode characters or as ASCII characters of a char enum.
+    fn is_char_item(&self, item: SemId, s: &str) -> bool {
+        item.is_unicode_char()
+            || (s.is_ascii() && self.find(item).is_some_and(|ty| ty.is_char_enum()))
+    }
+
+    fn check_order<'a>(
+        &self,
+        items: impl Iterator<Item = &'a StrictVal>,
+        sem_id: SemId,
+        path: &Path,
+    ) -> Result<(), Error> {
+        let mut prev = None;
+        for (no, item) in items.enumerate() {
+            if prev.is_some_and(|prev| self.cmp_vals(prev, item, sem_id) != Ordering::Less) {
+                let mut path = path.clone();
+                let _ = path.push(Step::Index(no as u32));
+                return Err(Error::BrokenOrder { path });
+            }
+            prev = Some(item);
+        }
+        Ok(())
     }
 
     fn strict_write_ty(
@@ -102,85 +206,102 @@ impl TypeSystem {
         val: &StrictVal,
         ty: &Ty<SemId>,
         writer: &mut impl io::Write,
-    ) -> Result<(), io::Error> {
+        path: &mut Path,
+    ) -> Result<(), Error> {
+        let mismatch = |path: &Path| Error::TypeMismatch {
+            path: path.clone(),
+            value: val.clone(),
+            expected: ty.clone(),
+        };
+        let check_len = |path: &Path, len: usize, sizing: Sizing| {
+            if sizing.check(len) {
+                Ok(())
+            } else {
+                Err(Error::OutOfBounds {
+                    path: path.clone(),
+                    len,
+                    expected: ty.clone(),
+                })
+            }
+        };
+
         match (val, ty) {
-            (StrictVal::Unit, Ty::Primitive(prim)) => {
-                debug_assert_eq!(*prim, Primitive::UNIT);
+            (StrictVal::Unit, Ty::Primitive(prim)) if *prim == Primitive::UNIT => {
                 // Do nothing
             }
-            (StrictVal::Number(StrictNum::Uint(num)), Ty::Primitive(prim)) => {
+            (StrictVal::Number(StrictNum::Float(f)), Ty::Primitive(prim)) => {
                 let bytes_count = prim.byte_size() as usize;
-                let le_bytes = &num.to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
-            }
-            (StrictVal::Number(StrictNum::BigUint(num)), Ty::Primitive(prim)) => {
-                let bytes_count = prim.byte_size() as usize;
-                let le_bytes = &num.to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
-            }
-            (StrictVal::Number(StrictNum::Int(num)), Ty::Primitive(prim)) => {
-                let bytes_count = prim.byte_size() as usize;
-                let le_bytes = &num.to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
+                let bits = float::to_bits(*prim, *f).ok_or_else(|| mismatch(path))?;
+                writer.write_all(&bits.to_le_bytes()[0..bytes_count])?;
             }
-            (StrictVal::Number(StrictNum::BigInt(num)), Ty::Primitive(prim)) => {
-                let bytes_count = prim.byte_size() as usize;
-                let le_bytes = &num.to_le_bytes()[0..bytes_count];
-                writer.write_all(le_bytes)?;
+            (StrictVal::Number(num), Ty::Primitive(prim)) => {
+                let le_bytes = int_le_bytes(num, *prim).ok_or_else(|| mismatch(path))?;
+                writer.write_all(&le_bytes[0..prim.byte_size() as usize])?;
             }
 
-            (StrictVal::String(s), Ty::UnicodeChar) => {
-                debug_assert_eq!(s.chars().count(), 1);
+            (StrictVal::String(s), Ty::UnicodeChar) if s.chars().count() == 1 => {
                 writer.write_all(s.as_bytes())?;
             }
-            (StrictVal::Bytes(vec), Ty::Array(_, len)) => {
-                debug_assert_eq!(vec.len(), *len as usize);
+            (StrictVal::Bytes(vec), Ty::Array(id, len)) if id.is_byte() => {
+                check_len(path, vec.len(), Sizing::fixed(*len as u64))?;
                 writer.write_all(vec)?;
             }
-            (StrictVal::String(s), Ty::Array(_, len)) => {
-                debug_assert_eq!(s.len(), *len as usize);
+            (StrictVal::String(s), Ty::Array(id, len)) if self.is_char_item(*id, s) => {
+                check_len(path, s.len(), Sizing::fixed(*len as u64))?;
                 writer.write_all(s.as_bytes())?;
             }
+            (StrictVal::List(list), Ty::Array(sem_id, len)) => {
+                check_len(path, list.len(), Sizing::fixed(*len as u64))?;
+                for (no, val) in list.iter().enumerate() {
+                    self.strict_write_nested(val, *sem_id, writer, path, Step::Index(no as u32))?;
+                }
+            }
 
-            (StrictVal::Tuple(vals), Ty::Tuple(fields)) => {
-                debug_assert_eq!(vals.len(), fields.len());
-                for (val, sem_id) in vals.iter().zip(fields) {
-                    self.strict_write_val(val, *sem_id, writer)?;
+            (StrictVal::Tuple(vals), Ty::Tuple(fields)) if vals.len() == fields.len() => {
+                for (no, (val, sem_id)) in vals.iter().zip(fields).enumerate() {
+                    let step = Step::UnnamedField(no as u8);
+                    self.strict_write_nested(val, *sem_id, writer, path, step)?;
                 }
             }
-            (StrictVal::Struct(vals), Ty::Struct(fields)) => {
-                debug_assert_eq!(vals.len(), fields.len());
-                for (val, field) in vals.values().zip(fields) {
-                    self.strict_write_val(val, field.ty, writer)?;
+            (StrictVal::Struct(vals), Ty::Struct(fields)) if vals.len() == fields.len() => {
+                for field in fields {
+                    let val = vals.get(&field.name).ok_or_else(|| mismatch(path))?;
+                    let step = Step::NamedField(field.name.clone());
+                    self.strict_write_nested(val, field.ty, writer, path, step)?;
                 }
             }
-            (StrictVal::Enum(EnumTag::Ord(tag)), Ty::Enum(variants)) => {
-                debug_assert!(variants.has_tag(*tag));
+            (StrictVal::Enum(EnumTag::Ord(tag)), Ty::Enum(variants)) if variants.has_tag(*tag) => {
                 writer.write_all(&[*tag])?;
             }
+            // Ascii or other sub-byte character
+            (StrictVal::String(s), Ty::Enum(variants))
+                if s.len() == 1 && variants.has_tag(s.as_bytes()[0]) =>
+            {
+                writer.write_all(s.as_bytes())?;
+            }
             (StrictVal::Enum(EnumTag::Name(tag)), Ty::Enum(variants)) => {
-                let tag = variants.tag_by_name(tag).expect("Type::System::typify guarantees");
+                let tag = variants.tag_by_name(tag).ok_or_else(|| mismatch(path))?;
                 writer.write_all(&[tag])?;
             }
-            (StrictVal::Union(EnumTag::Ord(tag), val), Ty::Union(variants)) => {
-                let sem_id = variants.ty_by_tag(*tag).expect("Type::System::typify guarantees");
-                writer.write_all(&[*tag])?;
-                self.strict_write_val(val, *sem_id, writer)?;
-            }
-            (StrictVal::Union(EnumTag::Name(tag), val), Ty::Union(variants)) => {
-                let (variant, sem_id) =
-                    variants.by_name(tag).expect("Type::System::typify guarantees");
+            (StrictVal::Union(tag, val), Ty::Union(variants)) => {
+                let (variant, sem_id) = match tag {
+                    EnumTag::Ord(tag) => variants.by_tag(*tag),
+                    EnumTag::Name(name) => variants.by_name(name),
+                }
+                .ok_or_else(|| mismatch(path))?;
                 writer.write_all(&[variant.tag])?;
-                self.strict_write_val(val, *sem_id, writer)?;
+                self.strict_write_val(val, *sem_id, writer, path)?;
             }
 
-            (StrictVal::String(s), Ty::List(_, sizing)) => {
+            (StrictVal::String(s), Ty::List(id, sizing)) if self.is_char_item(*id, s) => {
+                check_len(path, s.len(), *sizing)?;
                 let bytes_count = sizing.byte_size();
                 let le_bytes = &s.len().to_le_bytes()[0..bytes_count];
                 writer.write_all(le_bytes)?;
                 writer.write_all(s.as_bytes())?;
             }
-            (StrictVal::Bytes(s), Ty::List(_, sizing)) => {
+            (StrictVal::Bytes(s), Ty::List(id, sizing)) if id.is_byte() => {
+                check_len(path, s.len(), *sizing)?;
                 let bytes_count = sizing.byte_size();
                 let le_bytes = &s.len().to_le_bytes()[0..bytes_count];
                 writer.write_all(le_bytes)?;
@@ -188,54 +309,234 @@ impl TypeSystem {
             }
             (StrictVal::List(list), Ty::List(sem_id, sizing))
             | (StrictVal::Set(list), Ty::Set(sem_id, sizing)) => {
+                check_len(path, list.len(), *sizing)?;
+                if matches!(ty, Ty::Set(..)) {
+                    self.check_order(list.iter(), *sem_id, path)?;
+                }
                 let bytes_count = sizing.byte_size();
                 let le_bytes = &list.len().to_le_bytes()[0..bytes_count];
                 writer.write_all(le_bytes)?;
-                for val in list {
-                    self.strict_write_val(val, *sem_id, writer)?;
+                for (no, val) in list.iter().enumerate() {
+                    self.strict_write_nested(val, *sem_id, writer, path, Step::Index(no as u32))?;
                 }
             }
             (StrictVal::Map(list), Ty::Map(key_id, sem_id, sizing)) => {
+                check_len(path, list.len(), *sizing)?;
+                self.check_order(list.iter().map(|(key, _)| key), *key_id, path)?;
                 let bytes_count = sizing.byte_size();
                 let le_bytes = &list.len().to_le_bytes()[0..bytes_count];
                 writer.write_all(le_bytes)?;
-                for (key, val) in list {
-                    self.strict_write_val(key, *key_id, writer)?;
-                    self.strict_write_val(val, *sem_id, writer)?;
+                for (no, (key, val)) in list.iter().enumerate() {
+                    let step = Step::Index(no as u32);
+                    self.strict_write_nested(key, *key_id, writer, path, step.clone())?;
+                    let step = KeyStep::with_val(key).map(Step::Key).unwrap_or(step);
+                    self.strict_write_nested(val, *sem_id, writer, path, step)?;
                 }
             }
 
             (StrictVal::String(s), Ty::Tuple(fields))
-                if s.is_ascii() && self.is_rstring(fields).expect("type absent") =>
+                if s.is_ascii() && self.is_rstring(fields).unwrap_or_default() =>
             {
-                let (_, sizing) =
-                    self.rstring_sizing(fields).expect("type absent").expect("checked above");
+                let Ok(Some((_, sizing))) = self.rstring_sizing(fields) else {
+                    return Err(mismatch(path));
+                };
+                check_len(path, s.len(), sizing)?;
                 let bytes_count = sizing.byte_size();
-                debug_assert!(s.len() <= sizing.max as usize);
                 let le_bytes = &s.len().to_le_bytes()[0..bytes_count];
                 writer.write_all(le_bytes)?;
                 writer.write_all(s.as_bytes())?;
             }
 
-            (a, b) => panic!("bug in business logic of type system. Details:\n{a:#?}\n{b:#?}"),
+            _ => return Err(mismatch(path)),
         }
 
         Ok(())
     }
 }
 
-trait SizingExt {
-    fn byte_size(&self) -> usize;
+/// Returns little-endian representation of an integer number, if it fits into the primitive
+/// type.
+fn int_le_bytes(num: &StrictNum, prim: Primitive) -> Option<[u8; 128]> {
+    let (le_bytes, negative) = match num {
+        StrictNum::Uint(n) => (u1024::from(*n).to_le_bytes(), false),
+        StrictNum::BigUint(n) => (n.to_le_bytes(), false),
+        StrictNum::Int(n) => (i1024::from(*n).to_le_bytes(), *n < 0),
+        StrictNum::BigInt(n) => (n.to_le_bytes(), n.is_negative()),
+        StrictNum::Float(_) => return None,
+    };
+    let len = prim.byte_size() as usize;
+    if len == 0 || len > 128 {
+        return None;
+    }
+    let signed = match prim.info().ty {
+        NumCls::Signed if prim != Primitive::BYTE => true,
+        NumCls::Unsigned | NumCls::NonZero | NumCls::Signed => false,
+        NumCls::Float => return None,
+    };
+    let (value, extension) = le_bytes.split_at(len);
+    let fill = if negative { 0xFF } else { 0x00 };
+    if (negative && !signed) || extension.iter().any(|byte| *byte != fill) {
+        return None;
+    }
+    if signed && (value[len - 1] & 0x80 != 0) != negative {
+        return None;
+    }
+    if prim.info().ty == NumCls::NonZero && value.iter().all(|byte| *byte == 0) {
+        return None;
+    }
+    Some(le_bytes)
 }
 
-impl SizingExt for Sizing {
-    fn byte_size(&self) -> usize {
-        match self.max {
-            one if one <= u8::MAX as u64 => 1,
-            two if two <= u16::MAX as u64 => 2,
-            three if three <= u24::MAX.into_u64() => 3,
-            four if four <= u32::MAX as u64 => 4,
-            _ => 8,
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::super::test_helpers::*;
+    use super::*;
+    use crate::typesys::TypeSymbol;
+    use crate::typify;
+    use crate::value::ston;
+
+    fn edited(edit: impl FnOnce(&mut StrictVal)) -> Result<Vec<u8>, Error> {
+        let sys = test_system();
+        let nominal = ston!(ticker "TICK", name "Some name", precision svenum!(2));
+        let TypedVal { orig, mut val } = sys.typify(nominal, "TestLib.Nominal").unwrap();
+        edit(&mut val);
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&TypedVal { orig, val });
+        data.map(|data| data.release())
+    }
+
+    #[test]
+    fn encode_errors() {
+        let data = edited(|_| {}).unwrap();
+        let decoded = test_system().strict_deserialize_type("TestLib.Nominal", &data).unwrap();
+        assert_eq!(decoded.as_val().unwrap_struct("precision"), &svenum!(twoDecimals));
+
+        let err = edited(|val| {
+            val.remove_at_path(&Path::from_str(".precision").unwrap()).unwrap();
+        })
+        .unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { ref path, .. } if path.is_empty()));
+
+        let err = edited(|val| {
+            val.set_at_path(&Path::from_str(".precision").unwrap(), svenum!(5)).unwrap();
+        })
+        .unwrap_err();
+        assert_eq!(err.path().unwrap().to_string(), ".precision");
+        assert!(matches!(err, Error::TypeMismatch {
+            expected: Ty::Enum(_),
+            ..
+        }));
+
+        let err = edited(|val| {
+            val.set_at_path(&Path::from_str(".name").unwrap(), svstr!("")).unwrap();
+        })
+        .unwrap_err();
+        assert_eq!(err.path().unwrap().to_string(), ".name");
+        assert!(matches!(err, Error::OutOfBounds { len: 0, .. }));
+
+        let err = edited(|val| {
+            val.set_at_path(&Path::from_str(".ticker").unwrap(), svstr!("TICK")).unwrap();
+        })
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("value `\"TICK\"` at `.ticker` doesn't match type requirements `"));
+    }
+
+    #[test]
+    fn string_items() {
+        let sys = system_from_source(
+            "typelib Chars
+data Digit : zero#48 | one#49
+data Digits : [Digit ^ ..0xff]
+data Name : [Unicode ^ ..0xff]
+data Nums : [U16 ^ ..0xff]
+data Pair : [U16 ^ 2]
+",
+        );
+        let encode = |spec: &'static str, val: StrictVal| {
+            let orig = TypeSymbol::unnamed(sys.to_sem_id(spec).unwrap());
+            let typed = TypedVal {
+                orig,
+                val: StrictVal::newtype(val),
+            };
+            sys.as_types()
+                .strict_serialize_value::<{ usize::MAX }>(&typed)
+                .map(|data| data.release())
+        };
+        assert_eq!(encode("Chars.Digits", svstr!("10")).unwrap(), vec![2, 49, 48]);
+        assert_eq!(encode("Chars.Name", svstr!("é")).unwrap(), vec![2, 0xC3, 0xA9]);
+        for (spec, val) in [
+            ("Chars.Digits", svstr!("é")),
+            ("Chars.Digits", svbytes!([48u8])),
+            ("Chars.Name", svbytes!([48u8])),
+            ("Chars.Nums", svstr!("ab")),
+            ("Chars.Nums", svbytes!([1u8, 0])),
+            ("Chars.Pair", svstr!("abcd")),
+            ("Chars.Pair", svbytes!([1u8, 0, 2, 0])),
+        ] {
+            let err = encode(spec, val).unwrap_err();
+            assert!(matches!(err, Error::TypeMismatch { .. }), "{spec}: {err}");
+        }
+    }
+
+    #[test]
+    fn canonical_order() {
+        let sys = system_from_source(
+            "typelib Sets
+data Kind : zed | alpha
+data Nums : {U16 ^ ..0xff}
+data Index : {Kind -> ^ ..0xff U8}
+",
+        );
+        for (ston, spec, sorted) in [
+            ("{5, 1, 256}", "Sets.Nums", "{1, 5, 256}"),
+            (r#"{alpha -> 2, zed -> 1}"#, "Sets.Index", "{zed -> 1, alpha -> 2}"),
+        ] {
+            let typed = sys.parse_ston(ston, spec).unwrap();
+            assert_eq!(typed.val, StrictVal::newtype(StrictVal::from_str(sorted).unwrap()));
+            let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&typed).unwrap();
+            let decoded = sys.strict_deserialize_type(spec, &data).unwrap();
+            assert_eq!(decoded.val, typed.val);
+        }
+        assert!(matches!(
+            sys.parse_ston("{5, 1, 5}", "Sets.Nums"),
+            Err(ston::Error::Typify(typify::Error::RepeatedSetValue(..)))
+        ));
+
+        let mut typed = sys.parse_ston("{1, 5}", "Sets.Nums").unwrap();
+        typed.val = StrictVal::newtype(StrictVal::set([5u16, 1]));
+        let err = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&typed).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            s!("set element or map key at `.0[1]` is repeated or breaks the canonical order.")
+        );
+        typed.val = StrictVal::newtype(StrictVal::set([5u16, 5]));
+        assert!(matches!(
+            sys.as_types().strict_serialize_value::<{ usize::MAX }>(&typed),
+            Err(Error::BrokenOrder { .. })
+        ));
+    }
+
+    #[test]
+    fn integer_bounds() {
+        for (num, prim, ok) in [
+            (StrictNum::Uint(0xFF), Primitive::U8, true),
+            (StrictNum::Uint(0x100), Primitive::U8, false),
+            (StrictNum::Uint(0x7F), Primitive::I8, true),
+            (StrictNum::Uint(0x80), Primitive::I8, false),
+            (StrictNum::Int(-0x80), Primitive::I8, true),
+            (StrictNum::Int(-0x81), Primitive::I8, false),
+            (StrictNum::Int(-1), Primitive::U64, false),
+            (StrictNum::Int(-1), Primitive::I256, true),
+            (StrictNum::Uint(0), Primitive::N8, false),
+            (StrictNum::BigUint(u1024::from(1u64) << 255), Primitive::U256, true),
+            (StrictNum::BigUint(u1024::from(1u64) << 256), Primitive::U256, false),
+            (StrictNum::BigInt(i1024::from(-1i64) << 1023), Primitive::I1024, true),
+            (StrictNum::Uint(1), Primitive::F32, false),
+        ] {
+            assert_eq!(int_le_bytes(&num, prim).is_some(), ok, "{num} as {prim}");
         }
     }
 }
@@ -0,0 +1,162 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! IEEE-754 floating-point numbers in strict values.
+
+use std::str::FromStr;
+
+use amplify::num::apfloat::ieee::{self, IeeeFloat, Semantics};
+use amplify::num::apfloat::{ExpInt, Float, FloatConvert, Status, StatusAnd};
+use amplify::num::u256;
+use encoding::Primitive;
+
+/// Semantics of the brain floating-point format (`F16b`), not provided by `amplify_apfloat`.
+pub struct BFloatS;
+
+impl Semantics for BFloatS {
+    const BITS: usize = 16;
+    const PRECISION: usize = 8;
+    const MAX_EXP: ExpInt = 127;
+}
+
+/// Brain floating-point number (`F16b`).
+pub type BFloat = IeeeFloat<BFloatS>;
+
+/// Semantics of x87 extended precision format (`F80`) with the integer bit made implicit.
+///
+/// `amplify_apfloat` drops the explicit integer bit when reading x87 numbers from bits, so we
+/// strip and restore this bit ourselves.
+struct X87S;
+
+impl Semantics for X87S {
+    const BITS: usize = 79;
+    const PRECISION: usize = 64;
+    const MAX_EXP: ExpInt = (1 << 14) - 1;
+}
+
+const X87_FRACTION_BITS: usize = 63;
+
+fn x87_from_bits(bits: u256) -> ieee::Oct {
+    let fraction = bits & ((u256::ONE << X87_FRACTION_BITS) - u256::ONE);
+    from_bits_via::<X87S>(((bits >> 64) << X87_FRACTION_BITS) | fraction)
+}
+
+fn x87_to_bits(f: ieee::Oct) -> u256 {
+    let bits = to_bits_via::<X87S>(f);
+    let fraction = bits & ((u256::ONE << X87_FRACTION_BITS) - u256::ONE);
+    let sign_exp = bits >> X87_FRACTION_BITS;
+    // Integer bit is set for all numbers except zeros and denormals
+    let integer_bit = match (sign_exp & u256::from(0x7FFFu64)) == u256::ZERO {
+        true => u256::ZERO,
+        false => u256::ONE << X87_FRACTION_BITS,
+    };
+    (sign_exp << 64) | integer_bit | fraction
+}
+
+fn round_via<S: Semantics>(f: ieee::Oct) -> StatusAnd<ieee::Oct> {
+    let mut loses_info = false;
+    let StatusAnd { status, value } = FloatConvert::<IeeeFloat<S>>::convert(f, &mut loses_info);
+    let mut loses_info = false;
+    status.and(value.convert(&mut loses_info).value)
+}
+
+fn from_bits_via<S: Semantics>(bits: u256) -> ieee::Oct {
+    let mut loses_info = false;
+    IeeeFloat::<S>::from_bits(bits).convert(&mut loses_info).value
+}
+
+fn to_bits_via<S: Semantics>(f: ieee::Oct) -> u256 {
+    let mut loses_info = false;
+    FloatConvert::<IeeeFloat<S>>::convert(f, &mut loses_info).value.to_bits()
+}
+
+/// Rounds the number to the precision of a floating-point primitive type. Returns `None` if the
+/// primitive is not a supported floating-point type.
+pub(super) fn round(prim: Primitive, f: ieee::Oct) -> Option<StatusAnd<ieee::Oct>> {
+    Some(match prim {
+        Primitive::F16 => round_via::<ieee::HalfS>(f),
+        Primitive::F16B => round_via::<BFloatS>(f),
+        Primitive::F32 => round_via::<ieee::SingleS>(f),
+        Primitive::F64 => round_via::<ieee::DoubleS>(f),
+        Primitive::F80 => round_via::<X87S>(f),
+        Primitive::F128 => round_via::<ieee::QuadS>(f),
+        Primitive::F256 => Status::OK.and(f),
+        _ => return None,
+    })
+}
+
+/// Reads the number from its binary representation for a floating-point primitive type.
+pub(super) fn from_bits(prim: Primitive, bits: u256) -> Option<ieee::Oct> {
+    Some(match prim {
+        Primitive::F16 => from_bits_via::<ieee::HalfS>(bits),
+        Primitive::F16B => from_bits_via::<BFloatS>(bits),
+        Primitive::F32 => from_bits_via::<ieee::SingleS>(bits),
+        Primitive::F64 => from_bits_via::<ieee::DoubleS>(bits),
+        Primitive::F80 => x87_from_bits(bits),
+        Primitive::F128 => from_bits_via::<ieee::QuadS>(bits),
+        Primitive::F256 => ieee::Oct::from_bits(bits),
+        _ => return None,
+    })
+}
+
+/// Produces binary representation of the number for a floating-point primitive type.
+pub(super) fn to_bits(prim: Primitive, f: ieee::Oct) -> Option<u256> {
+    Some(match prim {
+        Primitive::F16 => to_bits_via::<ieee::HalfS>(f),
+        Primitive::F16B => to_bits_via::<BFloatS>(f),
+        Primitive::F32 => to_bits_via::<ieee::SingleS>(f),
+        Primitive::F64 => to_bits_via::<ieee::DoubleS>(f),
+        Primitive::F80 => x87_to_bits(f),
+        Primitive::F128 => to_bits_via::<ieee::QuadS>(f),
+        Primitive::F256 => f.to_bits(),
+        _ => return None,
+    })
+}
+
+/// Formats the number using the shortest of IEEE format representations which can be parsed back
+/// into exactly the same number.
+pub(super) fn fmt(f: ieee::Oct, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn shortest<S: Semantics>(f: ieee::Oct) -> Option<String> {
+        let mut loses_info = false;
+        let value = FloatConvert::<IeeeFloat<S>>::convert(f, &mut loses_info).value;
+        let s = value.to_string();
+        (!loses_info && ieee::Oct::from_str(&s).is_ok_and(|parsed| parsed.bitwise_eq(f)))
+            .then_some(s)
+    }
+
+    if f.is_nan() {
+        return fmt.write_str("nan");
+    } else if f.is_infinite() {
+        return fmt.write_str(if f.is_negative() { "-inf" } else { "inf" });
+    }
+    let s = shortest::<ieee::HalfS>(f)
+        .or_else(|| shortest::<ieee::SingleS>(f))
+        .or_else(|| shortest::<ieee::DoubleS>(f))
+        .or_else(|| shortest::<ieee::QuadS>(f))
+        .unwrap_or_else(|| f.to_string());
+    fmt.write_str(&s)?;
+    // Keep the number distinguishable from integers
+    if !s.contains(['.', 'E']) {
+        fmt.write_str(".0")?;
+    }
+    Ok(())
+}
@@ -24,31 +24,47 @@
 //! - [`path`]: path accessors/introspects into strict values;
 //! - [STON][ston]: strict type object notation, a JSON-like representation of strict types;
 //! - [`decode`]: conversion between strict encoding and strict values;
+//! - [`encode`]: serialization of typed strict values into strict encoding;
+//! - [`dump`]: annotated hex dumps of strict encoded data;
+//! - [`stream`]: incremental decoding of the data arriving by chunks;
 //! - [`typify`]: checks of strict values against strict type schema;
 //! - [`convert`]: conversion between strict values and other text representations (JSON, YAML,
-//!   TOML, etc).
+//!   TOML, etc);
+//! - [`schema`]: JSON Schema for the JSON representation of strict values;
+//! - [`view`]: zero-copy lazy access to strict encoded values;
+//! - [`random`]: generation of random values for property testing.
 
 #[macro_use]
 mod val;
+mod float;
 mod path;
 pub mod ston;
 pub mod typify;
 pub mod decode;
 #[cfg(feature = "serde")]
 pub mod convert;
-mod encode;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod encode;
+pub mod dump;
+pub mod stream;
+pub mod random;
+pub mod view;
 
-pub use path::{KeyStep, Path, PathError, Step};
+pub use float::{BFloat, BFloatS};
+pub use path::{EditError, KeyStep, Path, PathError, PathParseError, Step};
 pub use val::{Blob, EnumTag, StrictNum, StrictVal};
 
 #[cfg(test)]
-mod test_helpers {
+pub(crate) mod test_helpers {
+    use std::str::FromStr;
+
     use amplify::confinement::{Confined, NonEmptyString};
     use encoding::{Ident, StrictDeserialize, StrictSerialize};
 
     use crate::stl::{std_stl, strict_types_stl};
     use crate::typesys::{SymbolicSys, SystemBuilder};
-    use crate::LibBuilder;
+    use crate::{LibBuilder, SymbolicLib, TypeLib};
 
     #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
     #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
@@ -100,4 +116,21 @@ mod test_helpers {
             .finalize()
             .unwrap()
     }
+
+    pub fn lib_from_source(src: &str) -> TypeLib {
+        SymbolicLib::from_str(src).unwrap().compile().unwrap()
+    }
+
+    pub fn system_from_source(src: &str) -> SymbolicSys {
+        SystemBuilder::new().import(lib_from_source(src)).unwrap().finalize().unwrap()
+    }
+
+    /// Library with an enum, a union using it and a map of the union values, which is shared with
+    /// the command-line tool tests.
+    pub const EVENTS_LIB: &str = include_str!("../../tests/data/Events.sty");
+
+    /// Builds a system from the shared events library extended with `extra` type definitions.
+    pub fn events_system(extra: &str) -> SymbolicSys {
+        system_from_source(&format!("{EVENTS_LIB}{extra}"))
+    }
 }
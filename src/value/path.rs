@@ -23,10 +23,16 @@
 //! Path accessors into strict values.
 
 use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
 
 use amplify::confinement::{SmallVec, TinyBlob, TinyString};
-use encoding::{FieldName, STRICT_TYPES_LIB};
+use amplify::hex::FromHex;
+use amplify::num::u1024;
+use encoding::{FieldName, InvalidRString, STRICT_TYPES_LIB};
 
+use crate::typesys::SymbolicSys;
+use crate::value::typify::{self, TypedVal};
 use crate::value::{EnumTag, StrictNum};
 use crate::StrictVal;
 
@@ -48,6 +54,20 @@ pub enum KeyStep {
 }
 
 impl KeyStep {
+    /// Constructs key step matching the provided map key value, if the value can be used as a
+    /// key step.
+    pub fn with_val(val: &StrictVal) -> Option<KeyStep> {
+        Some(match val {
+            StrictVal::Number(StrictNum::Uint(no)) => KeyStep::Number(*no as u128),
+            StrictVal::Bytes(blob) => KeyStep::TinyBlob(TinyBlob::try_from(blob.0.clone()).ok()?),
+            StrictVal::String(s) => KeyStep::TinyString(TinyString::try_from(s.clone()).ok()?),
+            StrictVal::Enum(EnumTag::Name(name)) => {
+                KeyStep::TinyString(TinyString::try_from(name.to_string()).ok()?)
+            }
+            _ => return None,
+        })
+    }
+
     pub fn has_match(&self, val: &StrictVal) -> bool {
         match (self, val) {
             (KeyStep::Number(no), StrictVal::Enum(EnumTag::Ord(tag))) if *tag as u128 == *no => {
@@ -64,6 +84,11 @@ impl KeyStep {
                 true
             }
             (KeyStep::TinyString(s1), StrictVal::String(s2)) if s1.as_str() == s2.as_str() => true,
+            (KeyStep::TinyString(s1), StrictVal::Enum(EnumTag::Name(s2)))
+                if s1.as_str() == s2.as_str() =>
+            {
+                true
+            }
             _ => false,
         }
     }
@@ -81,8 +106,8 @@ impl Display for KeyStep {
                 Ok(())
             }
             KeyStep::TinyString(s) => {
-                let s = s.replace('"', "\\\"");
-                f.write_str(&s)
+                let s = s.replace('\\', "\\\\").replace('"', "\\\"");
+                write!(f, "\"{s}\"")
             }
         }
     }
@@ -122,7 +147,7 @@ impl Path {
 
     pub fn with(step: Step) -> Path { Path(small_vec!(step)) }
 
-    pub fn iter(&self) -> std::slice::Iter<Step> { self.0.iter() }
+    pub fn iter(&self) -> std::slice::Iter<'_, Step> { self.0.iter() }
 }
 
 impl<'path> IntoIterator for &'path Path {
@@ -141,7 +166,133 @@ impl Display for Path {
     }
 }
 
-// TODO: Implement FromStr for value Path expressions
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum PathParseError {
+    /// unexpected character `{1}` at position {0} of the path expression.
+    UnexpectedChar(usize, char),
+
+    /// unexpected end of the path expression.
+    UnexpectedEnd,
+
+    /// invalid field name at position {0}: {1}
+    InvalidFieldName(usize, InvalidRString),
+
+    /// number at position {0} exceeds the allowed range.
+    NumberOverflow(usize),
+
+    /// invalid hex value of the map key at position {0}.
+    InvalidBlob(usize),
+
+    /// map key at position {0} is longer than 255 bytes.
+    KeyTooLong(usize),
+
+    /// unterminated string at position {0}.
+    UnterminatedString(usize),
+
+    /// path expression contains too many steps.
+    TooManySteps,
+}
+
+impl FromStr for Path {
+    type Err = PathParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut path = Path::new();
+        let mut chars = s.char_indices().peekable();
+        let digits = |chars: &mut Peekable<CharIndices>| {
+            let mut no = String::new();
+            while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                no.push(c);
+            }
+            no
+        };
+        while let Some((pos, c)) = chars.next() {
+            let step = match c {
+                '.' if chars.peek().is_some_and(|(_, c)| c.is_ascii_digit()) => {
+                    let no = digits(&mut chars);
+                    Step::UnnamedField(no.parse().map_err(|_| PathParseError::NumberOverflow(pos))?)
+                }
+                '.' => {
+                    let mut name = String::new();
+                    while let Some((_, c)) =
+                        chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                    {
+                        name.push(c);
+                    }
+                    Step::NamedField(
+                        FieldName::try_from(name)
+                            .map_err(|err| PathParseError::InvalidFieldName(pos + 1, err))?,
+                    )
+                }
+                '[' => {
+                    let no = digits(&mut chars);
+                    match chars.next() {
+                        Some((_, ']')) if !no.is_empty() => {}
+                        Some((pos, c)) => return Err(PathParseError::UnexpectedChar(pos, c)),
+                        None => return Err(PathParseError::UnexpectedEnd),
+                    }
+                    Step::Index(no.parse().map_err(|_| PathParseError::NumberOverflow(pos + 1))?)
+                }
+                '{' => {
+                    let key = match chars.peek().copied() {
+                        Some((start, '"')) => {
+                            chars.next();
+                            let mut key = String::new();
+                            loop {
+                                match chars.next() {
+                                    None => return Err(PathParseError::UnterminatedString(start)),
+                                    Some((_, '"')) => break,
+                                    Some((_, '\\')) => match chars.next() {
+                                        Some((_, c)) => key.push(c),
+                                        None => {
+                                            return Err(PathParseError::UnterminatedString(start))
+                                        }
+                                    },
+                                    Some((_, c)) => key.push(c),
+                                }
+                            }
+                            KeyStep::TinyString(
+                                TinyString::try_from(key)
+                                    .map_err(|_| PathParseError::KeyTooLong(start))?,
+                            )
+                        }
+                        Some((start, '0')) if s[start..].starts_with("0h") => {
+                            chars.nth(1);
+                            let mut hex = String::new();
+                            while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                                hex.push(c);
+                            }
+                            let blob = Vec::<u8>::from_hex(&hex)
+                                .map_err(|_| PathParseError::InvalidBlob(start))?;
+                            KeyStep::TinyBlob(
+                                TinyBlob::try_from(blob)
+                                    .map_err(|_| PathParseError::KeyTooLong(start))?,
+                            )
+                        }
+                        Some((start, c)) if c.is_ascii_digit() => {
+                            let no = digits(&mut chars);
+                            KeyStep::Number(
+                                no.parse().map_err(|_| PathParseError::NumberOverflow(start))?,
+                            )
+                        }
+                        Some((pos, c)) => return Err(PathParseError::UnexpectedChar(pos, c)),
+                        None => return Err(PathParseError::UnexpectedEnd),
+                    };
+                    match chars.next() {
+                        Some((_, '}')) => {}
+                        Some((pos, c)) => return Err(PathParseError::UnexpectedChar(pos, c)),
+                        None => return Err(PathParseError::UnexpectedEnd),
+                    }
+                    Step::Key(key)
+                }
+                c => return Err(PathParseError::UnexpectedChar(pos, c)),
+            };
+            path.push(step).map_err(|_| PathParseError::TooManySteps)?;
+        }
+        Ok(path)
+    }
+}
 
 #[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
 #[display(doc_comments)]
@@ -156,40 +307,370 @@ pub enum PathError {
     UnknownKey(KeyStep),
     /// path doesn't match value at step {0}.
     TypeMismatch(Step, StrictVal),
+    /// empty path can't be used to remove a value.
+    EmptyPath,
+}
+
+/// Errors editing typed values.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(inner)]
+pub enum EditError {
+    #[from]
+    Path(PathError),
+
+    #[from]
+    Typify(typify::Error),
+}
+
+impl From<KeyStep> for StrictVal {
+    fn from(key: KeyStep) -> Self {
+        match key {
+            KeyStep::Number(no) => match u64::try_from(no) {
+                Ok(no) => StrictVal::num(no),
+                Err(_) => StrictVal::num(u1024::from(no)),
+            },
+            KeyStep::TinyBlob(blob) => StrictVal::bytes(blob),
+            KeyStep::TinyString(s) => StrictVal::String(s.release()),
+        }
+    }
 }
 
 impl StrictVal {
+    fn at_step(&self, step: &Step) -> Result<&StrictVal, PathError> {
+        Ok(match (self, step) {
+            // Union variants are transparent for paths
+            (StrictVal::Union(_, val), step) => return val.at_step(step),
+            (StrictVal::Tuple(fields), Step::UnnamedField(no)) => {
+                fields.get(*no as usize).ok_or(PathError::FieldNoOutOfBounds(*no, fields.len()))?
+            }
+            (StrictVal::Struct(fields), Step::NamedField(name)) => {
+                fields.get(name).ok_or(PathError::UnknownFieldName(name.clone()))?
+            }
+            (StrictVal::List(items) | StrictVal::Set(items), Step::Index(idx)) => items
+                .get(*idx as usize)
+                .ok_or(PathError::CollectionIndexOutOfBounds(*idx, items.len()))?,
+            (StrictVal::Map(items), Step::Key(idx)) => items
+                .iter()
+                .find(|(key, _)| idx.has_match(key))
+                .map(|(_, val)| val)
+                .ok_or(PathError::UnknownKey(idx.clone()))?,
+
+            (_, step) => return Err(PathError::TypeMismatch(step.clone(), self.clone())),
+        })
+    }
+
+    fn at_step_mut(&mut self, step: &Step) -> Result<&mut StrictVal, PathError> {
+        Ok(match (self, step) {
+            (StrictVal::Union(_, val), step) => return val.at_step_mut(step),
+            (StrictVal::Tuple(fields), Step::UnnamedField(no)) => {
+                let len = fields.len();
+                fields.get_mut(*no as usize).ok_or(PathError::FieldNoOutOfBounds(*no, len))?
+            }
+            (StrictVal::Struct(fields), Step::NamedField(name)) => {
+                fields.get_mut(name).ok_or(PathError::UnknownFieldName(name.clone()))?
+            }
+            (StrictVal::List(items) | StrictVal::Set(items), Step::Index(idx)) => {
+                let len = items.len();
+                items
+                    .get_mut(*idx as usize)
+                    .ok_or(PathError::CollectionIndexOutOfBounds(*idx, len))?
+            }
+            (StrictVal::Map(items), Step::Key(idx)) => items
+                .iter_mut()
+                .find(|(key, _)| idx.has_match(key))
+                .map(|(_, val)| val)
+                .ok_or(PathError::UnknownKey(idx.clone()))?,
+
+            (me, step) => return Err(PathError::TypeMismatch(step.clone(), me.clone())),
+        })
+    }
+
+    fn unwrap_union_mut(&mut self) -> &mut StrictVal {
+        match self {
+            StrictVal::Union(_, val) => val.unwrap_union_mut(),
+            me => me,
+        }
+    }
+
     pub fn at_path<'p>(
         &self,
         path: impl IntoIterator<Item = &'p Step>,
     ) -> Result<&StrictVal, PathError> {
-        let mut iter = path.into_iter();
-        match (self, iter.next()) {
-            (val, None) => Ok(val),
-            (StrictVal::Tuple(fields), Some(Step::UnnamedField(no)))
-                if *no as usize >= fields.len() =>
-            {
-                Err(PathError::FieldNoOutOfBounds(*no, fields.len()))
+        let mut val = self;
+        for step in path {
+            val = val.at_step(step)?;
+        }
+        Ok(val)
+    }
+
+    pub fn at_path_mut<'p>(
+        &mut self,
+        path: impl IntoIterator<Item = &'p Step>,
+    ) -> Result<&mut StrictVal, PathError> {
+        let mut val = self;
+        for step in path {
+            val = val.at_step_mut(step)?;
+        }
+        Ok(val)
+    }
+
+    /// Sets value at the provided path, returning the replaced value, if any.
+    ///
+    /// The last step of the path may address a non-existing struct field or map key, in which
+    /// case they are added; a list index equal to the collection length appends a new item, as
+    /// does any set index past the end of the set. All other steps must address existing values.
+    ///
+    /// The value is not checked against its type, and new set items and map keys are appended
+    /// to the end of the collection; use [`SymbolicSys::set_at_path`] to edit typed values,
+    /// which also puts them into the canonical order.
+    pub fn set_at_path<'p>(
+        &mut self,
+        path: impl IntoIterator<Item = &'p Step>,
+        val: impl Into<StrictVal>,
+    ) -> Result<Option<StrictVal>, PathError> {
+        let val = val.into();
+        let mut path = path.into_iter().collect::<Vec<_>>();
+        let Some(last) = path.pop() else {
+            return Ok(Some(std::mem::replace(self, val)));
+        };
+        let parent = self.at_path_mut(path)?.unwrap_union_mut();
+        Ok(match (parent, last) {
+            (StrictVal::Struct(fields), Step::NamedField(name)) => fields.insert(name.clone(), val),
+            (StrictVal::List(items), Step::Index(idx)) if *idx as usize == items.len() => {
+                items.push(val);
+                None
+            }
+            (StrictVal::Set(items), Step::Index(idx)) if *idx as usize >= items.len() => {
+                items.push(val);
+                None
+            }
+            (StrictVal::Map(items), Step::Key(idx)) => {
+                match items.iter_mut().find(|(key, _)| idx.has_match(key)) {
+                    Some((_, item)) => Some(std::mem::replace(item, val)),
+                    None => {
+                        items.push((idx.clone().into(), val));
+                        None
+                    }
+                }
             }
-            (StrictVal::Tuple(fields), Some(Step::UnnamedField(no))) => Ok(&fields[*no as usize]),
-            (StrictVal::Struct(fields), Some(Step::NamedField(name))) => {
-                fields.get(name).ok_or(PathError::UnknownFieldName(name.clone()))
+            (parent, step) => Some(std::mem::replace(parent.at_step_mut(step)?, val)),
+        })
+    }
+
+    /// Removes value at the provided path, returning the removed value.
+    ///
+    /// The value is not checked against its type; use [`SymbolicSys::remove_at_path`] to edit
+    /// typed values.
+    pub fn remove_at_path<'p>(
+        &mut self,
+        path: impl IntoIterator<Item = &'p Step>,
+    ) -> Result<StrictVal, PathError> {
+        let mut path = path.into_iter().collect::<Vec<_>>();
+        let last = path.pop().ok_or(PathError::EmptyPath)?;
+        let parent = self.at_path_mut(path)?.unwrap_union_mut();
+        Ok(match (parent, last) {
+            (StrictVal::Tuple(fields), Step::UnnamedField(no)) if (*no as usize) < fields.len() => {
+                fields.remove(*no as usize)
+            }
+            (StrictVal::Struct(fields), Step::NamedField(name)) => {
+                fields.shift_remove(name).ok_or(PathError::UnknownFieldName(name.clone()))?
             }
-            (StrictVal::List(items) | StrictVal::Set(items), Some(Step::Index(idx)))
-                if *idx as usize >= items.len() =>
+            (StrictVal::List(items) | StrictVal::Set(items), Step::Index(idx))
+                if (*idx as usize) < items.len() =>
             {
-                Err(PathError::CollectionIndexOutOfBounds(*idx, items.len()))
+                items.remove(*idx as usize)
             }
-            (StrictVal::List(items) | StrictVal::Set(items), Some(Step::Index(idx))) => {
-                Ok(&items[*idx as usize])
+            (StrictVal::Map(items), Step::Key(idx)) => {
+                let pos = items
+                    .iter()
+                    .position(|(key, _)| idx.has_match(key))
+                    .ok_or(PathError::UnknownKey(idx.clone()))?;
+                items.remove(pos).1
             }
-            (StrictVal::Map(items), Some(Step::Key(idx))) => items
-                .iter()
-                .find(|(key, _)| idx.has_match(key))
-                .map(|(_, val)| val)
-                .ok_or(PathError::UnknownKey(idx.clone())),
+            // Produces a proper error for the step
+            (parent, step) => {
+                parent.at_step(step)?;
+                unreachable!("at_step must fail on a step which can't be removed")
+            }
+        })
+    }
+}
 
-            (_, Some(step)) => Err(PathError::TypeMismatch(step.clone(), self.clone())),
-        }
+impl SymbolicSys {
+    /// Sets value at the provided path inside a typed value, and checks that the result still
+    /// matches the value type.
+    ///
+    /// New set items and map keys are placed according to the canonical order of their type,
+    /// so for sets any index past the end of the set may be used to insert a new item.
+    pub fn set_at_path<'p>(
+        &self,
+        val: TypedVal,
+        path: impl IntoIterator<Item = &'p Step>,
+        new: impl Into<StrictVal>,
+    ) -> Result<TypedVal, EditError> {
+        let TypedVal { orig, mut val } = val;
+        val.set_at_path(path, new)?;
+        let checked = self.as_types().typify(val, orig.id)?;
+        Ok(TypedVal {
+            orig,
+            val: checked.val,
+        })
+    }
+
+    /// Removes value at the provided path inside a typed value, and checks that the result still
+    /// matches the value type.
+    pub fn remove_at_path<'p>(
+        &self,
+        val: TypedVal,
+        path: impl IntoIterator<Item = &'p Step>,
+    ) -> Result<TypedVal, EditError> {
+        let TypedVal { orig, mut val } = val;
+        val.remove_at_path(path)?;
+        let checked = self.as_types().typify(val, orig.id)?;
+        Ok(TypedVal {
+            orig,
+            val: checked.val,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::Confined;
+
+    use super::super::test_helpers::*;
+    use super::*;
+
+    #[test]
+    fn parse_display() {
+        let path = Path::from(SmallVec::from_iter_checked([
+            Step::NamedField(fname!("field")),
+            Step::UnnamedField(3),
+            Step::Index(12),
+            Step::Key(KeyStep::Number(5)),
+            Step::Key(KeyStep::TinyString(Confined::try_from(s!(r#"quoted "key" \ }"#)).unwrap())),
+            Step::Key(KeyStep::TinyBlob(Confined::try_from(vec![0xDE, 0xAD]).unwrap())),
+        ]));
+        let s = path.to_string();
+        assert_eq!(s, r#".field.3[12]{5}{"quoted \"key\" \\ }"}{0hDEAD}"#);
+        assert_eq!(Path::from_str(&s).unwrap(), path);
+        assert_eq!(Path::from_str("").unwrap(), Path::new());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Path::from_str(".a[1"), Err(PathParseError::UnexpectedEnd));
+        assert_eq!(Path::from_str(".a[x]"), Err(PathParseError::UnexpectedChar(3, 'x')));
+        assert_eq!(Path::from_str("a"), Err(PathParseError::UnexpectedChar(0, 'a')));
+        assert_eq!(Path::from_str(".256"), Err(PathParseError::NumberOverflow(0)));
+        assert_eq!(Path::from_str(r#"{"key}"#), Err(PathParseError::UnterminatedString(1)));
+        assert_eq!(Path::from_str("{0hABC}"), Err(PathParseError::InvalidBlob(1)));
+        assert!(matches!(Path::from_str(".Field"), Err(PathParseError::InvalidFieldName(1, _))));
+    }
+
+    #[test]
+    fn at_parsed_path() {
+        let val = ston!(
+            list svlist!([ston!(sv!(1u8), sv!("a")), ston!(sv!(2u8), sv!("b"))]),
+            map StrictVal::map([(sv!("key"), sv!(7u8))])
+        );
+        let path = Path::from_str(".list[1].1").unwrap();
+        assert_eq!(val.at_path(&path), Ok(&sv!("b")));
+        let path = Path::from_str(r#".map{"key"}"#).unwrap();
+        assert_eq!(val.at_path(&path), Ok(&sv!(7u8)));
+        let path = Path::from_str(".list[2]").unwrap();
+        assert_eq!(val.at_path(&path), Err(PathError::CollectionIndexOutOfBounds(2, 2)));
+    }
+
+    #[test]
+    fn edit_paths() {
+        let mut val = ston!(
+            list svlist!([1u8, 2u8]),
+            opt StrictVal::some(StrictVal::struc([("inner", 5u8)])),
+            map StrictVal::map([(sv!("key"), sv!(7u8))])
+        );
+
+        let path = Path::from_str(".opt.inner").unwrap();
+        *val.at_path_mut(&path).unwrap() = sv!(6u8);
+        assert_eq!(val.at_path(&path), Ok(&sv!(6u8)));
+
+        let path = Path::from_str(".list[2]").unwrap();
+        assert_eq!(val.set_at_path(&path, 3u8), Ok(None));
+        assert_eq!(val.at_path(&path), Ok(&sv!(3u8)));
+        let path = Path::from_str(".list[4]").unwrap();
+        assert_eq!(val.set_at_path(&path, 3u8), Err(PathError::CollectionIndexOutOfBounds(4, 3)));
+
+        let path = Path::from_str(r#".map{"key"}"#).unwrap();
+        assert_eq!(val.set_at_path(&path, 8u8), Ok(Some(sv!(7u8))));
+        let path = Path::from_str(r#".map{"other"}"#).unwrap();
+        assert_eq!(val.set_at_path(&path, 9u8), Ok(None));
+        assert_eq!(val.at_path(&path), Ok(&sv!(9u8)));
+        assert_eq!(val.remove_at_path(&path), Ok(sv!(9u8)));
+        assert_eq!(
+            val.at_path(&path),
+            Err(PathError::UnknownKey(KeyStep::TinyString(tiny_s!("other"))))
+        );
+
+        let path = Path::from_str(".list[0]").unwrap();
+        assert_eq!(val.remove_at_path(&path), Ok(sv!(1u8)));
+        assert_eq!(val.at_path(&Path::from_str(".list").unwrap()), Ok(&svlist!([2u8, 3u8])));
+        assert_eq!(val.remove_at_path(&Path::new()), Err(PathError::EmptyPath));
+        assert!(matches!(
+            val.remove_at_path(&Path::from_str(".list.0").unwrap()),
+            Err(PathError::TypeMismatch(Step::UnnamedField(0), _))
+        ));
+    }
+
+    #[test]
+    fn edit_typed() {
+        let sys = test_system();
+        let val =
+            ston!(name "Some name", ticker svnewtype!("TICK"), precision svenum!(twoDecimals));
+        let val = sys.typify(val, "TestLib.Nominal").unwrap();
+
+        let path = Path::from_str(".precision").unwrap();
+        let edited = sys.set_at_path(val.clone(), &path, svenum!(0)).unwrap();
+        assert_eq!(edited.as_val().at_path(&path), Ok(&svenum!(noDecimals)));
+
+        assert!(matches!(
+            sys.set_at_path(val.clone(), &path, svenum!(5)),
+            Err(EditError::Typify(typify::Error::EnumTagInvalid(..)))
+        ));
+        assert!(matches!(
+            sys.remove_at_path(val.clone(), &path),
+            Err(EditError::Typify(typify::Error::FieldNumberMismatch { .. }))
+        ));
+        assert!(matches!(
+            sys.set_at_path(val, &Path::from_str(".unknown").unwrap(), 0u8),
+            Err(EditError::Typify(typify::Error::FieldNumberMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn edit_typed_order() {
+        let sys = system_from_source(
+            "typelib Edit
+data Index : {[Unicode ^ ..0xff] -> ^ ..0xff U8}
+data Ids : {U16 ^ ..0xff}
+",
+        );
+        let index = sys.parse_ston(r#"{"key" -> 7}"#, "Edit.Index").unwrap();
+        let path = Path::from_str(r#".0{"a"}"#).unwrap();
+        let edited = sys.set_at_path(index, &path, 9u8).unwrap();
+        let expected = StrictVal::map([(sv!("a"), 9u8), (sv!("key"), 7u8)]);
+        assert_eq!(edited.as_val(), &StrictVal::newtype(expected));
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&edited).unwrap();
+        assert_eq!(sys.strict_deserialize_type("Edit.Index", &data).unwrap(), edited);
+
+        let ids = sys.parse_ston("{1, 5}", "Edit.Ids").unwrap();
+        let edited = sys.set_at_path(ids.clone(), &Path::from_str(".0[7]").unwrap(), 3u16);
+        let edited = edited.unwrap();
+        assert_eq!(edited.as_val(), &StrictVal::newtype(StrictVal::set([1u16, 3, 5])));
+        let data = sys.as_types().strict_serialize_value::<{ usize::MAX }>(&edited).unwrap();
+        assert_eq!(sys.strict_deserialize_type("Edit.Ids", &data).unwrap(), edited);
+        assert!(matches!(
+            sys.set_at_path(ids, &Path::from_str(".0[2]").unwrap(), 5u16),
+            Err(EditError::Typify(typify::Error::RepeatedSetValue(..)))
+        ));
     }
 }
@@ -0,0 +1,525 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of random well-formed strict values for property testing.
+//!
+//! The generator walks a type in a [`TypeSystem`] and produces values which pass type checking
+//! and survive strict encoding round-trips: it respects sizing bounds, array lengths, enum and
+//! union tags, character sets of ASCII and restricted strings and uniqueness of set elements and
+//! map keys. Generation is deterministic for a given source of randomness and
+//! [`RandomLimits`]; [`SeededRng`] provides a seedable source. With the `proptest` feature
+//! [`ValueStrategy`] adapts the generator to the `proptest` framework.
+
+use amplify::confinement::U16 as MAX16;
+use encoding::{NumCls, Primitive, Sizing, StreamReader};
+use indexmap::IndexMap;
+
+use super::typify::{SpecError, TypeSpec, TypedVal};
+use super::Blob;
+use crate::typesys::{SymbolicSys, TypeSymbol, UnknownType};
+use crate::{SemId, StrictVal, Ty, TypeRef, TypeSystem};
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum Error {
+    /// unknown type `{0}`.
+    TypeAbsent(TypeSpec),
+
+    #[display(inner)]
+    #[from]
+    Spec(SpecError),
+
+    #[display(inner)]
+    #[from]
+    UnknownType(UnknownType),
+
+    /// random values of {0} can't be generated.
+    Unsupported(String),
+
+    /// type {0} doesn't have enough distinct values to fill a collection with at least {1}
+    /// items.
+    NotUnique(SemId, u64),
+}
+
+/// Source of randomness for the value generator.
+pub trait RandomSource {
+    /// Returns next random 64-bit number.
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<R: RandomSource + ?Sized> RandomSource for &mut R {
+    fn next_u64(&mut self) -> u64 { (**self).next_u64() }
+}
+
+/// Seedable pseudo-random number generator (SplitMix64).
+///
+/// The generator is fast and deterministic, but is not cryptographically secure.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SeededRng(u64);
+
+impl SeededRng {
+    pub fn with_seed(seed: u64) -> Self { SeededRng(seed) }
+}
+
+impl RandomSource for SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Limits on the size of generated values.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RandomLimits {
+    /// Maximal number of items added to collections on top of their minimal length. For unicode
+    /// strings the limit applies to the number of bytes.
+    pub max_len: u64,
+    /// Nesting depth after which collections get their minimal length and unions - their first
+    /// variant (which is `none` for optional values).
+    pub max_depth: usize,
+}
+
+impl Default for RandomLimits {
+    fn default() -> Self {
+        RandomLimits {
+            max_len: 8,
+            max_depth: 8,
+        }
+    }
+}
+
+impl SymbolicSys {
+    pub fn random_value(
+        &self,
+        spec: impl Into<TypeSpec>,
+        rng: &mut impl RandomSource,
+        limits: RandomLimits,
+    ) -> Result<TypedVal, Error> {
+        let sem_id = self.resolve_spec(spec).map_err(|err| err.report(Error::TypeAbsent))?;
+        self.as_types().random_value(sem_id, rng, limits)
+    }
+}
+
+impl TypeSystem {
+    /// Generates random value of the given type, which is valid under this type system.
+    pub fn random_value(
+        &self,
+        sem_id: SemId,
+        rng: &mut impl RandomSource,
+        limits: RandomLimits,
+    ) -> Result<TypedVal, Error> {
+        let mut gen = Generator {
+            sys: self,
+            rng,
+            limits,
+        };
+        Ok(TypedVal {
+            val: gen.value(sem_id, 0)?,
+            orig: TypeSymbol::unnamed(sem_id),
+        })
+    }
+}
+
+struct Generator<'a, R: RandomSource> {
+    sys: &'a TypeSystem,
+    rng: &'a mut R,
+    limits: RandomLimits,
+}
+
+impl<R: RandomSource> Generator<'_, R> {
+    fn range(&mut self, min: u64, max: u64) -> u64 {
+        match max - min {
+            u64::MAX => self.rng.next_u64(),
+            span => min + self.rng.next_u64() % (span + 1),
+        }
+    }
+
+    fn len(&mut self, sizing: Sizing, depth: usize) -> u64 {
+        let max = if depth >= self.limits.max_depth {
+            sizing.min
+        } else {
+            sizing.max.min(sizing.min.saturating_add(self.limits.max_len))
+        };
+        self.range(sizing.min, max)
+    }
+
+    fn bytes(&mut self, len: u64) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(len as usize);
+        while (bytes.len() as u64) < len {
+            let rest = (len - bytes.len() as u64).min(8) as usize;
+            bytes.extend_from_slice(&self.rng.next_u64().to_le_bytes()[..rest]);
+        }
+        bytes
+    }
+
+    fn primitive(&mut self, prim: Primitive) -> Result<StrictVal, Error> {
+        if prim == Primitive::UNIT {
+            return Ok(StrictVal::Unit);
+        }
+        let len = prim.byte_size() as u64;
+        // Numbers made of all-zero or all-one bytes are the edge cases (zero, minus one and the
+        // maximal value), so they are generated more often than a uniform distribution would do.
+        let mut bytes = match self.range(0, 7) {
+            0 => vec![0x00; len as usize],
+            1 => vec![0xFF; len as usize],
+            _ => self.bytes(len),
+        };
+        if prim.info().ty == NumCls::NonZero && bytes.iter().all(|byte| *byte == 0) {
+            bytes[0] = 1;
+        }
+        // Parsing the bytes with the decoder guarantees the canonical form of the number.
+        let mut cursor = StreamReader::cursor::<MAX16>(bytes);
+        TypeSystem::strict_read_primitive(prim, &mut cursor).map_err(|_| {
+            Error::Unsupported(format!("primitive with code {:#04x}", prim.into_code()))
+        })
+    }
+
+    fn char(&mut self, utf8_len: u64) -> char {
+        let (min, max) = match utf8_len {
+            1 => (0x00, 0x7F),
+            2 => (0x80, 0x7FF),
+            3 => (0x800, 0xFFFF),
+            _ => (0x10000, 0x10FFFF),
+        };
+        loop {
+            // Surrogates are not valid characters and are skipped
+            if let Some(c) = char::from_u32(self.range(min, max) as u32) {
+                return c;
+            }
+        }
+    }
+
+    fn unicode(&mut self, mut len: u64) -> String {
+        let mut s = String::with_capacity(len as usize);
+        while len > 0 {
+            let utf8_len = self.range(1, len.min(4));
+            s.push(self.char(utf8_len));
+            len -= utf8_len;
+        }
+        s
+    }
+
+    fn char_tags(&self, sem_id: SemId) -> Result<Vec<u8>, Error> {
+        match self.sys.find(sem_id).ok_or_else(|| Error::TypeAbsent(sem_id.into()))? {
+            Ty::Enum(variants) => Ok(variants.iter().map(|variant| variant.tag).collect()),
+            Ty::Tuple(fields) => self.char_tags(fields[0]),
+            _ => Err(Error::Unsupported(format!("characters of type {sem_id}"))),
+        }
+    }
+
+    fn ascii(&mut self, first: &[u8], rest: &[u8], len: u64) -> String {
+        (0..len)
+            .map(|pos| {
+                let tags = if pos == 0 { first } else { rest };
+                char::from(tags[self.range(0, tags.len() as u64 - 1) as usize])
+            })
+            .collect()
+    }
+
+    fn items(&mut self, ty: SemId, len: u64, depth: usize) -> Result<Vec<StrictVal>, Error> {
+        (0..len).map(|_| self.value(ty, depth + 1)).collect()
+    }
+
+    fn attempts(len: u64) -> u64 { len.saturating_mul(4).saturating_add(16) }
+
+    fn set(&mut self, ty: SemId, sizing: Sizing, depth: usize) -> Result<Vec<StrictVal>, Error> {
+        let len = self.len(sizing, depth);
+        let mut set = Vec::<StrictVal>::with_capacity(len as usize);
+        for _ in 0..Self::attempts(len) {
+            if set.len() as u64 >= len {
+                break;
+            }
+            let item = self.value(ty, depth + 1)?;
+            if let Err(pos) = set.binary_search_by(|probe| self.sys.cmp_vals(probe, &item, ty)) {
+                set.insert(pos, item);
+            }
+        }
+        if (set.len() as u64) < sizing.min {
+            return Err(Error::NotUnique(ty, sizing.min));
+        }
+        Ok(set)
+    }
+
+    fn map(
+        &mut self,
+        key_ty: SemId,
+        ty: SemId,
+        sizing: Sizing,
+        depth: usize,
+    ) -> Result<Vec<(StrictVal, StrictVal)>, Error> {
+        let len = self.len(sizing, depth);
+        let mut map = Vec::<(StrictVal, StrictVal)>::with_capacity(len as usize);
+        for _ in 0..Self::attempts(len) {
+            if map.len() as u64 >= len {
+                break;
+            }
+            let key = self.value(key_ty, depth + 1)?;
+            if let Err(pos) =
+                map.binary_search_by(|(probe, _)| self.sys.cmp_vals(probe, &key, key_ty))
+            {
+                let val = self.value(ty, depth + 1)?;
+                map.insert(pos, (key, val));
+            }
+        }
+        if (map.len() as u64) < sizing.min {
+            return Err(Error::NotUnique(key_ty, sizing.min));
+        }
+        Ok(map)
+    }
+
+    fn value(&mut self, sem_id: SemId, depth: usize) -> Result<StrictVal, Error> {
+        let sys = self.sys;
+        let ty = sys.find(sem_id).ok_or_else(|| Error::TypeAbsent(sem_id.into()))?;
+
+        Ok(match ty {
+            Ty::Primitive(prim) => self.primitive(*prim)?,
+            Ty::UnicodeChar => {
+                let utf8_len = self.range(1, 4);
+                StrictVal::String(self.char(utf8_len).to_string())
+            }
+
+            // ASCII strings:
+            Ty::List(id, sizing)
+                if sys.find(*id).ok_or_else(|| Error::TypeAbsent((*id).into()))?.is_char_enum() =>
+            {
+                let tags = self.char_tags(*id)?;
+                let len = self.len(*sizing, depth);
+                StrictVal::String(self.ascii(&tags, &tags, len))
+            }
+            // Restricted strings:
+            Ty::Tuple(fields) if sys.is_rstring(fields)? => {
+                let (rest, sizing) = sys.rstring_sizing(fields)?.expect("checked in match");
+                let first = self.char_tags(fields[0])?;
+                let rest = self.char_tags(rest)?;
+                let len = self.len(sizing, depth);
+                StrictVal::String(self.ascii(&first, &rest, len))
+            }
+
+            Ty::Enum(variants) => {
+                let no = self.range(0, variants.len() as u64 - 1) as usize;
+                let variant = variants.iter().nth(no).expect("variant index within bounds");
+                StrictVal::enumer(variant.name.clone())
+            }
+            Ty::Union(variants) => {
+                let no = if depth >= self.limits.max_depth {
+                    0
+                } else {
+                    self.range(0, variants.len() as u64 - 1) as usize
+                };
+                let (variant, ty) = variants.iter().nth(no).expect("variant index within bounds");
+                StrictVal::union(variant.name.clone(), self.value(*ty, depth + 1)?)
+            }
+            Ty::Tuple(fields) => StrictVal::tuple(
+                fields
+                    .iter()
+                    .map(|ty| self.value(*ty, depth + 1))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            Ty::Struct(fields) => {
+                let mut vals = IndexMap::with_capacity(fields.len());
+                for field in fields {
+                    vals.insert(field.name.clone(), self.value(field.ty, depth + 1)?);
+                }
+                StrictVal::Struct(vals)
+            }
+
+            // Fixed-size arrays:
+            Ty::Array(ty, len) if ty.is_byte() => StrictVal::Bytes(Blob(self.bytes(*len as u64))),
+            Ty::Array(ty, len) => StrictVal::List(self.items(*ty, *len as u64, depth)?),
+
+            // Byte and unicode strings:
+            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u32::MAX as u64 => {
+                let len = self.len(*sizing, depth);
+                StrictVal::Bytes(Blob(self.bytes(len)))
+            }
+            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u32::MAX as u64 => {
+                let len = self.len(*sizing, depth);
+                StrictVal::String(self.unicode(len))
+            }
+
+            // Other collections:
+            Ty::List(ty, sizing) => {
+                let len = self.len(*sizing, depth);
+                StrictVal::List(self.items(*ty, len, depth)?)
+            }
+            Ty::Set(ty, sizing) => StrictVal::Set(self.set(*ty, *sizing, depth)?),
+            Ty::Map(key_ty, ty, sizing) => StrictVal::Map(self.map(*key_ty, *ty, *sizing, depth)?),
+        })
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod strategy {
+    use proptest::prelude::Rng;
+    use proptest::strategy::{Just, NewTree, Strategy};
+    use proptest::test_runner::{TestRng, TestRunner};
+
+    use super::*;
+
+    impl RandomSource for TestRng {
+        fn next_u64(&mut self) -> u64 { Rng::next_u64(self) }
+    }
+
+    /// `proptest` strategy producing random values of a strict type.
+    ///
+    /// Generated values are not shrunk.
+    #[derive(Clone, Debug)]
+    pub struct ValueStrategy {
+        sys: TypeSystem,
+        sem_id: SemId,
+        limits: RandomLimits,
+    }
+
+    impl Strategy for ValueStrategy {
+        type Tree = Just<TypedVal>;
+        type Value = TypedVal;
+
+        fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+            self.sys
+                .random_value(self.sem_id, runner.rng(), self.limits)
+                .map(Just)
+                .map_err(|err| err.to_string().into())
+        }
+    }
+
+    impl SymbolicSys {
+        pub fn value_strategy(
+            &self,
+            spec: impl Into<TypeSpec>,
+            limits: RandomLimits,
+        ) -> Result<ValueStrategy, Error> {
+            let sem_id = self.resolve_spec(spec).map_err(|err| err.report(Error::TypeAbsent))?;
+            self.as_types().value_strategy(sem_id, limits)
+        }
+    }
+
+    impl TypeSystem {
+        /// Constructs `proptest` strategy for the values of a given type. The strategy contains
+        /// only the part of the type system which is required by the type.
+        pub fn value_strategy(
+            &self,
+            sem_id: SemId,
+            limits: RandomLimits,
+        ) -> Result<ValueStrategy, Error> {
+            Ok(ValueStrategy {
+                sys: self.extract([sem_id])?,
+                sem_id,
+                limits,
+            })
+        }
+    }
+}
+#[cfg(feature = "proptest")]
+pub use strategy::ValueStrategy;
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::U32 as MAX32;
+
+    use super::super::test_helpers::*;
+    use super::*;
+
+    fn round_trip(sys: &TypeSystem, sem_id: SemId, seed: u64) {
+        let mut rng = SeededRng::with_seed(seed);
+        let val = sys.random_value(sem_id, &mut rng, RandomLimits::default()).unwrap();
+        let typed = sys.typify(val.as_val().clone(), sem_id).unwrap();
+        let data = sys.strict_serialize_value::<MAX32>(&typed).unwrap();
+        let decoded = sys.strict_deserialize_type(sem_id, data.as_slice()).unwrap();
+        assert_eq!(decoded.as_val(), val.as_val(), "{sem_id} with seed {seed}");
+    }
+
+    #[test]
+    fn stl_round_trip() {
+        let sys = test_system();
+        let types = sys.as_types();
+        for sem_id in types.keys() {
+            for seed in 0..16 {
+                round_trip(types, *sem_id, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn primitives() {
+        let src = "typelib Prims
+data Char : Unicode
+data Floats : F16, F16b, F32, F64, F80, F128, F256
+data Numbers : Byte, U8, I16, U24, I64, U128, I256, N8, N64, N256, U1024, I1024
+";
+        let sys = system_from_source(src);
+        for name in ["Prims.Char", "Prims.Floats", "Prims.Numbers"] {
+            let sem_id = sys.to_sem_id(name).unwrap();
+            for seed in 0..256 {
+                round_trip(sys.as_types(), sem_id, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        let sys = test_system();
+        let gen = |seed| {
+            let mut rng = SeededRng::with_seed(seed);
+            sys.random_value("TestLib.Nominal", &mut rng, RandomLimits::default()).unwrap()
+        };
+        assert_eq!(gen(42), gen(42));
+        assert_ne!(gen(1), gen(2));
+    }
+
+    #[test]
+    fn depth_limit() {
+        let sys = test_system();
+        let limits = RandomLimits {
+            max_len: 8,
+            max_depth: 0,
+        };
+        for seed in 0..16 {
+            let mut rng = SeededRng::with_seed(seed);
+            let val = sys.random_value("StrictTypes.TypeLib", &mut rng, limits).unwrap();
+            assert_eq!(val.as_val().unwrap_struct("dependencies"), &StrictVal::Set(vec![]));
+            let StrictVal::Map(types) = val.as_val().unwrap_struct("types") else {
+                panic!()
+            };
+            assert_eq!(types.len(), 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn strategy() {
+        use proptest::test_runner::TestRunner;
+
+        let sys = test_system();
+        let strategy = sys.value_strategy("TestLib.Nominal", RandomLimits::default()).unwrap();
+        TestRunner::default()
+            .run(&strategy, |val| {
+                let data = sys.as_types().strict_serialize_value::<MAX32>(&val).unwrap();
+                let decoded = sys.strict_deserialize_type("TestLib.Nominal", &data).unwrap();
+                assert_eq!(decoded.as_val(), val.as_val());
+                Ok(())
+            })
+            .unwrap();
+    }
+}
@@ -0,0 +1,562 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON Schema (draft 2020-12) describing the canonical JSON representation of strict values,
+//! as produced by [`SymbolicSys::to_json`].
+
+use std::collections::BTreeSet;
+
+use encoding::{NumCls, Primitive, Sizing};
+use serde_json::{json, Map, Value};
+
+use super::typify::{SpecError, TypeSpec};
+use crate::ast::EnumVariants;
+use crate::{SemId, SymbolicSys, Ty, TypeRef};
+
+/// Identifier of the JSON Schema dialect used by the generated schemata.
+pub const JSON_SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum SchemaError {
+    /// unknown type `{0}`.
+    TypeAbsent(TypeSpec),
+
+    #[display(inner)]
+    #[from]
+    Spec(SpecError),
+}
+
+/// Form of a JSON value representing a map key, which defines whether the map is represented by
+/// an object or an array of key-value pairs.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum KeyForm {
+    String,
+    Other,
+    Either,
+}
+
+impl KeyForm {
+    fn join(self, other: KeyForm) -> KeyForm {
+        if self == other {
+            self
+        } else {
+            KeyForm::Either
+        }
+    }
+}
+
+impl SymbolicSys {
+    /// Constructs JSON Schema (draft 2020-12) for the canonical JSON representation of the
+    /// provided type, such that any value converted with [`SymbolicSys::to_json`] is valid
+    /// against it.
+    ///
+    /// Named types are placed into `$defs` under their fully qualified names and referenced with
+    /// `$ref`; unnamed types are inlined. Collection sizes are expressed with `minItems` and
+    /// `maxItems`, byte strings - with lowercase hex patterns and ASCII strings - with patterns
+    /// listing allowed characters. Since the schema length limits of unicode strings count
+    /// characters and not bytes, they are relaxed to the range of character numbers which may
+    /// fit the byte limits.
+    pub fn to_json_schema(&self, spec: impl Into<TypeSpec>) -> Result<Value, SchemaError> {
+        let sem_id = self.resolve_spec(spec).map_err(|err| err.report(SchemaError::TypeAbsent))?;
+        let mut builder = SchemaBuilder {
+            sys: self,
+            defs: Map::new(),
+        };
+        let root = builder.schema(sem_id)?;
+
+        let mut schema = Map::new();
+        schema.insert(s!("$schema"), json!(JSON_SCHEMA_DRAFT));
+        if let Value::Object(root) = root {
+            schema.extend(root);
+        }
+        if !builder.defs.is_empty() {
+            schema.insert(s!("$defs"), Value::Object(builder.defs));
+        }
+        Ok(Value::Object(schema))
+    }
+}
+
+struct SchemaBuilder<'a> {
+    sys: &'a SymbolicSys,
+    defs: Map<String, Value>,
+}
+
+impl<'a> SchemaBuilder<'a> {
+    fn get(&self, sem_id: SemId) -> Result<&'a Ty<SemId>, SchemaError> {
+        self.sys.as_types().find(sem_id).ok_or(SchemaError::TypeAbsent(sem_id.into()))
+    }
+
+    fn schema(&mut self, sem_id: SemId) -> Result<Value, SchemaError> {
+        let Some(fqn) = self.sys.lookup(sem_id) else {
+            return self.inline(self.get(sem_id)?);
+        };
+        let name = fqn.to_string();
+        if !self.defs.contains_key(&name) {
+            // Placeholder preventing infinite recursion on recursive types
+            self.defs.insert(name.clone(), Value::Null);
+            let def = self.inline(self.get(sem_id)?)?;
+            self.defs.insert(name.clone(), def);
+        }
+        Ok(json!({ "$ref": format!("#/$defs/{name}") }))
+    }
+
+    fn inline(&mut self, ty: &Ty<SemId>) -> Result<Value, SchemaError> {
+        Ok(match ty {
+            Ty::Primitive(prim) => primitive(*prim),
+            Ty::UnicodeChar => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+            Ty::Enum(variants) => {
+                let names = variants.iter().map(|variant| variant.name.to_string());
+                json!({ "enum": names.collect::<Vec<_>>() })
+            }
+            Ty::Union(_) if ty.is_option() => {
+                let some = self.schema(*ty.as_some().expect("checked option"))?;
+                json!({ "anyOf": [{ "type": "null" }, some] })
+            }
+            Ty::Union(variants) => {
+                let mut alts = Vec::with_capacity(variants.len());
+                for (variant, id) in variants {
+                    if self.is_unit(*id)? {
+                        alts.push(json!({ "const": variant.name.to_string() }));
+                        continue;
+                    }
+                    let name = variant.name.to_string();
+                    alts.push(json!({
+                        "type": "object",
+                        "properties": { name.clone(): self.schema(*id)? },
+                        "required": [name],
+                        "additionalProperties": false,
+                    }));
+                }
+                json!({ "oneOf": alts })
+            }
+            Ty::Tuple(fields) if self.sys.as_types().is_rstring(fields).unwrap_or_default() => {
+                let (rest, sizing) = self
+                    .sys
+                    .as_types()
+                    .rstring_sizing(fields)
+                    .ok()
+                    .flatten()
+                    .expect("checked rstring");
+                let first = self.char_class(fields[0])?;
+                let rest = self.char_class(rest)?;
+                let mut schema =
+                    json!({ "type": "string", "pattern": format!("^{first}{rest}*$") });
+                set_len(&mut schema, "Length", sizing);
+                schema
+            }
+            Ty::Tuple(fields) if fields.len() == 1 => self.schema(fields[0])?,
+            Ty::Tuple(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                for id in fields {
+                    items.push(self.schema(*id)?);
+                }
+                json!({
+                    "type": "array",
+                    "prefixItems": items,
+                    "items": false,
+                    "minItems": fields.len(),
+                })
+            }
+            Ty::Struct(fields) => {
+                let mut props = Map::new();
+                for field in fields {
+                    props.insert(field.name.to_string(), self.schema(field.ty)?);
+                }
+                let names = fields.iter().map(|field| field.name.to_string());
+                json!({
+                    "type": "object",
+                    "properties": props,
+                    "required": names.collect::<Vec<_>>(),
+                    "additionalProperties": false,
+                })
+            }
+
+            Ty::Array(id, len) if id.is_byte() => hex(Sizing::fixed(*len as u64)),
+            Ty::List(id, sizing) if id.is_byte() => hex(*sizing),
+            Ty::List(id, sizing) if id.is_unicode_char() => {
+                let mut schema = json!({ "type": "string" });
+                // Each character takes from 1 to 4 bytes
+                let chars = Sizing::new(sizing.min.div_ceil(4), sizing.max);
+                set_len(&mut schema, "Length", chars);
+                schema
+            }
+            Ty::List(id, sizing) if self.get(*id)?.is_char_enum() => {
+                let chars = self.char_class(*id)?;
+                let mut schema = json!({ "type": "string", "pattern": format!("^{chars}*$") });
+                set_len(&mut schema, "Length", *sizing);
+                schema
+            }
+            Ty::Array(id, len) => {
+                let mut schema = json!({ "type": "array", "items": self.schema(*id)? });
+                set_len(&mut schema, "Items", Sizing::fixed(*len as u64));
+                schema
+            }
+            Ty::List(id, sizing) => {
+                let mut schema = json!({ "type": "array", "items": self.schema(*id)? });
+                set_len(&mut schema, "Items", *sizing);
+                schema
+            }
+            Ty::Set(id, sizing) => {
+                let mut schema =
+                    json!({ "type": "array", "items": self.schema(*id)?, "uniqueItems": true });
+                set_len(&mut schema, "Items", *sizing);
+                schema
+            }
+            Ty::Map(key_id, val_id, sizing) => {
+                let key = self.schema(*key_id)?;
+                let val = self.schema(*val_id)?;
+                let mut obj = json!({
+                    "type": "object",
+                    "propertyNames": key,
+                    "additionalProperties": val,
+                });
+                set_len(&mut obj, "Properties", *sizing);
+                let mut list = json!({
+                    "type": "array",
+                    "items": {
+                        "type": "array",
+                        "prefixItems": [key, val],
+                        "items": false,
+                        "minItems": 2,
+                    },
+                });
+                set_len(&mut list, "Items", *sizing);
+                match self.key_form(*key_id, &mut empty!())? {
+                    KeyForm::String => obj,
+                    // Empty maps are always represented by an empty object
+                    KeyForm::Other if sizing.min == 0 => {
+                        json!({ "anyOf": [list, { "type": "object", "maxProperties": 0 }] })
+                    }
+                    KeyForm::Other => list,
+                    KeyForm::Either => json!({ "anyOf": [obj, list] }),
+                }
+            }
+        })
+    }
+
+    fn is_unit(&self, sem_id: SemId) -> Result<bool, SchemaError> {
+        Ok(match self.get(sem_id)? {
+            ty if *ty == Ty::UNIT => true,
+            Ty::Tuple(fields) => fields.is_empty(),
+            _ => false,
+        })
+    }
+
+    /// Detects whether JSON values of the type are strings, which is required for the map keys
+    /// in order for the map to be represented by a JSON object.
+    fn key_form(
+        &self,
+        sem_id: SemId,
+        visited: &mut BTreeSet<SemId>,
+    ) -> Result<KeyForm, SchemaError> {
+        if !visited.insert(sem_id) {
+            return Ok(KeyForm::Either);
+        }
+        let ty = self.get(sem_id)?;
+        Ok(match ty {
+            Ty::Primitive(prim) => match prim.info().ty {
+                _ if *prim == Primitive::UNIT => KeyForm::Other,
+                NumCls::Float => KeyForm::Either,
+                _ if *prim != Primitive::BYTE && prim.byte_size() > 8 => KeyForm::String,
+                _ => KeyForm::Other,
+            },
+            Ty::UnicodeChar | Ty::Enum(_) => KeyForm::String,
+            Ty::Union(_) if ty.is_option() => {
+                let some = *ty.as_some().expect("checked option");
+                KeyForm::Other.join(self.key_form(some, visited)?)
+            }
+            Ty::Union(variants) => {
+                let mut forms = Vec::with_capacity(variants.len());
+                for (_, id) in variants {
+                    forms.push(if self.is_unit(*id)? { KeyForm::String } else { KeyForm::Other });
+                }
+                forms.into_iter().reduce(KeyForm::join).unwrap_or(KeyForm::Other)
+            }
+            Ty::Tuple(fields) if self.sys.as_types().is_rstring(fields).unwrap_or_default() => {
+                KeyForm::String
+            }
+            Ty::Tuple(fields) if fields.len() == 1 => self.key_form(fields[0], visited)?,
+            Ty::Array(id, _) if id.is_byte() => KeyForm::String,
+            Ty::List(id, _) if id.is_byte() || id.is_unicode_char() => KeyForm::String,
+            Ty::List(id, _) if self.get(*id)?.is_char_enum() => KeyForm::String,
+            Ty::Tuple(_)
+            | Ty::Struct(_)
+            | Ty::Array(_, _)
+            | Ty::List(_, _)
+            | Ty::Set(_, _)
+            | Ty::Map(_, _, _) => KeyForm::Other,
+        })
+    }
+
+    /// Constructs regular expression character class matching characters of a character enum.
+    fn char_class(&self, sem_id: SemId) -> Result<String, SchemaError> {
+        let Ty::Enum(variants) = self.get(sem_id)? else {
+            return Ok(s!("."));
+        };
+        Ok(char_class(variants))
+    }
+}
+
+fn primitive(prim: Primitive) -> Value {
+    let info = prim.info();
+    let len = prim.byte_size();
+    match info.ty {
+        _ if prim == Primitive::UNIT => json!({ "type": "null" }),
+        _ if prim == Primitive::BYTE => json!({ "type": "integer", "minimum": 0, "maximum": 255 }),
+        // Floats which can't be exactly represented by JSON numbers are given as strings
+        NumCls::Float => json!({ "type": ["number", "string"] }),
+        // Large numbers are always given as decimal strings
+        NumCls::Signed if len > 8 => json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+        _ if len > 8 => json!({ "type": "string", "pattern": "^[0-9]+$" }),
+        NumCls::Signed => {
+            let max = (1u128 << (len * 8 - 1)) - 1;
+            json!({ "type": "integer", "minimum": -(max as i128) - 1, "maximum": max })
+        }
+        cls => {
+            let max = (1u128 << (len * 8)) - 1;
+            let min = if cls == NumCls::NonZero { 1 } else { 0 };
+            json!({ "type": "integer", "minimum": min, "maximum": max })
+        }
+    }
+}
+
+/// Schema of a lowercase hex string encoding bytes in the given number range.
+fn hex(sizing: Sizing) -> Value {
+    let mut schema = json!({ "type": "string", "pattern": "^([0-9a-f]{2})*$" });
+    set_len(&mut schema, "Length", Sizing::new(sizing.min * 2, sizing.max.saturating_mul(2)));
+    schema
+}
+
+/// Adds `min*` and `max*` keywords with the given suffix to a schema object.
+fn set_len(schema: &mut Value, suffix: &str, sizing: Sizing) {
+    let obj = schema.as_object_mut().expect("schema object");
+    if sizing.min > 0 {
+        obj.insert(format!("min{suffix}"), json!(sizing.min));
+    }
+    obj.insert(format!("max{suffix}"), json!(sizing.max));
+}
+
+/// Constructs regular expression character class for the characters of a character enum,
+/// merging consecutive characters into ranges.
+fn char_class(variants: &EnumVariants) -> String {
+    fn push(class: &mut String, c: u8) {
+        if b"\\]^-[".contains(&c) {
+            class.push('\\');
+        }
+        match c {
+            0x7F => class.push_str("\\x7f"),
+            c => class.push(c as char),
+        }
+    }
+
+    let tags = variants.iter().map(|variant| variant.tag).collect::<BTreeSet<_>>();
+    let mut class = s!("[");
+    let mut tags = tags.into_iter().peekable();
+    while let Some(start) = tags.next() {
+        let mut end = start;
+        while tags.peek() == Some(&(end + 1)) {
+            end = tags.next().expect("peeked");
+        }
+        push(&mut class, start);
+        if end > start + 1 {
+            class.push('-');
+        }
+        if end > start {
+            push(&mut class, end);
+        }
+    }
+    class.push(']');
+    class
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stl::std_stl;
+    use crate::typelib::SymbolicLib;
+    use crate::SystemBuilder;
+
+    fn schema_system() -> SymbolicSys {
+        let src = "typelib Schema
+
+import Std#delete-roman-hair
+  use AlphaCapsLodash#duet-hammer-labor
+  use AlphaNumLodash#percent-bingo-caesar
+
+data Hash : [Byte ^ 32]
+data Kind : alpha | beta#5 | gamma
+data Name : (Std.AlphaCapsLodash, [Std.AlphaNumLodash ^ ..0x1f])
+data Shape : circle U16 | rect (w U16, h U16) | empty ()
+data Item : id U64, big U256, name [Unicode ^ 2..0xff], kind Kind, shape Shape, note Hash?, blob \
+                   [Byte ^ ..0xff], nums {U8 ^ 1..8}, index {Kind -> U32}, pair (I8, F32), owner \
+                   Name, tags {Hash -> ^ ..0xff Name}
+data Counts : {U16 -> ^ ..0xff U8}
+data Scores : {F32 -> ^ 1..0xff U8}
+";
+        let lib = SymbolicLib::from_source(src, [&std_stl()]).unwrap().compile().unwrap();
+        SystemBuilder::new().import(lib).unwrap().import(std_stl()).unwrap().finalize().unwrap()
+    }
+
+    #[test]
+    fn named() {
+        let sys = schema_system();
+        let schema = sys.to_json_schema("Schema.Item").unwrap();
+        assert_eq!(schema["$schema"], json!(JSON_SCHEMA_DRAFT));
+        assert_eq!(schema["$ref"], json!("#/$defs/Schema.Item"));
+        let defs = schema["$defs"].as_object().unwrap();
+        assert_eq!(defs.keys().collect::<Vec<_>>(), [
+            "Schema.Hash",
+            "Schema.Item",
+            "Schema.Kind",
+            "Schema.Name",
+            "Schema.Shape"
+        ]);
+        assert_eq!(
+            defs["Schema.Hash"],
+            json!({
+                "type": "string",
+                "pattern": "^([0-9a-f]{2})*$",
+                "minLength": 64,
+                "maxLength": 64,
+            })
+        );
+        assert_eq!(defs["Schema.Kind"], json!({ "enum": ["alpha", "beta", "gamma"] }));
+        assert_eq!(
+            defs["Schema.Name"],
+            json!({
+                "type": "string",
+                "pattern": "^[A-Z_][0-9A-Z_a-z]*$",
+                "minLength": 1,
+                "maxLength": 32,
+            })
+        );
+        assert_eq!(
+            defs["Schema.Shape"]["oneOf"][0],
+            json!({
+                "type": "object",
+                "properties": { "circle": { "type": "integer", "minimum": 0, "maximum": 0xFFFF } },
+                "required": ["circle"],
+                "additionalProperties": false,
+            })
+        );
+        assert_eq!(defs["Schema.Shape"]["oneOf"][2], json!({ "const": "empty" }));
+
+        let item = &defs["Schema.Item"];
+        assert_eq!(item["required"].as_array().unwrap().len(), 12);
+        assert_eq!(item["additionalProperties"], json!(false));
+        let props = &item["properties"];
+        assert_eq!(props["big"], json!({ "type": "string", "pattern": "^[0-9]+$" }));
+        assert_eq!(props["name"], json!({ "type": "string", "minLength": 1, "maxLength": 0xFF }));
+        assert_eq!(
+            props["note"],
+            json!({ "anyOf": [{ "type": "null" }, { "$ref": "#/$defs/Schema.Hash" }] })
+        );
+        assert_eq!(
+            props["nums"],
+            json!({
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0, "maximum": 0xFF },
+                "uniqueItems": true,
+                "minItems": 1,
+                "maxItems": 8,
+            })
+        );
+        assert_eq!(
+            props["pair"],
+            json!({
+                "type": "array",
+                "prefixItems": [
+                    { "type": "integer", "minimum": -128, "maximum": 127 },
+                    { "type": ["number", "string"] },
+                ],
+                "items": false,
+                "minItems": 2,
+            })
+        );
+        assert_eq!(
+            props["tags"],
+            json!({
+                "type": "object",
+                "propertyNames": { "$ref": "#/$defs/Schema.Hash" },
+                "additionalProperties": { "$ref": "#/$defs/Schema.Name" },
+                "maxProperties": 0xFF,
+            })
+        );
+    }
+
+    #[test]
+    fn maps() {
+        let sys = schema_system();
+        let counts = sys.to_json_schema("Schema.Counts").unwrap();
+        let pairs = json!({
+            "type": "array",
+            "items": {
+                "type": "array",
+                "prefixItems": [
+                    { "type": "integer", "minimum": 0, "maximum": 0xFFFF },
+                    { "type": "integer", "minimum": 0, "maximum": 0xFF },
+                ],
+                "items": false,
+                "minItems": 2,
+            },
+            "maxItems": 0xFF,
+        });
+        assert_eq!(
+            counts["$defs"]["Schema.Counts"],
+            json!({ "anyOf": [pairs, { "type": "object", "maxProperties": 0 }] })
+        );
+
+        let scores = sys.to_json_schema("Schema.Scores").unwrap();
+        let scores = &scores["$defs"]["Schema.Scores"]["anyOf"];
+        assert_eq!(scores[0]["type"], json!("object"));
+        assert_eq!(scores[0]["minProperties"], json!(1));
+        assert_eq!(scores[1]["type"], json!("array"));
+        assert_eq!(scores[1]["minItems"], json!(1));
+    }
+
+    #[test]
+    fn unnamed() {
+        let sys = schema_system();
+        let id = sys.resolve("Schema.Item").copied().unwrap();
+        let Ty::Struct(fields) = sys.as_types().find(id).unwrap() else {
+            panic!("structure type expected")
+        };
+        let schema = sys.to_json_schema(fields[7].ty).unwrap();
+        assert_eq!(
+            schema,
+            json!({
+                "$schema": JSON_SCHEMA_DRAFT,
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0, "maximum": 0xFF },
+                "uniqueItems": true,
+                "minItems": 1,
+                "maxItems": 8,
+            })
+        );
+
+        assert_eq!(
+            sys.to_json_schema("Schema.Absent").unwrap_err(),
+            SchemaError::TypeAbsent(TypeSpec::from("Schema.Absent"))
+        );
+    }
+}
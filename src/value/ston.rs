@@ -20,14 +20,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Strict type object notation (STON): JSON-like text serialization for stric types.
+//! Strict type object notation (STON): JSON-like text serialization for stric types, and its
+//! parser.
 
 use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
-use amplify::hex::ToHex;
+use amplify::hex::{FromHex, ToHex};
+use amplify::num::apfloat::{ieee, Float};
+use amplify::num::{i1024, u1024};
+use encoding::{FieldName, InvalidRString, VariantName};
+use indexmap::IndexMap;
 
-use super::StrictVal;
+use super::typify::{self, TypeSpec, TypedVal};
+use super::{Blob, StrictNum, StrictVal};
 use crate::value::EnumTag;
+use crate::{Pos, Span, SymbolicSys};
 
 impl StrictVal {
     fn needs_parenthesis(&self) -> bool {
@@ -53,7 +61,7 @@ impl Display for StrictVal {
         match self {
             StrictVal::Unit => f.write_str("(,)"),
             StrictVal::Number(n) => Display::fmt(n, f),
-            StrictVal::String(s) => write!(f, r#""{s}""#),
+            StrictVal::String(s) => write!(f, "{s:?}"),
             StrictVal::Bytes(blob) => write!(f, "0x{}", blob.to_hex()),
             StrictVal::Tuple(fields) => {
                 let mut iter = fields.iter();
@@ -96,11 +104,15 @@ impl Display for StrictVal {
                 f.write_str("~")
             }
             StrictVal::Union(tag, content) => {
-                if content.needs_parenthesis() {
+                // Numbers followed by a numeric tag would look like a float
+                let parenthesis = content.needs_parenthesis()
+                    || (matches!(**content, StrictVal::Number(_))
+                        && matches!(tag, EnumTag::Ord(_)));
+                if parenthesis {
                     f.write_str("(")?;
                 }
                 Display::fmt(content, f)?;
-                if content.needs_parenthesis() {
+                if parenthesis {
                     f.write_str(")")?;
                 }
                 f.write_str(".")?;
@@ -144,8 +156,12 @@ impl Display for StrictVal {
             StrictVal::Map(items) => {
                 let mut iter = items.iter();
                 f.write_str("{")?;
-                while let Some((fname, fval)) = iter.next() {
-                    write!(f, "{fname} -> ")?;
+                while let Some((key, fval)) = iter.next() {
+                    if key.needs_parenthesis() {
+                        write!(f, "({key}) -> ")?;
+                    } else {
+                        write!(f, "{key} -> ")?;
+                    }
                     if fval.needs_parenthesis() {
                         f.write_str("(")?;
                     }
@@ -163,9 +179,482 @@ impl Display for StrictVal {
     }
 }
 
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display("{span}: {kind}")]
+pub struct ParseError {
+    pub span: Span,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum ParseErrorKind {
+    /// unexpected character `{0}`.
+    UnexpectedChar(char),
+
+    /// unterminated string literal.
+    UnterminatedString,
+
+    /// invalid escape sequence `{0}` in a string literal.
+    InvalidEscape(String),
+
+    /// invalid hex literal `{0}`.
+    InvalidHex(String),
+
+    /// number `{0}` exceeds 1024-bit range.
+    NumberOverflow(String),
+
+    /// invalid floating-point number `{0}`.
+    InvalidFloat(String),
+
+    /// expected {expected}, found {found}.
+    Unexpected {
+        expected: &'static str,
+        found: String,
+    },
+
+    /// invalid name: {0}
+    #[from]
+    InvalidName(InvalidRString),
+
+    /// field `{0}` is repeated.
+    RepeatedField(FieldName),
+
+    /// named fields can't be mixed with unnamed ones.
+    MixedFields,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(inner)]
+pub enum Error {
+    #[from]
+    Parse(ParseError),
+
+    #[from]
+    Typify(typify::Error),
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Float(String),
+    Bytes(Vec<u8>),
+    String(String),
+    Punct(&'static str),
+    Eof,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(s) | Token::Number(s) | Token::Float(s) => write!(f, "`{s}`"),
+            Token::Bytes(_) => f.write_str("byte string"),
+            Token::String(_) => f.write_str("string"),
+            Token::Punct(p) => write!(f, "`{p}`"),
+            Token::Eof => f.write_str("end of input"),
+        }
+    }
+}
+
+const PUNCTUATION: [&str; 10] = ["->", "~", "(", ")", "[", "]", "{", "}", ",", "."];
+
+fn tokenize(src: &str) -> Result<Vec<(Token, Span)>, ParseError> {
+    let mut tokens = vec![];
+    let mut pos = Pos::start();
+    let bump = |pos: &mut Pos, len: usize| {
+        for c in src[pos.offset..pos.offset + len].chars() {
+            pos.advance(c);
+        }
+    };
+    loop {
+        let rest = &src[pos.offset..];
+        let trimmed = rest.trim_start();
+        bump(&mut pos, rest.len() - trimmed.len());
+        let rest = trimmed;
+        let start = pos;
+        let error = |pos: Pos, kind: ParseErrorKind| ParseError {
+            span: Span::new(start, pos),
+            kind,
+        };
+        let Some(c) = rest.chars().next() else {
+            tokens.push((Token::Eof, Span::new(start, start)));
+            return Ok(tokens);
+        };
+        let token = if c.is_ascii_alphabetic() || c == '_' {
+            let len =
+                rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len());
+            bump(&mut pos, len);
+            Token::Ident(rest[..len].to_owned())
+        } else if let Some(hex) = rest.strip_prefix("0x") {
+            let len = hex.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(hex.len());
+            bump(&mut pos, len + 2);
+            let bytes = Vec::<u8>::from_hex(&hex[..len])
+                .map_err(|_| error(pos, ParseErrorKind::InvalidHex(hex[..len].to_owned())))?;
+            Token::Bytes(bytes)
+        } else if c.is_ascii_digit() {
+            let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let mut len = digits(rest);
+            let mut is_float = false;
+            // Fraction must contain digits, otherwise the dot is a union variant separator
+            if rest[len..].starts_with('.') && digits(&rest[len + 1..]) > 0 {
+                len += 1 + digits(&rest[len + 1..]);
+                is_float = true;
+            }
+            if rest[len..].starts_with(['e', 'E']) {
+                let sign = rest[len + 1..].starts_with(['+', '-']) as usize;
+                let exp = digits(&rest[len + 1 + sign..]);
+                if exp > 0 {
+                    len += 1 + sign + exp;
+                    is_float = true;
+                }
+            }
+            bump(&mut pos, len);
+            match is_float {
+                true => Token::Float(rest[..len].to_owned()),
+                false => Token::Number(rest[..len].to_owned()),
+            }
+        } else if c == '"' {
+            let mut s = String::new();
+            let mut chars = rest.char_indices().skip(1);
+            let len = loop {
+                match chars.next() {
+                    None => {
+                        bump(&mut pos, rest.len());
+                        return Err(error(pos, ParseErrorKind::UnterminatedString));
+                    }
+                    Some((i, '"')) => break i + 1,
+                    Some((i, '\\')) => {
+                        let unescaped = match chars.next().map(|(_, c)| c) {
+                            Some('"') => '"',
+                            Some('\\') => '\\',
+                            Some('\'') => '\'',
+                            Some('n') => '\n',
+                            Some('r') => '\r',
+                            Some('t') => '\t',
+                            Some('0') => '\0',
+                            Some('u') => {
+                                let code = rest[i..]
+                                    .strip_prefix("\\u{")
+                                    .and_then(|s| s.split_once('}'))
+                                    .map(|(code, _)| code);
+                                let c = code
+                                    .and_then(|code| u32::from_str_radix(code, 16).ok())
+                                    .and_then(char::from_u32);
+                                match (code, c) {
+                                    (Some(code), Some(c)) => {
+                                        chars.nth(code.len() + 1);
+                                        c
+                                    }
+                                    _ => {
+                                        bump(&mut pos, i);
+                                        let kind = ParseErrorKind::InvalidEscape(s!("\\u"));
+                                        return Err(error(pos, kind));
+                                    }
+                                }
+                            }
+                            other => {
+                                bump(&mut pos, i);
+                                let seq =
+                                    format!("\\{}", other.map(String::from).unwrap_or_default());
+                                return Err(error(pos, ParseErrorKind::InvalidEscape(seq)));
+                            }
+                        };
+                        s.push(unescaped);
+                    }
+                    Some((_, c)) => s.push(c),
+                }
+            };
+            bump(&mut pos, len);
+            Token::String(s)
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| rest.starts_with(**p)) {
+            bump(&mut pos, punct.len());
+            Token::Punct(punct)
+        } else if c == '-' {
+            bump(&mut pos, 1);
+            Token::Punct("-")
+        } else {
+            bump(&mut pos, c.len_utf8());
+            return Err(error(pos, ParseErrorKind::UnexpectedChar(c)));
+        };
+        tokens.push((token, Span::new(start, pos)));
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, Span)>,
+    cursor: usize,
+}
+
+impl Parser {
+    fn peek_at(&self, offset: usize) -> &Token {
+        let pos = (self.cursor + offset).min(self.tokens.len() - 1);
+        &self.tokens[pos].0
+    }
+
+    fn span(&self) -> Span { self.tokens[self.cursor].1 }
+
+    fn next(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.cursor].clone();
+        if self.cursor < self.tokens.len() - 1 {
+            self.cursor += 1;
+        }
+        token
+    }
+
+    fn is_punct(&self, offset: usize, punct: &str) -> bool {
+        matches!(self.peek_at(offset), Token::Punct(p) if *p == punct)
+    }
+
+    fn error_at(&self, span: Span, kind: impl Into<ParseErrorKind>) -> ParseError {
+        ParseError {
+            span,
+            kind: kind.into(),
+        }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        self.error_at(self.span(), ParseErrorKind::Unexpected {
+            expected,
+            found: self.peek_at(0).to_string(),
+        })
+    }
+
+    fn expect_punct(&mut self, punct: &'static str) -> Result<(), ParseError> {
+        if !self.is_punct(0, punct) {
+            return Err(self.unexpected(punct));
+        }
+        self.next();
+        Ok(())
+    }
+
+    fn is_value_start(&self, offset: usize) -> bool {
+        match self.peek_at(offset) {
+            Token::Ident(_)
+            | Token::Number(_)
+            | Token::Float(_)
+            | Token::Bytes(_)
+            | Token::String(_) => true,
+            Token::Punct(p) => ["~", "(", "[", "{", "-"].contains(p),
+            Token::Eof => false,
+        }
+    }
+
+    /// Parses comma-separated list of values or named fields, which are not surrounded by
+    /// brackets: the top-level value, or a content of parenthesis.
+    fn parse_fields(&mut self) -> Result<StrictVal, ParseError> {
+        let span = self.span();
+        let mut fields = IndexMap::<FieldName, StrictVal>::new();
+        let mut items = vec![];
+        loop {
+            match self.peek_at(0).clone() {
+                Token::Ident(name) if self.is_value_start(1) => {
+                    let (_, span) = self.next();
+                    let name = FieldName::try_from(name).map_err(|err| self.error_at(span, err))?;
+                    if fields.contains_key(&name) {
+                        return Err(self.error_at(span, ParseErrorKind::RepeatedField(name)));
+                    }
+                    fields.insert(name, self.parse_value()?);
+                }
+                _ => items.push(self.parse_value()?),
+            }
+            if !self.is_punct(0, ",") {
+                break;
+            }
+            self.next();
+        }
+        match (fields.is_empty(), items.len()) {
+            (true, 1) => Ok(items.remove(0)),
+            (true, _) => Ok(StrictVal::Tuple(items)),
+            (false, 0) => Ok(StrictVal::Struct(fields)),
+            (false, _) => Err(self.error_at(span, ParseErrorKind::MixedFields)),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<StrictVal, ParseError> {
+        let mut val = self.parse_primary()?;
+        while self.is_punct(0, ".") {
+            self.next();
+            let tag = match self.next() {
+                (Token::Ident(name), span) => EnumTag::Name(
+                    VariantName::try_from(name).map_err(|err| self.error_at(span, err))?,
+                ),
+                (Token::Number(no), span) => EnumTag::Ord(u8::from_str(&no).map_err(|_| {
+                    self.error_at(span, ParseErrorKind::Unexpected {
+                        expected: "variant tag",
+                        found: format!("`{no}`"),
+                    })
+                })?),
+                _ => {
+                    self.cursor -= 1;
+                    return Err(self.unexpected("variant name or tag"));
+                }
+            };
+            val = StrictVal::Union(tag, Box::new(val));
+        }
+        Ok(val)
+    }
+
+    fn parse_items(&mut self, end: &'static str) -> Result<Vec<StrictVal>, ParseError> {
+        let mut items = vec![];
+        while !self.is_punct(0, end) {
+            items.push(self.parse_value()?);
+            if !self.is_punct(0, ",") {
+                break;
+            }
+            self.next();
+        }
+        self.expect_punct(end)?;
+        Ok(items)
+    }
+
+    fn parse_primary(&mut self) -> Result<StrictVal, ParseError> {
+        let (token, span) = self.next();
+        Ok(match token {
+            Token::Punct("~") => StrictVal::none(),
+            Token::Punct("(") if self.is_punct(0, ",") && self.is_punct(1, ")") => {
+                self.cursor += 2;
+                StrictVal::Unit
+            }
+            Token::Punct("(") => {
+                let val = self.parse_fields()?;
+                self.expect_punct(")")?;
+                val
+            }
+            Token::Punct("[") => StrictVal::List(self.parse_items("]")?),
+            Token::Punct("{") if self.is_punct(0, "}") => {
+                self.next();
+                StrictVal::Set(vec![])
+            }
+            Token::Punct("{") => {
+                let first = self.parse_value()?;
+                if !self.is_punct(0, "->") {
+                    let mut items = vec![first];
+                    if self.is_punct(0, ",") {
+                        self.next();
+                        items.extend(self.parse_items("}")?);
+                    } else {
+                        self.expect_punct("}")?;
+                    }
+                    return Ok(StrictVal::Set(items));
+                }
+                let mut key = first;
+                let mut entries = vec![];
+                loop {
+                    self.expect_punct("->")?;
+                    entries.push((key, self.parse_value()?));
+                    if !self.is_punct(0, ",") {
+                        break;
+                    }
+                    self.next();
+                    if self.is_punct(0, "}") {
+                        break;
+                    }
+                    key = self.parse_value()?;
+                }
+                self.expect_punct("}")?;
+                StrictVal::Map(entries)
+            }
+            Token::Punct("-") => match self.next() {
+                (Token::Number(no), end) => {
+                    let span = Span::new(span.start, end.end);
+                    StrictVal::Number(
+                        parse_number(&no, true).map_err(|kind| self.error_at(span, kind))?,
+                    )
+                }
+                (Token::Float(no), end) => {
+                    let span = Span::new(span.start, end.end);
+                    let f = parse_float(&no).map_err(|kind| self.error_at(span, kind))?;
+                    StrictVal::Number(StrictNum::Float(-f))
+                }
+                (Token::Ident(name), _) if name == "inf" => {
+                    StrictVal::Number(StrictNum::Float(-ieee::Oct::INFINITY))
+                }
+                _ => {
+                    self.cursor -= 1;
+                    return Err(self.unexpected("number"));
+                }
+            },
+            Token::Number(no) => StrictVal::Number(
+                parse_number(&no, false).map_err(|kind| self.error_at(span, kind))?,
+            ),
+            Token::Float(no) => StrictVal::Number(StrictNum::Float(
+                parse_float(&no).map_err(|kind| self.error_at(span, kind))?,
+            )),
+            Token::Bytes(bytes) => StrictVal::Bytes(Blob(bytes)),
+            Token::String(s) => StrictVal::String(s),
+            Token::Ident(name) => StrictVal::Enum(EnumTag::Name(
+                VariantName::try_from(name).map_err(|err| self.error_at(span, err))?,
+            )),
+            _ => {
+                self.cursor -= 1;
+                return Err(self.unexpected("value"));
+            }
+        })
+    }
+}
+
+fn parse_number(digits: &str, neg: bool) -> Result<StrictNum, ParseErrorKind> {
+    if let Ok(n) = u64::from_str(digits) {
+        return Ok(match neg {
+            false => StrictNum::Uint(n),
+            true if n <= i64::MAX as u64 + 1 => StrictNum::Int((n as i64).wrapping_neg()),
+            true => StrictNum::BigInt(-i1024::from(n)),
+        });
+    }
+    let overflow =
+        || ParseErrorKind::NumberOverflow(format!("{}{digits}", if neg { "-" } else { "" }));
+    let mut n = u1024::ZERO;
+    for digit in digits.bytes() {
+        n = n.checked_mul(10u8).and_then(|n| n.checked_add(digit - b'0')).ok_or_else(overflow)?;
+    }
+    if !neg {
+        return Ok(StrictNum::BigUint(n));
+    }
+    let n = i1024::from_le_bytes(n.to_le_bytes()).wrapping_neg();
+    if !n.is_negative() {
+        return Err(overflow());
+    }
+    Ok(StrictNum::BigInt(n))
+}
+
+fn parse_float(s: &str) -> Result<ieee::Oct, ParseErrorKind> {
+    ieee::Oct::from_str(s).map_err(|_| ParseErrorKind::InvalidFloat(s.to_owned()))
+}
+
+impl FromStr for StrictVal {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            cursor: 0,
+        };
+        let val = parser.parse_fields()?;
+        if parser.peek_at(0) != &Token::Eof {
+            return Err(parser.unexpected("end of input"));
+        }
+        Ok(val)
+    }
+}
+
+impl SymbolicSys {
+    /// Parses STON representation of a value and checks it against the type specification.
+    pub fn parse_ston(&self, s: &str, spec: impl Into<TypeSpec>) -> Result<TypedVal, Error> {
+        let val = StrictVal::from_str(s)?;
+        Ok(self.typify(val, spec)?)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    //use super::*;
+    use std::str::FromStr;
+
+    use amplify::num::apfloat::{ieee, Float};
+    use amplify::num::{i1024, u1024};
+
+    use super::super::test_helpers::*;
+    use super::*;
 
     #[test]
     fn simple() {
@@ -197,6 +686,95 @@ mod test {
         )
     }
 
+    #[test]
+    fn parse_roundtrip() {
+        let values = [
+            sv!(10u8),
+            sv!(-10i16),
+            StrictVal::num(u1024::from(u128::MAX) * u1024::from(u64::MAX)),
+            StrictVal::num(-i1024::from(i128::MAX)),
+            sv!("string with \"quotes\", \\ and\nnewlines\u{1}\u{263a}"),
+            svbytes!([0xDEu8, 0xAD, 0xBE, 0xEF]),
+            svbytes!([]),
+            StrictVal::Unit,
+            svenum!(tag),
+            svnone!(),
+            svsome!(svsome!("value")),
+            StrictVal::union(3, ston!(a 1u8, b "text")),
+            StrictVal::union("unit", ()),
+            svlist!([sv!(1u8), sv!(2u8)]),
+            svlist!(Vec::<StrictVal>::new()),
+            svset!([ston!(svenum!(a), svenum!(b)), ston!(svenum!(c), svenum!(d))]),
+            StrictVal::map([(sv!("key"), svsome!(5u8)), (ston!(x 1u8, y 2u8), svnone!())]),
+            ston!(name "Some name", ticker "TICK", precision svenum!(eight), data svlist!([0u8, 1]), tuple ston!(a 15u8, b "text")),
+            ston!(sv!(1u8), ston!(sv!(2u8), sv!(3u8)), svlist!([ston!(a svnone!())])),
+            sv!(1.5f64),
+            sv!(-2.0f32),
+            sv!(-0.0f64),
+            sv!(1024.25f64),
+            sv!(-f64::INFINITY),
+            StrictVal::union(0, 2u8),
+            StrictVal::union(1, 0.5f32),
+        ];
+        for val in values {
+            let s = val.to_string();
+            assert_eq!(StrictVal::from_str(&s).unwrap(), val, "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_floats() {
+        assert_eq!(StrictVal::from_str("1e3").unwrap(), sv!(1000.0f64));
+        assert_eq!(StrictVal::from_str("-2.5E-1").unwrap(), sv!(-0.25f64));
+        assert_eq!(StrictVal::from_str("0.5.1").unwrap(), StrictVal::union(1, 0.5f64));
+        assert_eq!(StrictVal::from_str("1.some").unwrap(), svsome!(1u8));
+        assert_eq!(StrictVal::from_str("nan").unwrap(), svenum!(nan));
+        let val = StrictVal::from_str("0.1").unwrap();
+        let StrictVal::Number(StrictNum::Float(f)) = val else {
+            panic!("float expected")
+        };
+        assert!(f.is_finite() && f > ieee::Oct::ZERO);
+        // Floats are printed in the form which is parsed back into exactly the same number
+        let exact = "0.1000000000000000055511151231257827021181583404541015625";
+        assert_eq!(sv!(0.1f64).to_string(), exact);
+        assert_eq!(StrictVal::from_str(exact).unwrap(), sv!(0.1f64));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = StrictVal::from_str("name \"text\",\n  data 0xabc").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidHex(s!("abc")));
+        assert_eq!((err.span.start.line, err.span.start.col), (2, 8));
+        assert_eq!(err.to_string(), "2:8: invalid hex literal `abc`.");
+
+        let err = StrictVal::from_str("[1, 2,\n 3 4]").unwrap_err();
+        assert_eq!((err.span.start.line, err.span.start.col), (2, 4));
+        assert_eq!(err.to_string(), "2:4: expected ], found `4`.");
+
+        let err = StrictVal::from_str("a 1, 2").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::MixedFields);
+        let err = StrictVal::from_str("a 1, a 2").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::RepeatedField(fname!("a")));
+        let err = StrictVal::from_str(r#""unterminated"#).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnterminatedString);
+    }
+
+    #[test]
+    fn parse_typed() {
+        let sys = test_system();
+        let typed = sys
+            .parse_ston(r#"ticker "TICK", name "Some name", precision 2"#, "TestLib.Nominal")
+            .unwrap();
+        assert_eq!(
+            typed.as_val(),
+            &ston!(ticker svnewtype!("TICK"), name "Some name", precision svenum!(twoDecimals))
+        );
+        let err = sys
+            .parse_ston(r#"ticker "TICK", name "Some name", precision 5"#, "TestLib.Nominal")
+            .unwrap_err();
+        assert!(matches!(err, Error::Typify(typify::Error::EnumTagInvalid(..))));
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn serde() {
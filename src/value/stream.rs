@@ -0,0 +1,915 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Incremental decoding of strict values from the data arriving by chunks.
+//!
+//! [`EventDecoder`] is a push-style state machine: the data are provided with
+//! [`EventDecoder::push`] as they arrive, and the decoder reports SAX-style [`Event`]s for each
+//! nested value, or [`Next::NeedMoreData`] with the number of bytes it waits for. Decoded values
+//! are not kept in memory, such that the decoder can process collections of any size; because of
+//! this, the order of set elements and map keys is not verified in this mode.
+//!
+//! [`StreamDecoder`] assembles the events into a [`TypedVal`], performing the same checks as
+//! [`TypeSystem::strict_deserialize_type`].
+
+use std::cmp::Ordering;
+use std::io;
+use std::mem::size_of;
+
+use amplify::ascii::AsciiString;
+use amplify::confinement::U32 as MAX32;
+use encoding::{DecodeError, FieldName, Sizing, StreamReader, VariantName};
+use indexmap::IndexMap;
+
+use super::decode::{check_len, Budget, DecodeLimits, Error, ErrorKind, READ_CHUNK};
+use super::typify::{TypeSpec, TypedVal};
+use super::{Blob, KeyStep, Path, Step};
+use crate::ast::{NamedFields, UnnamedFields};
+use crate::typesys::{SymbolicSys, TypeSymbol};
+use crate::util::SizingExt;
+use crate::{SemId, StrictVal, Ty, TypeRef, TypeSystem};
+
+/// Kind of a composite value entered by the decoder.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Compound {
+    Tuple,
+    Struct,
+    /// Union variant with the given name.
+    Union(VariantName),
+    /// Fixed-size array or a list with the given number of items.
+    List(u64),
+    /// Set with the given number of elements.
+    Set(u64),
+    /// Map with the given number of entries.
+    Map(u64),
+}
+
+/// Event reported by the [`EventDecoder`].
+///
+/// Nested values are identified by the same steps which are used in value paths: fields by
+/// their number or name, collection items and map keys by their index, and map values by their
+/// key, if the key can be used as a path step, or by the index otherwise. Union variant content
+/// and the decoded value itself have no step.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Event {
+    /// Decoder has entered a composite value of the given type.
+    Enter {
+        step: Option<Step>,
+        sem_id: SemId,
+        compound: Compound,
+    },
+    /// Decoder has read a value which has no nested values: a primitive, a character, an enum
+    /// or a byte or character string.
+    Leaf {
+        step: Option<Step>,
+        sem_id: SemId,
+        val: StrictVal,
+    },
+    /// Decoder has read all nested values of the most recently entered composite value.
+    Leave,
+}
+
+/// Result of an [`EventDecoder`] step.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Next {
+    Event(Event),
+    /// Decoder needs at least the given number of bytes to proceed.
+    NeedMoreData(usize),
+    /// The value is completely decoded.
+    Done,
+}
+
+/// Result of a [`StreamDecoder`] run.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Status {
+    /// Decoder needs at least the given number of bytes to proceed.
+    NeedMoreData(usize),
+    /// The value is completely decoded.
+    Complete(TypedVal),
+}
+
+impl SymbolicSys {
+    pub fn event_decoder(&self, spec: impl Into<TypeSpec>) -> Result<EventDecoder<'_>, Error> {
+        let sem_id = self.resolve_spec(spec).map_err(|err| err.report(ErrorKind::TypeAbsent))?;
+        Ok(EventDecoder::new(self.as_types(), Some(self), sem_id))
+    }
+
+    pub fn stream_decoder(&self, spec: impl Into<TypeSpec>) -> Result<StreamDecoder<'_>, Error> {
+        self.event_decoder(spec).map(StreamDecoder::with)
+    }
+}
+
+impl TypeSystem {
+    pub fn event_decoder(&self, sem_id: SemId) -> EventDecoder<'_> {
+        EventDecoder::new(self, None, sem_id)
+    }
+
+    pub fn stream_decoder(&self, sem_id: SemId) -> StreamDecoder<'_> {
+        StreamDecoder::with(self.event_decoder(sem_id))
+    }
+}
+
+/// Composite value entered by the [`EventDecoder`].
+struct Frame<'a> {
+    sem_id: SemId,
+    content: Content<'a>,
+    /// Number of nested values which were already entered.
+    next: u64,
+    /// Whether the step to the value was added to the path.
+    pushed: bool,
+}
+
+enum Content<'a> {
+    Tuple(&'a UnnamedFields<SemId>),
+    Struct(&'a NamedFields<SemId>),
+    Union(SemId),
+    Items(SemId, u64),
+    Map {
+        key_ty: SemId,
+        ty: SemId,
+        len: u64,
+        key: Option<KeyStep>,
+    },
+}
+
+impl Frame<'_> {
+    /// Returns the step and the type of the next nested value, if there is one.
+    fn child(&self) -> Option<(Option<Step>, SemId)> {
+        let next = self.next;
+        Some(match &self.content {
+            Content::Tuple(fields) => {
+                (Some(Step::UnnamedField(next as u8)), *fields.get(next as usize)?)
+            }
+            Content::Struct(fields) => {
+                let field = fields.get(next as usize)?;
+                (Some(Step::NamedField(field.name.clone())), field.ty)
+            }
+            Content::Union(ty) if next == 0 => (None, *ty),
+            Content::Union(_) => return None,
+            Content::Items(ty, len) if next < *len => (Some(Step::Index(next as u32)), *ty),
+            Content::Items(..) => return None,
+            Content::Map { len, .. } if next / 2 >= *len => return None,
+            Content::Map { key_ty, .. } if next % 2 == 0 => {
+                (Some(Step::Index((next / 2) as u32)), *key_ty)
+            }
+            Content::Map { ty, key, .. } => {
+                let step = key.clone().map(Step::Key).unwrap_or(Step::Index((next / 2) as u32));
+                (Some(step), *ty)
+            }
+        })
+    }
+}
+
+/// Value which starts at the current position of the decoder.
+enum Open<'a> {
+    Need(usize),
+    Leaf(StrictVal, usize),
+    Enter(Compound, Content<'a>, usize),
+}
+
+/// Push-style decoder reporting [`Event`]s for the values of a type.
+pub struct EventDecoder<'a> {
+    types: &'a TypeSystem,
+    symbols: Option<&'a SymbolicSys>,
+    sem_id: SemId,
+    budget: Budget,
+    /// Received data; the bytes before `pos` are already consumed.
+    buf: Vec<u8>,
+    pos: usize,
+    /// Offset of the first byte of `buf`, counting from the start of the data.
+    base: u64,
+    frames: Vec<Frame<'a>>,
+    path: Path,
+    started: bool,
+}
+
+impl<'a> EventDecoder<'a> {
+    fn new(types: &'a TypeSystem, symbols: Option<&'a SymbolicSys>, sem_id: SemId) -> Self {
+        EventDecoder {
+            types,
+            symbols,
+            sem_id,
+            budget: Budget::new(DecodeLimits::default()),
+            buf: vec![],
+            pos: 0,
+            base: 0,
+            frames: vec![],
+            path: Path::new(),
+            started: false,
+        }
+    }
+
+    /// Replaces the default [`DecodeLimits`] of the decoder. Only the depth and the collection
+    /// length limits apply to events, since they do not hold the decoded values.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.budget = Budget::new(limits);
+        self
+    }
+
+    /// Provides more data to the decoder.
+    pub fn push(&mut self, data: impl AsRef<[u8]>) {
+        self.buf.drain(..self.pos);
+        self.base += self.pos as u64;
+        self.pos = 0;
+        self.buf.extend_from_slice(data.as_ref());
+    }
+
+    /// Data which were provided to the decoder but are not consumed yet. Once the value is
+    /// decoded, these are the data following the value.
+    pub fn rest(&self) -> &[u8] { &self.buf[self.pos..] }
+
+    /// Offset of the next byte to be consumed, counting from the start of the data.
+    pub fn offset(&self) -> u64 { self.base + self.pos as u64 }
+
+    /// Path to the most recently entered composite value.
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Detects whether the value is completely decoded.
+    pub fn is_done(&self) -> bool { self.started && self.frames.is_empty() }
+
+    /// Prepares the decoder to decode the next value of the same type from the rest of the data.
+    pub fn reset(&mut self) {
+        self.frames.clear();
+        self.path = Path::new();
+        self.started = false;
+    }
+
+    /// Decodes the data until the next event.
+    pub fn next_event(&mut self) -> Result<Next, Error> {
+        let (step, sem_id) = if !self.started {
+            (None, self.sem_id)
+        } else {
+            let Some(frame) = self.frames.last() else {
+                return Ok(Next::Done);
+            };
+            match frame.child() {
+                Some(child) => child,
+                None => return Ok(Next::Event(self.leave())),
+            }
+        };
+
+        let start = self.offset();
+        let open = self
+            .open(sem_id)
+            .map_err(|kind| self.error_at(kind, start, step.clone(), Some(sem_id)))?;
+        let len = match open {
+            Open::Need(len) => return Ok(Next::NeedMoreData(len)),
+            Open::Leaf(_, len) | Open::Enter(_, _, len) => len,
+        };
+        self.budget
+            .enter()
+            .map_err(|kind| self.error_at(kind, start, step.clone(), Some(sem_id)))?;
+        self.pos += len;
+        self.started = true;
+        if let Some(frame) = self.frames.last_mut() {
+            if let (Content::Map { key, .. }, true) = (&mut frame.content, frame.next % 2 == 0) {
+                *key = match &open {
+                    Open::Leaf(val, _) => KeyStep::with_val(val),
+                    _ => None,
+                };
+            }
+            frame.next += 1;
+        }
+
+        Ok(Next::Event(match open {
+            Open::Need(_) => unreachable!("returned above"),
+            Open::Leaf(val, _) => {
+                self.budget.leave();
+                Event::Leaf { step, sem_id, val }
+            }
+            Open::Enter(compound, content, _) => {
+                // Paths are limited in their length; deeper values are reported with the
+                // path truncated
+                let pushed = match &step {
+                    Some(step) => self.path.push(step.clone()).is_ok(),
+                    None => false,
+                };
+                self.frames.push(Frame {
+                    sem_id,
+                    content,
+                    next: 0,
+                    pushed,
+                });
+                Event::Enter {
+                    step,
+                    sem_id,
+                    compound,
+                }
+            }
+        }))
+    }
+
+    fn leave(&mut self) -> Event {
+        self.budget.leave();
+        if self.frames.pop().is_some_and(|frame| frame.pushed) {
+            self.path.pop();
+        }
+        Event::Leave
+    }
+
+    /// Constructs an error in the value at the given step from the most recently entered value.
+    /// If the value type is given, it is used to name the type of the failed value.
+    fn error_at(
+        &self,
+        kind: ErrorKind,
+        offset: u64,
+        step: Option<Step>,
+        sem_id: Option<SemId>,
+    ) -> Error {
+        let mut path = self.path.clone();
+        if let Some(step) = step {
+            let _ = path.push(step);
+        }
+        let stack = self.frames.iter().map(|frame| frame.sem_id).chain(sem_id);
+        let fqn = self.symbols.and_then(|sys| stack.rev().find_map(|id| sys.lookup(id)));
+        Error {
+            kind,
+            offset,
+            path,
+            fqn: fqn.cloned(),
+            partial: None,
+        }
+    }
+
+    /// Reads the beginning of the value of the given type at the current position, without
+    /// consuming the data.
+    fn open(&self, sem_id: SemId) -> Result<Open<'a>, ErrorKind> {
+        let types = self.types;
+        let spec = TypeSpec::from(sem_id);
+        let ty = types.get(sem_id).ok_or_else(|| ErrorKind::TypeAbsent(spec.clone()))?;
+        let data = self.rest();
+
+        Ok(match ty {
+            Ty::Primitive(prim) => {
+                let len = prim.byte_size() as usize;
+                let Some(bytes) = data.get(..len) else {
+                    return Ok(Open::Need(len - data.len()));
+                };
+                let mut cursor = StreamReader::cursor::<MAX32>(bytes);
+                Open::Leaf(TypeSystem::strict_read_primitive(*prim, &mut cursor)?, len)
+            }
+            Ty::UnicodeChar => {
+                let Some(first) = data.first() else {
+                    return Ok(Open::Need(1));
+                };
+                // Invalid leading bytes are reported by the UTF-8 check below
+                let len = match first.leading_ones() {
+                    2..=4 => first.leading_ones() as usize,
+                    _ => 1,
+                };
+                let Some(bytes) = data.get(..len) else {
+                    return Ok(Open::Need(len - data.len()));
+                };
+                let s = String::from_utf8(bytes.to_vec()).map_err(DecodeError::from)?;
+                Open::Leaf(StrictVal::String(s), len)
+            }
+
+            // ASCII strings:
+            Ty::List(id, sizing)
+                if types
+                    .get(*id)
+                    .ok_or_else(|| ErrorKind::TypeAbsent(spec.clone()))?
+                    .is_char_enum() =>
+            {
+                self.open_data(*sizing, data, |bytes| {
+                    let s = AsciiString::from_ascii(bytes).map_err(|err| err.ascii_error())?;
+                    Ok(StrictVal::String(s.to_string()))
+                })?
+            }
+            // Restricted strings:
+            Ty::Tuple(fields) if types.is_rstring(fields)? => {
+                let (_, sizing) = types.rstring_sizing(fields)?.expect("checked in match");
+                self.open_data(sizing, data, |bytes| {
+                    let s = AsciiString::from_ascii(bytes).map_err(|err| err.ascii_error())?;
+                    Ok(StrictVal::String(s.to_string()))
+                })?
+            }
+
+            Ty::Enum(variants) => {
+                let Some(tag) = data.first() else {
+                    return Ok(Open::Need(1));
+                };
+                let Some(name) = variants.name_by_tag(*tag) else {
+                    return Err(DecodeError::EnumTagNotKnown(spec.to_string(), *tag).into());
+                };
+                Open::Leaf(StrictVal::enumer(name.clone()), 1)
+            }
+            Ty::Union(variants) => {
+                let Some(tag) = data.first() else {
+                    return Ok(Open::Need(1));
+                };
+                let Some((variant, ty)) = variants.by_tag(*tag) else {
+                    return Err(DecodeError::EnumTagNotKnown(spec.to_string(), *tag).into());
+                };
+                Open::Enter(Compound::Union(variant.name.clone()), Content::Union(*ty), 1)
+            }
+            Ty::Tuple(fields) => Open::Enter(Compound::Tuple, Content::Tuple(fields), 0),
+            Ty::Struct(fields) => Open::Enter(Compound::Struct, Content::Struct(fields), 0),
+
+            // Fixed-size arrays:
+            Ty::Array(ty, len) if ty.is_byte() => {
+                let len = *len as usize;
+                let Some(bytes) = data.get(..len) else {
+                    return Ok(Open::Need(len - data.len()));
+                };
+                Open::Leaf(StrictVal::Bytes(Blob(bytes.to_vec())), len)
+            }
+            Ty::Array(ty, len) => {
+                let len = *len as u64;
+                Open::Enter(Compound::List(len), Content::Items(*ty, len), 0)
+            }
+
+            // Byte strings:
+            Ty::List(ty, sizing) if ty.is_byte() && sizing.max <= u32::MAX as u64 => {
+                self.open_data(*sizing, data, |bytes| Ok(StrictVal::Bytes(Blob(bytes))))?
+            }
+
+            // Unicode strings:
+            Ty::List(ty, sizing) if ty.is_unicode_char() && sizing.max <= u32::MAX as u64 => {
+                self.open_data(*sizing, data, |bytes| {
+                    Ok(StrictVal::String(String::from_utf8(bytes)?))
+                })?
+            }
+
+            // Other collections:
+            Ty::List(ty, sizing) => self
+                .open_items(*sizing, data, |len| (Compound::List(len), Content::Items(*ty, len)))?,
+            Ty::Set(ty, sizing) => self
+                .open_items(*sizing, data, |len| (Compound::Set(len), Content::Items(*ty, len)))?,
+            Ty::Map(key_ty, ty, sizing) => self.open_items(*sizing, data, |len| {
+                (Compound::Map(len), Content::Map {
+                    key_ty: *key_ty,
+                    ty: *ty,
+                    len,
+                    key: None,
+                })
+            })?,
+        })
+    }
+
+    fn open_len(
+        &self,
+        sizing: Sizing,
+        data: &[u8],
+    ) -> Result<Result<(u64, usize), usize>, ErrorKind> {
+        let width = sizing.byte_size();
+        let Some(bytes) = data.get(..width) else {
+            return Ok(Err(width - data.len()));
+        };
+        let len = bytes.iter().rev().fold(0u64, |len, byte| (len << 8) | *byte as u64);
+        check_len(len, sizing)?;
+        self.budget.len(len)?;
+        Ok(Ok((len, width)))
+    }
+
+    fn open_data(
+        &self,
+        sizing: Sizing,
+        data: &[u8],
+        convert: impl FnOnce(Vec<u8>) -> Result<StrictVal, DecodeError>,
+    ) -> Result<Open<'a>, ErrorKind> {
+        let (len, width) = match self.open_len(sizing, data)? {
+            Ok(len) => len,
+            Err(need) => return Ok(Open::Need(need)),
+        };
+        let end = width as u64 + len;
+        if (data.len() as u64) < end {
+            return Ok(Open::Need(usize::try_from(end - data.len() as u64).unwrap_or(usize::MAX)));
+        }
+        let val = convert(data[width..end as usize].to_vec())?;
+        Ok(Open::Leaf(val, end as usize))
+    }
+
+    fn open_items(
+        &self,
+        sizing: Sizing,
+        data: &[u8],
+        content: impl FnOnce(u64) -> (Compound, Content<'a>),
+    ) -> Result<Open<'a>, ErrorKind> {
+        Ok(match self.open_len(sizing, data)? {
+            Ok((len, width)) => {
+                let (compound, content) = content(len);
+                Open::Enter(compound, content, width)
+            }
+            Err(need) => Open::Need(need),
+        })
+    }
+}
+
+/// Composite value assembled by the [`StreamDecoder`].
+struct Partial {
+    step: Option<Step>,
+    offset: u64,
+    sem_id: SemId,
+    compound: Compound,
+    names: Vec<FieldName>,
+    vals: Vec<StrictVal>,
+}
+
+impl Partial {
+    fn finish(self) -> StrictVal {
+        let mut vals = self.vals;
+        match self.compound {
+            Compound::Tuple => StrictVal::Tuple(vals),
+            Compound::Struct => {
+                StrictVal::Struct(self.names.into_iter().zip(vals).collect::<IndexMap<_, _>>())
+            }
+            Compound::Union(name) => StrictVal::union(name, vals.pop().unwrap_or(StrictVal::Unit)),
+            Compound::List(_) => StrictVal::List(vals),
+            Compound::Set(_) => StrictVal::Set(vals),
+            Compound::Map(_) => {
+                let mut iter = vals.into_iter();
+                let mut map = vec![];
+                while let Some((key, val)) = iter.next().zip(iter.next()) {
+                    map.push((key, val));
+                }
+                StrictVal::Map(map)
+            }
+        }
+    }
+}
+
+/// Push-style decoder assembling values of a type from the data arriving by chunks.
+pub struct StreamDecoder<'a> {
+    events: EventDecoder<'a>,
+    limits: DecodeLimits,
+    budget: Budget,
+    stack: Vec<Partial>,
+}
+
+impl<'a> StreamDecoder<'a> {
+    fn with(events: EventDecoder<'a>) -> Self {
+        StreamDecoder {
+            events,
+            limits: DecodeLimits::default(),
+            budget: Budget::new(DecodeLimits::default()),
+            stack: vec![],
+        }
+    }
+
+    /// Replaces the default [`DecodeLimits`] of the decoder.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.events = self.events.with_limits(limits);
+        self.limits = limits;
+        self.budget = Budget::new(limits);
+        self
+    }
+
+    /// Provides more data to the decoder.
+    pub fn push(&mut self, data: impl AsRef<[u8]>) { self.events.push(data) }
+
+    /// Data which were provided to the decoder but are not consumed yet.
+    pub fn rest(&self) -> &[u8] { self.events.rest() }
+
+    /// Offset of the next byte to be consumed, counting from the start of the data.
+    pub fn offset(&self) -> u64 { self.events.offset() }
+
+    /// Decodes the data provided so far.
+    ///
+    /// Once a value is complete, it is returned and the decoder is ready to decode the next value
+    /// of the same type from the rest of the data. In case of an error, the error contains the
+    /// value decoded so far, and the decoder must not be used anymore.
+    pub fn decode(&mut self) -> Result<Status, Error> { self.run().map_err(|err| self.fail(err)) }
+
+    fn run(&mut self) -> Result<Status, Error> {
+        loop {
+            let start = self.events.offset();
+            let event = match self.events.next_event()? {
+                Next::Event(event) => event,
+                Next::NeedMoreData(len) => return Ok(Status::NeedMoreData(len)),
+                Next::Done => unreachable!("decoder is reset once the value is complete"),
+            };
+            let (step, val, start) = match event {
+                Event::Enter {
+                    step,
+                    sem_id,
+                    compound,
+                } => {
+                    let items = match compound {
+                        Compound::List(len) | Compound::Set(len) => {
+                            Some((len, size_of::<StrictVal>()))
+                        }
+                        Compound::Map(len) => Some((len, size_of::<(StrictVal, StrictVal)>())),
+                        _ => None,
+                    };
+                    if let Some((len, size)) = items {
+                        self.budget.items(len, size).map_err(|kind| self.error(kind, start))?;
+                    }
+                    self.stack.push(Partial {
+                        step,
+                        offset: start,
+                        sem_id,
+                        compound,
+                        names: vec![],
+                        vals: vec![],
+                    });
+                    continue;
+                }
+                Event::Leaf { step, sem_id, val } => {
+                    // Strings are accounted in the same way as by the non-incremental decoder
+                    let len = match (&val, self.types().get(sem_id)) {
+                        (_, Some(Ty::UnicodeChar)) => 0,
+                        (StrictVal::String(s), _) => s.len(),
+                        (StrictVal::Bytes(blob), _) => blob.len(),
+                        _ => 0,
+                    };
+                    self.budget.alloc(len as u64).map_err(|kind| {
+                        self.events.error_at(kind, start, step.clone(), Some(sem_id))
+                    })?;
+                    (step, val, start)
+                }
+                Event::Leave => {
+                    let partial = self.stack.pop().expect("unbalanced events");
+                    let (step, offset) = (partial.step.clone(), partial.offset);
+                    (step, partial.finish(), offset)
+                }
+            };
+
+            let types = self.types();
+            let Some(parent) = self.stack.last_mut() else {
+                let val = TypedVal {
+                    val,
+                    orig: TypeSymbol::unnamed(self.events.sem_id),
+                };
+                self.events.reset();
+                self.budget = Budget::new(self.limits);
+                return Ok(Status::Complete(val));
+            };
+            let broken = match (&parent.compound, types.get(parent.sem_id)) {
+                (Compound::Set(_), Some(Ty::Set(ty, _))) => match parent.vals.last() {
+                    Some(last) => match types.cmp_vals(last, &val, *ty) {
+                        Ordering::Less => None,
+                        Ordering::Equal => Some(DecodeError::RepeatedSetValue),
+                        Ordering::Greater => Some(DecodeError::BrokenSetOrder),
+                    },
+                    None => None,
+                },
+                (Compound::Map(_), Some(Ty::Map(ty, ..))) if parent.vals.len() % 2 == 0 => {
+                    match parent.vals.len().checked_sub(2).map(|pos| &parent.vals[pos]) {
+                        Some(last) => match types.cmp_vals(last, &val, *ty) {
+                            Ordering::Less => None,
+                            Ordering::Equal => Some(DecodeError::RepeatedMapValue),
+                            Ordering::Greater => Some(DecodeError::BrokenMapOrder),
+                        },
+                        None => None,
+                    }
+                }
+                _ => None,
+            };
+            if let Some(Step::NamedField(name)) = &step {
+                parent.names.push(name.clone());
+            }
+            parent.vals.push(val);
+            if let Some(kind) = broken {
+                return Err(self.events.error_at(kind.into(), start, step, None));
+            }
+        }
+    }
+
+    /// Assembles the value decoded so far into the error.
+    fn fail(&mut self, mut err: Error) -> Error {
+        let mut nested = None::<(Option<Step>, StrictVal)>;
+        while let Some(mut partial) = self.stack.pop() {
+            if let Some((step, val)) = nested {
+                if let Some(Step::NamedField(name)) = step {
+                    partial.names.push(name);
+                }
+                partial.vals.push(val);
+            }
+            nested = Some((partial.step.clone(), partial.finish()));
+        }
+        err.partial = nested.map(|(_, val)| val);
+        err
+    }
+
+    /// Reads a value from the reader. The reader is not read past the end of the value.
+    pub fn read_from(&mut self, reader: &mut impl io::Read) -> Result<TypedVal, Error> {
+        let mut buf = vec![];
+        loop {
+            match self.decode()? {
+                Status::Complete(val) => return Ok(val),
+                Status::NeedMoreData(len) => {
+                    buf.resize(len.min(READ_CHUNK), 0);
+                    let offset = self.events.offset() + self.rest().len() as u64;
+                    reader
+                        .read_exact(&mut buf)
+                        .map_err(|err| self.error(DecodeError::from(err).into(), offset))?;
+                    self.push(&buf);
+                }
+            }
+        }
+    }
+
+    fn types(&self) -> &'a TypeSystem { self.events.types }
+
+    fn error(&self, kind: ErrorKind, offset: u64) -> Error {
+        self.events.error_at(kind, offset, None, None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use encoding::StrictSerialize;
+
+    use super::super::test_helpers::*;
+    use super::*;
+    use crate::stl::strict_types_stl;
+
+    fn test_lib() -> SymbolicSys {
+        events_system("data Ids : {U8 ^ ..0xff}\ndata Name : [Unicode ^ 1..0x10]\n")
+    }
+
+    #[test]
+    fn chunks() {
+        let sys = test_system();
+        let mut data =
+            strict_types_stl().to_strict_serialized::<{ usize::MAX }>().unwrap().release();
+        let expected = sys.strict_deserialize_type("StrictTypes.TypeLib", &data).unwrap();
+
+        let mut decoder = sys.stream_decoder("StrictTypes.TypeLib").unwrap();
+        for byte in &data {
+            assert!(matches!(decoder.decode().unwrap(), Status::NeedMoreData(len) if len > 0));
+            decoder.push([*byte]);
+        }
+        assert_eq!(decoder.decode().unwrap(), Status::Complete(expected.clone()));
+        assert_eq!(decoder.offset(), data.len() as u64);
+        assert!(decoder.rest().is_empty());
+
+        let len = data.len();
+        data.extend([0xDE, 0xAD]);
+        let mut reader = io::Cursor::new(&data);
+        let mut decoder = sys.stream_decoder("StrictTypes.TypeLib").unwrap();
+        assert_eq!(decoder.read_from(&mut reader).unwrap(), expected);
+        assert_eq!(reader.position(), len as u64);
+    }
+
+    #[test]
+    fn hints() {
+        let sys = test_lib();
+        let mut decoder = sys.stream_decoder("Events.Index").unwrap();
+        assert_eq!(decoder.decode().unwrap(), Status::NeedMoreData(1));
+        decoder.push([0x01, 0x05, 0x00]);
+        assert_eq!(decoder.decode().unwrap(), Status::NeedMoreData(1));
+        decoder.push([0x01, 0x2A, 0x00]);
+        assert_eq!(decoder.decode().unwrap(), Status::NeedMoreData(6));
+        decoder.push([0x00; 6]);
+        assert_eq!(decoder.decode().unwrap(), Status::NeedMoreData(1));
+        decoder.push([0x07, 0x00, 0xFF]);
+        let Status::Complete(val) = decoder.decode().unwrap() else {
+            panic!("value is not complete")
+        };
+        let settled = StrictVal::union(
+            vname!("settled"),
+            StrictVal::Struct(
+                [
+                    (fname!("at"), StrictVal::num(42u64)),
+                    (fname!("kind"), StrictVal::enumer(vname!("custom"))),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        );
+        assert_eq!(
+            val.val,
+            StrictVal::tuple([StrictVal::Map(vec![(StrictVal::num(5u16), settled)])])
+        );
+        assert_eq!(decoder.rest(), [0x00, 0xFF]);
+        assert_eq!(decoder.offset(), 13);
+
+        // The next value is decoded from the rest of the data
+        let Status::Complete(val) = decoder.decode().unwrap() else {
+            panic!("value is not complete")
+        };
+        assert_eq!(val.val, StrictVal::tuple([StrictVal::Map(vec![])]));
+        assert_eq!(decoder.rest(), [0xFF]);
+    }
+
+    #[test]
+    fn events() {
+        let sys = test_lib();
+        let data = [
+            0x02, 0x01, 0x00, 0x00, 0x03, 0x00, 0x04, 0x00, 0x00, 0x05, 0x00, 0x01, 0x2A, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07,
+        ];
+        let index = sys.to_sem_id("Events.Index").unwrap();
+        let event = sys.to_sem_id("Events.Event").unwrap();
+        let kind = sys.to_sem_id("Events.Kind").unwrap();
+
+        let mut decoder = sys.event_decoder("Events.Index").unwrap();
+        decoder.push(data);
+        let mut events = vec![];
+        loop {
+            match decoder.next_event().unwrap() {
+                Next::Event(event) => events.push(event),
+                Next::Done => break,
+                Next::NeedMoreData(len) => panic!("decoder requires {len} more bytes"),
+            }
+        }
+        assert!(decoder.is_done());
+        assert!(decoder.rest().is_empty());
+
+        let Some(Event::Enter {
+            sem_id: map,
+            compound: Compound::Map(2),
+            ..
+        }) = events.get(1).cloned()
+        else {
+            panic!("no map event")
+        };
+        assert_eq!(&events[..2], &[
+            Event::Enter {
+                step: None,
+                sem_id: index,
+                compound: Compound::Tuple,
+            },
+            Event::Enter {
+                step: Some(Step::UnnamedField(0)),
+                sem_id: map,
+                compound: Compound::Map(2),
+            },
+        ]);
+        let steps = events
+            .iter()
+            .map(|event| match event {
+                Event::Enter { step, .. } | Event::Leaf { step, .. } => {
+                    step.as_ref().map(Step::to_string)
+                }
+                Event::Leave => Some(s!("<")),
+            })
+            .map(Option::unwrap_or_default)
+            .collect::<Vec<_>>();
+        assert_eq!(steps, [
+            "", ".0", "[0]", "{1}", "", ".0", ".1", "<", "<", "[1]", "{5}", "", ".at", ".kind",
+            "<", "<", "<", "<"
+        ]);
+        assert_eq!(events[3], Event::Enter {
+            step: Some(Step::Key(KeyStep::Number(1))),
+            sem_id: event,
+            compound: Compound::Union(vname!("transfer")),
+        });
+        assert_eq!(events[13], Event::Leaf {
+            step: Some(Step::NamedField(fname!("kind"))),
+            sem_id: kind,
+            val: StrictVal::enumer(vname!("custom")),
+        });
+    }
+
+    #[test]
+    fn errors() {
+        let sys = test_lib();
+        for data in [&[0x02u8, 0x05, 0x03][..], &[0x02, 0x05, 0x05], &[
+            0x02, 0x05, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04,
+            0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ]] {
+            let spec = if data.len() == 3 { "Events.Ids" } else { "Events.Index" };
+            let expected = sys.strict_deserialize_type(spec, data).unwrap_err();
+            let mut decoder = sys.stream_decoder(spec).unwrap();
+            decoder.push(data);
+            assert_eq!(decoder.decode().unwrap_err(), expected);
+        }
+
+        let data = [0x01, 0x05, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09];
+        let expected = sys.strict_deserialize_type("Events.Index", &data).unwrap_err();
+        let mut decoder = sys.stream_decoder("Events.Index").unwrap();
+        decoder.push(data);
+        let err = decoder.decode().unwrap_err();
+        assert_eq!(err.offset, 12);
+        assert_eq!(err, expected);
+
+        for data in [&[0x00u8][..], &[0x11]] {
+            let expected = sys.strict_deserialize_type("Events.Name", data).unwrap_err();
+            assert!(matches!(expected.kind, ErrorKind::LengthOutOfBounds { .. }));
+            let mut decoder = sys.stream_decoder("Events.Name").unwrap();
+            decoder.push(data);
+            assert_eq!(decoder.decode().unwrap_err(), expected);
+        }
+
+        let limits = DecodeLimits {
+            max_len: 1,
+            ..DecodeLimits::unlimited()
+        };
+        let mut decoder = sys.stream_decoder("Events.Ids").unwrap().with_limits(limits);
+        decoder.push([0x02]);
+        assert_eq!(decoder.decode().unwrap_err().kind, ErrorKind::LengthLimit { len: 2, max: 1 });
+    }
+}
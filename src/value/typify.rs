@@ -22,28 +22,170 @@
 
 //! Checks strict values against provied strict type specification.
 
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
 use amplify::ascii::{AsAsciiStrError, AsciiString};
 use amplify::confinement::NonEmptyOrdSet;
+use amplify::hex::FromHex;
+use amplify::num::apfloat::{ieee, Float, Status};
+use amplify::num::{i256, u256};
 use amplify::Wrapper;
-use encoding::{FieldName, InvalidRString, Primitive, Sizing, VariantName};
+use baid64::{BAID64_ALPHABET, ID_MIN_LEN};
+use encoding::{
+    FieldName, InvalidRString, LibName, NumCls, Primitive, Sizing, TypeName, VariantName,
+};
 use indexmap::IndexMap;
 
-use super::{Blob, StrictVal};
+use super::{float, Blob, StrictVal};
 use crate::ast::EnumVariants;
 use crate::typesys::{SymbolicSys, TypeFqn, TypeSymbol};
 use crate::value::{EnumTag, StrictNum};
-use crate::{SemId, Ty, TypeRef, TypeSystem};
-
-#[derive(Clone, Eq, PartialEq, Hash, Debug, From, Display)]
-#[display(inner)]
+use crate::{SemId, Ty, TypeRef, TypeSystem, Urn, UrnParseError};
+
+/// Specification of a type, which can be resolved into its semantic id by a [`SymbolicSys`].
+///
+/// String representations of type specifications are:
+/// - a fully qualified type name, optionally followed by the checkword of the type semantic id:
+///   `Lib.Type` or `Lib.Type#word-word-word`;
+/// - a semantic id in Baid64 or hex, or its URN;
+/// - a prefix of a semantic id, at least four characters long: `semid:9SJxLn7R`, which must be
+///   unique within the type system.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, From)]
 pub enum TypeSpec {
     #[from]
     SemId(SemId),
 
+    /// Prefix of the semantic id Baid64 representation, without dashes.
+    SemIdPrefix(String),
+
+    /// Fully qualified type name with an optional checkword of the type semantic id.
+    Fqn(TypeFqn, Option<String>),
+}
+
+impl From<TypeFqn> for TypeSpec {
+    fn from(fqn: TypeFqn) -> Self { TypeSpec::Fqn(fqn, None) }
+}
+
+impl From<&'static str> for TypeSpec {
+    fn from(s: &'static str) -> Self {
+        TypeSpec::from_str(s).unwrap_or_else(|err| panic!("invalid type specification: {err}"))
+    }
+}
+
+impl Display for TypeSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeSpec::SemId(sem_id) => Display::fmt(sem_id, f),
+            TypeSpec::SemIdPrefix(prefix) => write!(f, "semid:{prefix}"),
+            TypeSpec::Fqn(fqn, None) => Display::fmt(fqn, f),
+            TypeSpec::Fqn(fqn, Some(checkword)) => write!(f, "{fqn}#{checkword}"),
+        }
+    }
+}
+
+impl FromStr for TypeSpec {
+    type Err = SpecParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("urn:") {
+            return match Urn::from_str(s)? {
+                Urn::Type(sem_id) => Ok(TypeSpec::SemId(sem_id)),
+                Urn::Lib(_) => Err(SpecParseError::LibUrn(s.to_owned())),
+            };
+        }
+        if let Ok(sem_id) = SemId::from_hex(s) {
+            return Ok(TypeSpec::SemId(sem_id));
+        }
+
+        if let Some((lib, name)) = s.split_once('.') {
+            let (name, checkword) = match name.split_once('#') {
+                Some((name, checkword)) => (name, Some(checkword)),
+                None => (name, None),
+            };
+            let lib = LibName::try_from(lib.to_owned())
+                .map_err(|_| SpecParseError::InvalidFqn(s.to_owned()))?;
+            let name = TypeName::try_from(name.to_owned())
+                .map_err(|_| SpecParseError::InvalidFqn(s.to_owned()))?;
+            if let Some(checkword) = checkword {
+                let words = checkword.split('-').collect::<Vec<_>>();
+                if words.len() != 3
+                    || !words.iter().all(|word| {
+                        !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase())
+                    })
+                {
+                    return Err(SpecParseError::InvalidCheckword(s.to_owned()));
+                }
+            }
+            return Ok(TypeSpec::Fqn(TypeFqn::with(lib, name), checkword.map(str::to_owned)));
+        }
+
+        match SemId::from_str(s) {
+            Ok(sem_id) => return Ok(TypeSpec::SemId(sem_id)),
+            // Only complete semantic ids may have checkwords
+            Err(err) if s.contains('#') => {
+                return Err(SpecParseError::InvalidSemId(s.to_owned(), err.to_string()))
+            }
+            Err(_) => {}
+        }
+        let prefix = s.strip_prefix("semid:").unwrap_or(s).replace('-', "");
+        if prefix.len() < ID_MIN_LEN || !prefix.chars().all(|c| BAID64_ALPHABET.contains(c)) {
+            return Err(SpecParseError::Unrecognized(s.to_owned()));
+        }
+        Ok(TypeSpec::SemIdPrefix(prefix))
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum SpecParseError {
+    #[display(inner)]
     #[from]
-    #[from(&'static str)]
-    // TODO: Add optional checkword suffix
-    Fqn(TypeFqn /* , Option<CheckWords> */),
+    InvalidUrn(UrnParseError),
+
+    /// `{0}` identifies a type library and not a type.
+    LibUrn(String),
+
+    /// invalid semantic id `{0}`: {1}
+    InvalidSemId(String, String),
+
+    /// invalid fully qualified type name `{0}`.
+    InvalidFqn(String),
+
+    /// invalid checkword in `{0}`; a checkword must consist of three dash-separated words.
+    InvalidCheckword(String),
+
+    /// `{0}` is neither a fully qualified type name nor a semantic id, its prefix or URN.
+    Unrecognized(String),
+}
+
+/// Errors resolving [`TypeSpec`] into a semantic id.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum SpecError {
+    /// unknown type `{0}`.
+    TypeAbsent(TypeSpec),
+
+    /// checkword `{found}` doesn't match type `{fqn}`, which has semantic id {sem_id}.
+    CheckwordMismatch {
+        fqn: TypeFqn,
+        sem_id: SemId,
+        found: String,
+    },
+
+    /// semantic id prefix `{prefix}` is ambiguous, matching {count} types.
+    AmbiguousPrefix { prefix: String, count: usize },
+}
+
+impl SpecError {
+    /// Converts the error into an error of an operation, which reports unknown types with its
+    /// own error variant.
+    pub(crate) fn report<E: From<SpecError>>(self, absent: impl FnOnce(TypeSpec) -> E) -> E {
+        match self {
+            SpecError::TypeAbsent(spec) => absent(spec),
+            err => err.into(),
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Display)]
@@ -65,6 +207,10 @@ pub enum Error {
     /// unknown type `{0}`.
     TypeAbsent(TypeSpec),
 
+    #[display(inner)]
+    #[from]
+    Spec(SpecError),
+
     /// collection `{0}` has size {1} which is out of type required bounds {2}.
     OutOfBounds(TypeSpec, usize, Sizing),
 
@@ -116,19 +262,34 @@ trait PrimitiveValue {
     fn is_large_unsigned(&self) -> bool;
     fn is_small_signed(&self) -> bool;
     fn is_large_signed(&self) -> bool;
+    fn is_non_zero(&self) -> bool;
+    fn is_float(&self) -> bool;
 }
 
 impl PrimitiveValue for Primitive {
-    fn is_small_unsigned(&self) -> bool { self.into_code() <= 16 }
-    fn is_large_unsigned(&self) -> bool { self.into_code() > 16 && self.into_code() < 0x40 }
-    fn is_small_signed(&self) -> bool { self.into_code() >= 0x40 && self.into_code() <= 0x4f }
-    fn is_large_signed(&self) -> bool { self.into_code() > 0x4f && self.into_code() < 0x80 }
+    fn is_small_unsigned(&self) -> bool {
+        *self == Primitive::BYTE
+            || (matches!(self.info().ty, NumCls::Unsigned | NumCls::NonZero)
+                && (1..=8).contains(&self.byte_size()))
+    }
+    fn is_large_unsigned(&self) -> bool {
+        matches!(self.info().ty, NumCls::Unsigned | NumCls::NonZero)
+            && (9..=128).contains(&self.byte_size())
+    }
+    fn is_small_signed(&self) -> bool {
+        self.info().ty == NumCls::Signed && *self != Primitive::BYTE && self.byte_size() <= 8
+    }
+    fn is_large_signed(&self) -> bool {
+        self.info().ty == NumCls::Signed && (9..=128).contains(&self.byte_size())
+    }
+    fn is_non_zero(&self) -> bool { self.info().ty == NumCls::NonZero }
+    fn is_float(&self) -> bool { self.info().ty == NumCls::Float }
 }
 
 impl SymbolicSys {
     pub fn typify(&self, val: StrictVal, spec: impl Into<TypeSpec>) -> Result<TypedVal, Error> {
         let spec = spec.into();
-        let sem_id = self.to_sem_id(spec.clone()).ok_or(Error::TypeAbsent(spec))?;
+        let sem_id = self.resolve_spec(spec).map_err(|err| err.report(Error::TypeAbsent))?;
         self.as_types().typify(val, sem_id)
     }
 }
@@ -144,6 +305,52 @@ impl TypeSystem {
         let val = match (val, ty) {
             // Primitive direct matches:
             (val @ StrictVal::Unit, Ty::Primitive(prim)) if *prim == Primitive::UNIT => val,
+            (val, Ty::Primitive(prim)) if prim.is_float() => {
+                let f = match &val {
+                    StrictVal::Number(StrictNum::Float(f)) => Some(*f),
+                    StrictVal::Number(StrictNum::Uint(n)) => {
+                        Some(ieee::Oct::from_u256(u256::from(*n)).value)
+                    }
+                    StrictVal::Number(StrictNum::Int(n)) => {
+                        Some(ieee::Oct::from_i256(i256::from(*n)).value)
+                    }
+                    // Special values parsed from text representations:
+                    StrictVal::Enum(EnumTag::Name(name)) if name.as_str() == "inf" => {
+                        Some(ieee::Oct::INFINITY)
+                    }
+                    StrictVal::Enum(EnumTag::Name(name)) if name.as_str() == "nan" => {
+                        Some(ieee::Oct::NAN)
+                    }
+                    _ => None,
+                };
+                match f.and_then(|f| float::round(*prim, f)) {
+                    Some(rounded) if !rounded.status.contains(Status::OVERFLOW) => {
+                        StrictVal::Number(StrictNum::Float(rounded.value))
+                    }
+                    _ => {
+                        return Err(Error::TypeMismatch {
+                            value: val,
+                            expected: ty.clone(),
+                        });
+                    }
+                }
+            }
+            (val @ StrictVal::Number(StrictNum::Uint(0)), Ty::Primitive(prim))
+                if prim.is_non_zero() =>
+            {
+                return Err(Error::TypeMismatch {
+                    value: val,
+                    expected: ty.clone(),
+                });
+            }
+            (StrictVal::Number(StrictNum::BigUint(val)), Ty::Primitive(prim))
+                if prim.is_non_zero() && val.is_zero() =>
+            {
+                return Err(Error::TypeMismatch {
+                    value: StrictVal::Number(StrictNum::BigUint(val)),
+                    expected: ty.clone(),
+                });
+            }
             (val @ StrictVal::Number(StrictNum::Uint(_)), Ty::Primitive(prim))
                 if prim.is_small_unsigned() =>
             {
@@ -169,6 +376,22 @@ impl TypeSystem {
             {
                 val
             }
+            // Small numbers parsed from text representations:
+            (StrictVal::Number(StrictNum::Uint(val)), Ty::Primitive(prim))
+                if prim.is_large_unsigned() =>
+            {
+                StrictVal::Number(StrictNum::BigUint(val.into()))
+            }
+            (StrictVal::Number(StrictNum::Uint(val)), Ty::Primitive(prim))
+                if prim.is_large_signed() =>
+            {
+                StrictVal::Number(StrictNum::BigInt(val.into()))
+            }
+            (StrictVal::Number(StrictNum::Int(val)), Ty::Primitive(prim))
+                if prim.is_large_signed() =>
+            {
+                StrictVal::Number(StrictNum::BigInt(val.into()))
+            }
 
             // Collection bounds check:
             (StrictVal::Bytes(s), Ty::Array(id, len))
@@ -198,6 +421,13 @@ impl TypeSystem {
             {
                 return Err(Error::OutOfBounds(spec, s.len(), *sizing));
             }
+            // Empty map is indistinguishable from an empty set in text representations
+            (StrictVal::Set(s), Ty::Map(_, _, sizing)) if s.is_empty() => {
+                if !sizing.check(0) {
+                    return Err(Error::OutOfBounds(spec, 0, *sizing));
+                }
+                StrictVal::Map(vec![])
+            }
             (StrictVal::Map(s), Ty::Map(_, _, sizing)) if !sizing.check(s.len()) => {
                 return Err(Error::OutOfBounds(spec, s.len(), *sizing));
             }
@@ -208,7 +438,7 @@ impl TypeSystem {
             }
             // Unicode character
             (StrictVal::String(s), ty @ Ty::UnicodeChar)
-                if ty.is_unicode_char() && s.len() == 1 =>
+                if ty.is_unicode_char() && s.chars().count() == 1 =>
             {
                 StrictVal::String(s)
             }
@@ -254,10 +484,10 @@ impl TypeSystem {
                 let mut new = Vec::with_capacity(s.len());
                 for item in s {
                     let checked = self.typify(item, *id)?;
-                    if new.contains(&checked.val) {
-                        return Err(Error::RepeatedSetValue(spec, checked.val));
+                    match new.binary_search_by(|probe| self.cmp_vals(probe, &checked.val, *id)) {
+                        Ok(_) => return Err(Error::RepeatedSetValue(spec, checked.val)),
+                        Err(pos) => new.insert(pos, checked.val),
                     }
-                    new.push(checked.val);
                 }
                 StrictVal::Set(new)
             }
@@ -266,10 +496,11 @@ impl TypeSystem {
                 for (key, item) in s {
                     let checked_key = self.typify(key, *key_id)?;
                     let checked_val = self.typify(item, *id)?;
-                    if new.iter().any(|(k, _)| k == &checked_key.val) {
-                        return Err(Error::RepeatedKeyValue(spec, checked_key.val));
+                    let key = &checked_key.val;
+                    match new.binary_search_by(|(probe, _)| self.cmp_vals(probe, key, *key_id)) {
+                        Ok(_) => return Err(Error::RepeatedKeyValue(spec, checked_key.val)),
+                        Err(pos) => new.insert(pos, (checked_key.val, checked_val.val)),
                     }
-                    new.push((checked_key.val, checked_val.val));
                 }
                 StrictVal::Map(new)
             }
@@ -424,10 +655,13 @@ impl TypeSystem {
 #[cfg(test)]
 mod test {
     use amplify::confinement::U32 as MAX32;
+    use amplify::hex::ToHex;
+    use baid64::DisplayBaid64;
     use encoding::{StreamReader, StrictSerialize};
 
     use super::super::test_helpers::*;
-    // use super::*;
+    use super::*;
+    use crate::stl::strict_types_stl;
 
     #[test]
     fn load() {
@@ -441,4 +675,75 @@ mod test {
         let loaded = sys.strict_read_type("TestLib.Nominal", &mut reader).unwrap();
         assert_eq!(loaded.val, value);
     }
+
+    #[test]
+    fn spec_parse() {
+        let sem_id = Ty::<SemId>::UNICODE.sem_id_unnamed();
+        for s in ["Lib.Type", "Lib.Type#alpha-beta-gamma", "semid:9SJxLn7R", &sem_id.to_string()] {
+            assert_eq!(TypeSpec::from_str(s).unwrap().to_string(), s);
+        }
+        assert_eq!(TypeSpec::from_str(&format!("urn:sten:id:{sem_id}")), Ok(sem_id.into()));
+        assert_eq!(TypeSpec::from_str(&format!("{sem_id:#}")), Ok(sem_id.into()));
+        assert_eq!(TypeSpec::from_str(&sem_id.to_hex()), Ok(sem_id.into()));
+        let lib_urn = format!("urn:sten:lib:{}", strict_types_stl().id());
+        assert_eq!(TypeSpec::from_str(&lib_urn), Err(SpecParseError::LibUrn(lib_urn.clone())));
+        assert!(matches!(
+            TypeSpec::from_str("urn:sten:id:typo"),
+            Err(SpecParseError::InvalidUrn(UrnParseError::Invalid(..)))
+        ));
+        assert_eq!(TypeSpec::from_str("9SJx-Ln7"), Ok(TypeSpec::SemIdPrefix(s!("9SJxLn7"))));
+        assert_eq!(
+            TypeSpec::from("Lib.Type#alpha-beta-gamma"),
+            TypeSpec::Fqn(TypeFqn::with("Lib", "Type"), Some(s!("alpha-beta-gamma")))
+        );
+
+        assert_eq!(
+            TypeSpec::from_str("Lib.Type#alpha-beta"),
+            Err(SpecParseError::InvalidCheckword(s!("Lib.Type#alpha-beta")))
+        );
+        assert_eq!(TypeSpec::from_str("Lib."), Err(SpecParseError::InvalidFqn(s!("Lib."))));
+        assert_eq!(TypeSpec::from_str("9SJ"), Err(SpecParseError::Unrecognized(s!("9SJ"))));
+        assert_eq!(
+            TypeSpec::from_str("typesys:9SJxLn7R"),
+            Err(SpecParseError::Unrecognized(s!("typesys:9SJxLn7R")))
+        );
+        assert!(matches!(
+            TypeSpec::from_str("semid:9SJxLn7R#alpha-beta-gamma"),
+            Err(SpecParseError::InvalidSemId(..))
+        ));
+    }
+
+    #[test]
+    fn spec_resolve() {
+        let sys = test_system();
+        let sem_id = sys.resolve_spec("TestLib.Nominal").unwrap();
+        let checkword = sem_id.to_baid64_mnemonic();
+
+        let spec = TypeSpec::from_str(&format!("TestLib.Nominal#{checkword}")).unwrap();
+        assert_eq!(sys.resolve_spec(spec), Ok(sem_id));
+        let prefix = format!("{sem_id:-#}")[..8].to_owned();
+        assert_eq!(sys.resolve_spec(TypeSpec::from_str(&prefix).unwrap()), Ok(sem_id));
+        let urn = format!("urn:sten:id:{sem_id}");
+        assert_eq!(sys.resolve_spec(TypeSpec::from_str(&urn).unwrap()), Ok(sem_id));
+
+        let spec = TypeSpec::from("TestLib.Nominal#alpha-beta-gamma");
+        assert_eq!(
+            sys.resolve_spec(spec.clone()),
+            Err(SpecError::CheckwordMismatch {
+                fqn: TypeFqn::from("TestLib.Nominal"),
+                sem_id,
+                found: s!("alpha-beta-gamma"),
+            })
+        );
+        assert_eq!(sys.to_sem_id(spec.clone()), None);
+        assert_eq!(
+            sys.strict_deserialize_type(spec, &[]).unwrap_err().kind.to_string(),
+            format!(
+                "checkword `alpha-beta-gamma` doesn't match type `TestLib.Nominal`, which has \
+                 semantic id {sem_id}."
+            )
+        );
+        let spec = TypeSpec::from("TestLib.Absent#alpha-beta-gamma");
+        assert_eq!(sys.resolve_spec(spec.clone()), Err(SpecError::TypeAbsent(spec)));
+    }
 }
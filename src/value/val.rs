@@ -22,15 +22,19 @@
 
 //! Strict value core types.
 
-use std::fmt::Debug;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 
 use amplify::hex::ToHex;
-// use amplify::num::apfloat::ieee;
-use amplify::num::{i1024, u1024, u24, u40, u48, u56};
+use amplify::num::apfloat::{ieee, Float, FloatConvert};
+use amplify::num::{i1024, u1024, u24, u256, u40, u48, u56};
 use amplify::Wrapper;
 use encoding::{FieldName, StrictEnum, VariantName};
 use indexmap::IndexMap;
 
+use super::float::{self, BFloat};
+
 #[macro_export]
 macro_rules! ston {
     (~) => {
@@ -172,8 +176,7 @@ macro_rules! svtable {
     };
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display, From)]
-#[display(inner)]
+#[derive(Copy, Clone, Debug, From)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -210,22 +213,158 @@ pub enum StrictNum {
     //#[from(i512)]
     #[from]
     BigInt(i1024),
-    // TODO: Do conversion of number types in to amplify_num
-    /*
-    #[from(half::bf16)]
-    #[from(ieee::Half)]
-    #[from(ieee::Single)]
-    #[from(ieee::Double)]
-    #[from(ieee::Quad)]
-    #[from(ieee::Oct]
-    #[from(f32)]
-    #[from(f64)]
+
+    /// Floating-point number of any precision up to IEEE-754 octuple.
+    #[from]
+    #[cfg_attr(feature = "serde", serde(with = "float_serde"))]
     Float(ieee::Oct),
-    */
     // TODO: Addnon-zero
 }
 
-// TODO: Do conversion of number types in to amplify_num
+macro_rules! impl_from_float {
+    ($($ty:ty),+) => {$(
+        impl From<$ty> for StrictNum {
+            fn from(f: $ty) -> Self {
+                let mut loses_info = false;
+                StrictNum::Float(f.convert(&mut loses_info).value)
+            }
+        }
+    )+};
+}
+impl_from_float!(
+    BFloat,
+    ieee::Half,
+    ieee::Single,
+    ieee::Double,
+    ieee::X87DoubleExtended,
+    ieee::Quad
+);
+
+impl From<f32> for StrictNum {
+    fn from(f: f32) -> Self { ieee::Single::from_bits(f.to_bits().into()).into() }
+}
+
+impl From<f64> for StrictNum {
+    fn from(f: f64) -> Self { ieee::Double::from_bits(f.to_bits().into()).into() }
+}
+
+impl StrictNum {
+    fn variant_no(&self) -> u8 {
+        match self {
+            StrictNum::Uint(_) => 0,
+            StrictNum::BigUint(_) => 1,
+            StrictNum::Int(_) => 2,
+            StrictNum::BigInt(_) => 3,
+            StrictNum::Float(_) => 4,
+        }
+    }
+}
+
+/// Floats are compared by their binary representation, such that `NaN`s are equal to
+/// themselves and the comparison is a total order.
+fn float_ord_key(f: ieee::Oct) -> u256 {
+    let bits = f.to_bits();
+    if f.is_negative() {
+        !bits
+    } else {
+        bits | (u256::ONE << 255)
+    }
+}
+
+impl PartialEq for StrictNum {
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for StrictNum {}
+
+impl PartialOrd for StrictNum {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for StrictNum {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (StrictNum::Uint(a), StrictNum::Uint(b)) => a.cmp(b),
+            (StrictNum::BigUint(a), StrictNum::BigUint(b)) => a.cmp(b),
+            (StrictNum::Int(a), StrictNum::Int(b)) => a.cmp(b),
+            (StrictNum::BigInt(a), StrictNum::BigInt(b)) => a.cmp(b),
+            (StrictNum::Float(a), StrictNum::Float(b)) => float_ord_key(*a).cmp(&float_ord_key(*b)),
+            (a, b) => a.variant_no().cmp(&b.variant_no()),
+        }
+    }
+}
+
+impl Hash for StrictNum {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.variant_no().hash(state);
+        match self {
+            StrictNum::Uint(n) => n.hash(state),
+            StrictNum::BigUint(n) => n.hash(state),
+            StrictNum::Int(n) => n.hash(state),
+            StrictNum::BigInt(n) => n.hash(state),
+            StrictNum::Float(f) => f.to_bits().hash(state),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod float_serde {
+    use amplify::num::apfloat::{ieee, Float, FloatConvert};
+    use amplify::num::u256;
+    use serde::ser::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(f: &ieee::Oct, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        let mut loses_info = false;
+        let double: ieee::Double = f.convert(&mut loses_info).value;
+        if loses_info {
+            return Err(S::Error::custom(format_args!(
+                "float {f} can't be represented as f64 without loss of precision"
+            )));
+        }
+        f64::from_bits(double.to_bits().low_u64()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ieee::Oct, D::Error>
+    where D: Deserializer<'de> {
+        let f = f64::deserialize(deserializer)?;
+        let mut loses_info = false;
+        Ok(ieee::Double::from_bits(u256::from(f.to_bits())).convert(&mut loses_info).value)
+    }
+}
+
+impl Display for StrictNum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StrictNum::Uint(n) => Display::fmt(n, f),
+            StrictNum::Int(n) => Display::fmt(n, f),
+            StrictNum::BigUint(n) => fmt_big_decimal(*n, f),
+            StrictNum::BigInt(n) if n.is_negative() => {
+                f.write_str("-")?;
+                fmt_big_decimal(u1024::from_le_bytes(n.wrapping_neg().to_le_bytes()), f)
+            }
+            StrictNum::BigInt(n) => fmt_big_decimal(u1024::from_le_bytes(n.to_le_bytes()), f),
+            StrictNum::Float(n) => float::fmt(*n, f),
+        }
+    }
+}
+
+/// Formats large integer in decimal notation (big integer types from `amplify_num` use hex).
+fn fmt_big_decimal(mut n: u1024, f: &mut Formatter<'_>) -> fmt::Result {
+    const CHUNK: u64 = 10_000_000_000_000_000_000;
+    let mut chunks = vec![];
+    while !n.is_zero() {
+        chunks.push((n % u1024::from(CHUNK)).low_u64());
+        n /= u1024::from(CHUNK);
+    }
+    let mut iter = chunks.into_iter().rev();
+    write!(f, "{}", iter.next().unwrap_or_default())?;
+    for chunk in iter {
+        write!(f, "{chunk:019}")?;
+    }
+    Ok(())
+}
 
 impl StrictNum {
     pub fn unwrap_uint<N: TryFrom<u64>>(self) -> N
@@ -343,14 +482,15 @@ pub enum StrictVal {
     //#[from(i256)]
     //#[from(i512)]
     //#[from(i1024)]
-    //#[from(f32)]
-    //#[from(f64)]
-    //#[from(half::bf16)]
-    //#[from(ieee::Half)]
-    //#[from(ieee::Single)]
-    //#[from(ieee::Double)]
-    //#[from(ieee::Quad)]
-    //#[from(ieee::Oct)]
+    #[from(f32)]
+    #[from(f64)]
+    #[from(BFloat)]
+    #[from(ieee::Half)]
+    #[from(ieee::Single)]
+    #[from(ieee::Double)]
+    #[from(ieee::X87DoubleExtended)]
+    #[from(ieee::Quad)]
+    #[from(ieee::Oct)]
     Number(StrictNum),
 
     // Covers Unicode & ASCII strings and characters
@@ -571,7 +711,8 @@ impl<T: Into<StrictVal>> From<Option<T>> for StrictVal {
 
 #[cfg(test)]
 mod test {
-    // use super::*;
+    #[cfg(feature = "serde")]
+    use super::*;
 
     #[test]
     fn construct() {
@@ -588,4 +729,19 @@ mod test {
             r#"Struct({FieldName("name"): String("Some name"), FieldName("ticker"): String("TICK"), FieldName("precision"): Number(Uint(8))})"#
         )
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn float_serde() {
+        let num = StrictNum::from(0.1f64);
+        let json = serde_json::to_string(&num).unwrap();
+        assert_eq!(json, "0.1");
+        assert_eq!(serde_json::from_str::<StrictNum>(&json).unwrap(), num);
+
+        let mut loses_info = false;
+        let quad: ieee::Quad = "0.1".parse().unwrap();
+        let num = StrictNum::Float(quad.convert(&mut loses_info).value);
+        let err = serde_json::to_string(&num).unwrap_err();
+        assert!(err.to_string().contains("without loss of precision"));
+    }
 }
@@ -0,0 +1,574 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-copy lazy access to strict encoded values.
+//!
+//! [`ValueRef`] borrows encoded data together with the type system and walks the data only as
+//! far as needed to reach a requested node: preceding fields, items and map entries are skipped
+//! using the structure of their types, without decoding or allocating them. Byte and character
+//! strings are returned as slices of the original data; any other node may be decoded into an
+//! owned [`StrictVal`] with [`ValueRef::decode`].
+
+use encoding::{FieldName, Sizing, StreamReader};
+
+use super::typify::{SpecError, TypeSpec};
+use super::{decode, KeyStep, Step};
+use crate::typesys::{SymbolicSys, UnknownType};
+use crate::util::SizingExt;
+use crate::{SemId, StrictVal, Ty, TypeRef, TypeSystem};
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum Error {
+    /// unknown type `{0}`.
+    TypeAbsent(TypeSpec),
+
+    #[display(inner)]
+    #[from]
+    Spec(SpecError),
+
+    #[display(inner)]
+    #[from]
+    UnknownType(UnknownType),
+
+    /// data end unexpectedly at byte {0}.
+    UnexpectedEnd(u64),
+
+    /// unknown tag {1} of union type {0}.
+    UnionTag(SemId, u8),
+
+    /// path step `{0}` doesn't match type {1}.
+    StepMismatch(Step, SemId),
+
+    /// tuple has less fields than requested in the path ({0} vs {1}).
+    FieldNoOutOfBounds(u8, usize),
+
+    /// struct doesn't have field named `{0}`.
+    UnknownFieldName(FieldName),
+
+    /// collection has less items than requested in the path ({0} vs {1}).
+    CollectionIndexOutOfBounds(u32, u64),
+
+    /// map doesn't have key named `{0}`.
+    UnknownKey(KeyStep),
+
+    /// value of type {0} is not a byte string.
+    NotBytes(SemId),
+
+    /// value of type {0} is not a string.
+    NotString(SemId),
+
+    /// invalid string at byte {0}.
+    InvalidString(u64),
+
+    /// collection length {len} at byte {offset} is out of the type bounds {min}..={max}.
+    LengthOutOfBounds {
+        offset: u64,
+        len: u64,
+        min: u64,
+        max: u64,
+    },
+
+    #[display(inner)]
+    Decode(Box<decode::Error>),
+}
+
+impl From<decode::Error> for Error {
+    fn from(err: decode::Error) -> Self { Error::Decode(Box::new(err)) }
+}
+
+/// Borrowed view of a strict encoded value of a specific type.
+///
+/// The view starts at the first byte of the value; the data may continue after the end of the
+/// value, which is detected only when needed. All offsets reported by the view are counted from
+/// the start of the data provided to [`TypeSystem::value_ref`].
+#[derive(Copy, Clone, Debug)]
+pub struct ValueRef<'a> {
+    types: &'a TypeSystem,
+    sem_id: SemId,
+    ty: &'a Ty<SemId>,
+    data: &'a [u8],
+    offset: u64,
+}
+
+impl SymbolicSys {
+    pub fn value_ref<'a>(
+        &'a self,
+        spec: impl Into<TypeSpec>,
+        data: &'a [u8],
+    ) -> Result<ValueRef<'a>, Error> {
+        let sem_id = self.resolve_spec(spec).map_err(|err| err.report(Error::TypeAbsent))?;
+        self.as_types().value_ref(sem_id, data)
+    }
+}
+
+impl TypeSystem {
+    /// Constructs a view of a value of the given type encoded at the start of the data.
+    ///
+    /// The data are not checked at this point: errors are reported once the view is accessed.
+    pub fn value_ref<'a>(&'a self, sem_id: SemId, data: &'a [u8]) -> Result<ValueRef<'a>, Error> {
+        ValueRef::with(self, sem_id, data, 0)
+    }
+}
+
+impl<'a> ValueRef<'a> {
+    fn with(
+        types: &'a TypeSystem,
+        sem_id: SemId,
+        data: &'a [u8],
+        offset: u64,
+    ) -> Result<Self, Error> {
+        let ty = types.get(sem_id).ok_or(UnknownType(sem_id))?;
+        Ok(ValueRef {
+            types,
+            sem_id,
+            ty,
+            data,
+            offset,
+        })
+    }
+
+    pub fn sem_id(&self) -> SemId { self.sem_id }
+
+    pub fn ty(&self) -> &'a Ty<SemId> { self.ty }
+
+    /// Offset of the value from the start of the data.
+    pub fn offset(&self) -> u64 { self.offset }
+
+    /// Returns the encoded representation of the value.
+    pub fn as_raw(&self) -> Result<&'a [u8], Error> {
+        let end = self.skip(self.sem_id, 0)?;
+        Ok(&self.data[..end])
+    }
+
+    /// Returns the content of a byte array or a byte string, without the length prefix.
+    pub fn as_bytes(&self) -> Result<&'a [u8], Error> {
+        match self.ty {
+            Ty::Array(ty, len) if ty.is_byte() => self.take(0, *len as u64).map(|(data, _)| data),
+            Ty::List(ty, sizing) if ty.is_byte() => {
+                let (len, pos) = self.read_len(0, *sizing)?;
+                self.take(pos, len).map(|(data, _)| data)
+            }
+            _ => Err(Error::NotBytes(self.sem_id)),
+        }
+    }
+
+    /// Returns the content of a unicode, ASCII or restricted string.
+    pub fn as_str(&self) -> Result<&'a str, Error> {
+        let (sizing, ascii) = match self.ty {
+            Ty::List(ty, sizing) if ty.is_unicode_char() => (*sizing, false),
+            Ty::List(ty, sizing) if self.ty_of(*ty)?.is_char_enum() => (*sizing, true),
+            Ty::Tuple(fields) if self.types.is_rstring(fields)? => {
+                let (_, sizing) = self.types.rstring_sizing(fields)?.expect("checked in match");
+                (sizing, true)
+            }
+            _ => return Err(Error::NotString(self.sem_id)),
+        };
+        let (len, pos) = self.read_len(0, sizing)?;
+        let (data, _) = self.take(pos, len)?;
+        let invalid = Error::InvalidString(self.offset + pos as u64);
+        if ascii && !data.is_ascii() {
+            return Err(invalid);
+        }
+        std::str::from_utf8(data).map_err(|_| invalid)
+    }
+
+    /// Decodes the value into an owned strict value, restricting the decoder resources with the
+    /// default [`decode::DecodeLimits`].
+    ///
+    /// Offsets in decoding errors are counted from the start of this value.
+    pub fn decode(&self) -> Result<StrictVal, Error> {
+        self.decode_limited(decode::DecodeLimits::default())
+    }
+
+    /// Decodes the value into an owned strict value within the given resource limits.
+    ///
+    /// Offsets in decoding errors are counted from the start of this value.
+    pub fn decode_limited(&self, limits: decode::DecodeLimits) -> Result<StrictVal, Error> {
+        let mut reader = StreamReader::cursor::<{ usize::MAX }>(self.data);
+        Ok(self.types.strict_read_type_limited(self.sem_id, &mut reader, limits)?.val)
+    }
+
+    /// Returns a view of the nested value addressed by the path step. Union variants are
+    /// transparent for the path steps.
+    pub fn at_step(&self, step: &Step) -> Result<ValueRef<'a>, Error> {
+        let me = self.unwrap_union()?;
+        let (sem_id, pos) = match (me.ty, step) {
+            (Ty::Tuple(fields), Step::UnnamedField(no)) => {
+                let no = *no as usize;
+                let sem_id =
+                    *fields.get(no).ok_or(Error::FieldNoOutOfBounds(no as u8, fields.len()))?;
+                let mut pos = 0;
+                for ty in fields.iter().take(no) {
+                    pos = me.skip(*ty, pos)?;
+                }
+                (sem_id, pos)
+            }
+            (Ty::Struct(fields), Step::NamedField(name)) => {
+                let no = fields
+                    .iter()
+                    .position(|field| &field.name == name)
+                    .ok_or_else(|| Error::UnknownFieldName(name.clone()))?;
+                let mut pos = 0;
+                for field in fields.iter().take(no) {
+                    pos = me.skip(field.ty, pos)?;
+                }
+                (fields[no].ty, pos)
+            }
+            (Ty::Array(ty, len), Step::Index(idx)) => {
+                me.nth_item(*ty, *idx, *len as u64, 0).map(|pos| (*ty, pos))?
+            }
+            (Ty::List(ty, sizing) | Ty::Set(ty, sizing), Step::Index(idx)) => {
+                let (len, pos) = me.read_len(0, *sizing)?;
+                me.nth_item(*ty, *idx, len, pos).map(|pos| (*ty, pos))?
+            }
+            (Ty::Map(key_ty, ty, sizing), Step::Key(key)) => {
+                let (len, mut pos) = me.read_len(0, *sizing)?;
+                let mut found = None;
+                for _ in 0..len {
+                    let end = me.skip(*key_ty, pos)?;
+                    let val = self.types.strict_deserialize_type(*key_ty, &me.data[pos..end])?;
+                    if key.has_match(&val.val) {
+                        found = Some(end);
+                        break;
+                    }
+                    pos = me.skip(*ty, end)?;
+                }
+                (*ty, found.ok_or_else(|| Error::UnknownKey(key.clone()))?)
+            }
+            (_, step) => return Err(Error::StepMismatch(step.clone(), me.sem_id)),
+        };
+        me.nested(sem_id, pos)
+    }
+
+    /// Returns a view of the nested value addressed by the path. Union variants are transparent
+    /// for the path steps.
+    pub fn at_path<'p>(
+        &self,
+        path: impl IntoIterator<Item = &'p Step>,
+    ) -> Result<ValueRef<'a>, Error> {
+        let mut val = *self;
+        for step in path {
+            val = val.at_step(step)?;
+        }
+        Ok(val)
+    }
+
+    fn nested(&self, sem_id: SemId, pos: usize) -> Result<ValueRef<'a>, Error> {
+        ValueRef::with(self.types, sem_id, &self.data[pos..], self.offset + pos as u64)
+    }
+
+    fn unwrap_union(&self) -> Result<ValueRef<'a>, Error> {
+        let mut val = *self;
+        while let Ty::Union(variants) = val.ty {
+            let (tag, _) = val.read_byte(0)?;
+            let (_, ty) = variants.by_tag(tag).ok_or(Error::UnionTag(val.sem_id, tag))?;
+            val = val.nested(*ty, 1)?;
+        }
+        Ok(val)
+    }
+
+    fn ty_of(&self, sem_id: SemId) -> Result<&'a Ty<SemId>, Error> {
+        Ok(self.types.get(sem_id).ok_or(UnknownType(sem_id))?)
+    }
+
+    fn take(&self, pos: usize, len: u64) -> Result<(&'a [u8], usize), Error> {
+        let end = (pos as u64)
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len() as u64)
+            .ok_or(Error::UnexpectedEnd(self.offset + self.data.len() as u64))?
+            as usize;
+        Ok((&self.data[pos..end], end))
+    }
+
+    fn read_byte(&self, pos: usize) -> Result<(u8, usize), Error> {
+        self.take(pos, 1).map(|(bytes, pos)| (bytes[0], pos))
+    }
+
+    fn read_len(&self, start: usize, sizing: Sizing) -> Result<(u64, usize), Error> {
+        let (bytes, pos) = self.take(start, sizing.byte_size() as u64)?;
+        let len = bytes.iter().rev().fold(0u64, |len, byte| (len << 8) | *byte as u64);
+        if len < sizing.min || len > sizing.max {
+            return Err(Error::LengthOutOfBounds {
+                offset: self.offset + start as u64,
+                len,
+                min: sizing.min,
+                max: sizing.max,
+            });
+        }
+        Ok((len, pos))
+    }
+
+    fn nth_item(&self, ty: SemId, idx: u32, len: u64, mut pos: usize) -> Result<usize, Error> {
+        if idx as u64 >= len {
+            return Err(Error::CollectionIndexOutOfBounds(idx, len));
+        }
+        if let Some(size) = self.fixed_size(ty)? {
+            let offset = size.saturating_mul(idx as u64);
+            return self.take(pos, offset).map(|(_, pos)| pos);
+        }
+        for _ in 0..idx {
+            pos = self.skip(ty, pos)?;
+        }
+        Ok(pos)
+    }
+
+    /// Returns the encoded size of the values of the type, if it is the same for all of them.
+    fn fixed_size(&self, sem_id: SemId) -> Result<Option<u64>, Error> {
+        Ok(match self.ty_of(sem_id)? {
+            Ty::Primitive(prim) => Some(prim.byte_size() as u64),
+            Ty::Enum(_) => Some(1),
+            Ty::Array(ty, len) => {
+                self.fixed_size(*ty)?.and_then(|size| size.checked_mul(*len as u64))
+            }
+            Ty::Tuple(fields) if !self.types.is_rstring(fields)? => {
+                let mut sum = Some(0u64);
+                for ty in fields {
+                    sum = sum.zip(self.fixed_size(*ty)?).and_then(|(a, b)| a.checked_add(b));
+                }
+                sum
+            }
+            Ty::Struct(fields) => {
+                let mut sum = Some(0u64);
+                for field in fields {
+                    sum = sum.zip(self.fixed_size(field.ty)?).and_then(|(a, b)| a.checked_add(b));
+                }
+                sum
+            }
+            _ => None,
+        })
+    }
+
+    /// Skips a value of the given type located at the position, returning the position of the
+    /// next value.
+    fn skip(&self, sem_id: SemId, pos: usize) -> Result<usize, Error> {
+        if let Some(size) = self.fixed_size(sem_id)? {
+            return self.take(pos, size).map(|(_, pos)| pos);
+        }
+        Ok(match self.ty_of(sem_id)? {
+            Ty::UnicodeChar => {
+                let (first, _) = self.read_byte(pos)?;
+                let len = match first.leading_ones() {
+                    2..=4 => first.leading_ones() as u64,
+                    _ => 1,
+                };
+                self.take(pos, len)?.1
+            }
+            Ty::Union(variants) => {
+                let (tag, pos) = self.read_byte(pos)?;
+                let (_, ty) = variants.by_tag(tag).ok_or(Error::UnionTag(sem_id, tag))?;
+                self.skip(*ty, pos)?
+            }
+            Ty::Tuple(fields) if self.types.is_rstring(fields)? => {
+                let (_, sizing) = self.types.rstring_sizing(fields)?.expect("checked in match");
+                let (len, pos) = self.read_len(pos, sizing)?;
+                self.take(pos, len)?.1
+            }
+            Ty::Tuple(fields) => {
+                let mut pos = pos;
+                for ty in fields {
+                    pos = self.skip(*ty, pos)?;
+                }
+                pos
+            }
+            Ty::Struct(fields) => {
+                let mut pos = pos;
+                for field in fields {
+                    pos = self.skip(field.ty, pos)?;
+                }
+                pos
+            }
+            Ty::Array(ty, len) => self.skip_items(*ty, *len as u64, pos)?,
+            Ty::List(ty, sizing) | Ty::Set(ty, sizing) => {
+                let (len, pos) = self.read_len(pos, *sizing)?;
+                self.skip_items(*ty, len, pos)?
+            }
+            Ty::Map(key_ty, ty, sizing) => {
+                let (len, mut pos) = self.read_len(pos, *sizing)?;
+                for _ in 0..len {
+                    pos = self.skip(*key_ty, pos)?;
+                    pos = self.skip(*ty, pos)?;
+                }
+                pos
+            }
+            Ty::Primitive(_) | Ty::Enum(_) => unreachable!("types of fixed size"),
+        })
+    }
+
+    fn skip_items(&self, ty: SemId, len: u64, mut pos: usize) -> Result<usize, Error> {
+        if let Some(size) = self.fixed_size(ty)? {
+            return self.take(pos, size.saturating_mul(len)).map(|(_, pos)| pos);
+        }
+        for _ in 0..len {
+            pos = self.skip(ty, pos)?;
+        }
+        Ok(pos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use encoding::StrictSerialize;
+
+    use super::super::test_helpers::*;
+    use super::*;
+    use crate::stl::strict_types_stl;
+    use crate::typesys::TypeFqn;
+    use crate::value::Path;
+
+    fn serialize(val: &impl StrictSerialize) -> Vec<u8> {
+        val.to_strict_serialized::<{ usize::MAX }>().unwrap().release()
+    }
+
+    #[test]
+    fn paths() {
+        let sys = test_system();
+        let lib = strict_types_stl();
+        let data = serialize(&lib);
+        let full = sys.strict_deserialize_type("StrictTypes.TypeLib", &data).unwrap().val;
+        let view = sys.value_ref("StrictTypes.TypeLib", &data).unwrap();
+
+        for path in
+            ["", ".name", ".dependencies[0]", ".dependencies[0].name", ".types", ".externTypes"]
+        {
+            let path = Path::from_str(path).unwrap();
+            let expected = full.at_path(&path).unwrap();
+            assert_eq!(&view.at_path(&path).unwrap().decode().unwrap(), expected, "{path}");
+        }
+
+        assert_eq!(view.as_raw().unwrap(), data.as_slice());
+        let name = view.at_path(&Path::from_str(".name.0").unwrap()).unwrap();
+        assert_eq!(name.as_str().unwrap(), "StrictTypes");
+        assert_eq!(name.offset(), 0);
+        let path = Path::from_str(".dependencies[0].id.0").unwrap();
+        let id = view.at_path(&path).unwrap();
+        let dep = lib.dependencies.iter().next().unwrap();
+        assert_eq!(id.as_bytes().unwrap(), dep.id.as_slice());
+        assert_eq!(id.as_raw().unwrap(), dep.id.as_slice());
+    }
+
+    #[test]
+    fn strings() {
+        let sys = test_system();
+        let data = serialize(&Nominal::with("TICK", "Some name", 2));
+        let view = sys.value_ref(TypeFqn::with("TestLib", "Nominal"), &data).unwrap();
+        let ticker = view.at_path(&Path::from_str(".ticker.0").unwrap()).unwrap();
+        assert_eq!(ticker.as_str().unwrap(), "TICK");
+        assert_eq!(ticker.as_raw().unwrap(), b"\x04TICK");
+        let name = view.at_step(&Step::from(fname!("name"))).unwrap();
+        assert_eq!(name.offset(), 5);
+        assert_eq!(name.as_str().unwrap(), "Some name");
+        assert_eq!(name.as_bytes(), Err(Error::NotBytes(name.sem_id())));
+        let precision = view.at_step(&Step::from(fname!("precision"))).unwrap();
+        assert_eq!(precision.as_str(), Err(Error::NotString(precision.sem_id())));
+        assert_eq!(precision.decode().unwrap(), StrictVal::enumer(vname!("twoDecimals")));
+    }
+
+    #[test]
+    fn errors() {
+        let sys = test_system();
+        let data = serialize(&Nominal::with("TICK", "Some name", 2));
+        let view = sys.value_ref(TypeFqn::with("TestLib", "Nominal"), &data[..8]).unwrap();
+        let name = view.at_step(&Step::from(fname!("name"))).unwrap();
+        assert_eq!(name.as_str(), Err(Error::UnexpectedEnd(8)));
+        assert_eq!(
+            view.at_step(&Step::from(fname!("precision"))).unwrap_err(),
+            Error::UnexpectedEnd(8)
+        );
+        assert_eq!(
+            view.at_step(&Step::from(fname!("absent"))).unwrap_err(),
+            Error::UnknownFieldName(fname!("absent"))
+        );
+        assert_eq!(
+            view.at_step(&Step::Index(0)).unwrap_err(),
+            Error::StepMismatch(Step::Index(0), view.sem_id())
+        );
+        assert_eq!(
+            sys.value_ref("TestLib.Absent", &data).unwrap_err(),
+            Error::TypeAbsent(TypeSpec::from("TestLib.Absent"))
+        );
+
+        let data = serialize(&strict_types_stl());
+        let view = sys.value_ref("StrictTypes.TypeLib", &data).unwrap();
+        let path = Path::from_str(".dependencies[1]").unwrap();
+        assert_eq!(view.at_path(&path).unwrap_err(), Error::CollectionIndexOutOfBounds(1, 1));
+
+        let mut data = serialize(&Nominal::with("TICK", "Some name", 2));
+        data[5] = 0x21;
+        let view = sys.value_ref(TypeFqn::with("TestLib", "Nominal"), &data).unwrap();
+        let name = view.at_step(&Step::from(fname!("name"))).unwrap();
+        assert_eq!(
+            name.as_str(),
+            Err(Error::LengthOutOfBounds {
+                offset: 5,
+                len: 0x21,
+                min: 1,
+                max: 32
+            })
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let sys = events_system("data Blob : [Byte ^ ..0xffffffff]\n");
+        let mut data = vec![0x01, 0x00, 0x01, 0x00];
+        data.resize(4 + 0x10001, 0xAB);
+        let view = sys.value_ref("Events.Blob", &data).unwrap();
+        let Error::Decode(err) = view.decode().unwrap_err() else {
+            panic!("decoding error is expected")
+        };
+        assert_eq!(err.kind, decode::ErrorKind::LengthLimit {
+            len: 0x10001,
+            max: 0x10000
+        });
+        let val = view.decode_limited(decode::DecodeLimits::unlimited()).unwrap();
+        assert_eq!(val, StrictVal::newtype(StrictVal::bytes(vec![0xAB; 0x10001])));
+    }
+
+    #[test]
+    fn maps() {
+        let sys = events_system("");
+        let data = [
+            0x02, 0x01, 0x00, 0x00, 0x03, 0x00, 0x04, 0x00, 0x00, 0x05, 0x00, 0x01, 0x2A, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07,
+        ];
+        let view = sys.value_ref("Events.Index", &data).unwrap();
+
+        let kind = view.at_path(&Path::from_str(".0{5}.kind").unwrap()).unwrap();
+        assert_eq!(kind.offset(), 20);
+        assert_eq!(kind.decode().unwrap(), StrictVal::enumer(vname!("custom")));
+        let at = view.at_path(&Path::from_str(".0{5}.at").unwrap()).unwrap();
+        assert_eq!(at.as_raw().unwrap(), &data[12..20]);
+        let no = view.at_path(&Path::from_str(".0{1}.1").unwrap()).unwrap();
+        assert_eq!(no.decode().unwrap(), StrictVal::num(4u64));
+        assert_eq!(
+            view.at_path(&Path::from_str(".0{9}").unwrap()).unwrap_err(),
+            Error::UnknownKey(KeyStep::Number(9))
+        );
+        assert!(matches!(
+            view.at_path(&Path::from_str(".0{1}.at").unwrap()),
+            Err(Error::StepMismatch(Step::NamedField(_), _))
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate strict_types;
+
+#[rustfmt::skip]
+#[path = "codegen/sample.rs"]
+mod sample;
+#[rustfmt::skip]
+#[allow(clippy::large_enum_variant)]
+#[path = "codegen/strict_types_lib.rs"]
+mod strict_types_lib;
+
+use std::fs;
+use std::process::Command;
+
+use amplify::confinement::Confined;
+use amplify::hex::ToHex;
+use amplify::num::u256;
+use strict_encoding::StrictSerialize;
+use strict_types::codegen::{RustGen, TsGen};
+use strict_types::stl::{std_stl, strict_types_stl};
+use strict_types::{LibBuilder, SymbolicLib, SymbolicSys, SystemBuilder, TypeLib};
+
+const SAMPLE: &str = "typelib Sample
+
+import Std#delete-roman-hair
+  use AlphaCapsLodash#duet-hammer-labor
+  use AlphaNumLodash#percent-bingo-caesar
+
+data Account : name Name, htlcID U64, type Kind, self [Byte ^ 64], amounts {U32 ^ 1..0xff}
+             , digests [Digest ^ 2..0xff], memo U16?
+             , title [Unicode ^ 2..0x20]
+             , meta {Name -> ^ ..0xffff [Byte]}, pair (U16, I8), big U256
+data Choice : many (items [Name]) | one (Name)
+data Digest : [Byte ^ 32]
+data Event : transfer (Digest, U24) | settled (at U64, kind Kind) | created ()
+data Kind : plain | htlcTimeout | custom#7
+data Name : (Std.AlphaCapsLodash, [Std.AlphaNumLodash ^ ..0x1f])
+";
+
+fn sample_lib() -> TypeLib {
+    SymbolicLib::from_source(SAMPLE, [&std_stl()]).unwrap().compile().unwrap()
+}
+
+#[test]
+fn sample_codegen() {
+    let code = RustGen::new().generate(&sample_lib()).unwrap();
+    assert_eq!(code, include_str!("codegen/sample.rs"));
+}
+
+#[test]
+fn sample_roundtrip() {
+    let lib = LibBuilder::new(libname!(sample::LIB_NAME_SAMPLE), [std_stl().to_dependency()])
+        .transpile::<sample::Account>()
+        .transpile::<sample::Choice>()
+        .transpile::<sample::Event>()
+        .compile()
+        .unwrap();
+    assert_eq!(lib, sample_lib());
+}
+
+fn sample_sys() -> SymbolicSys {
+    SystemBuilder::new()
+        .import(sample_lib())
+        .unwrap()
+        .import(std_stl())
+        .unwrap()
+        .finalize()
+        .unwrap()
+}
+
+#[test]
+fn typescript_codegen() {
+    let sys = sample_sys();
+    let code = TsGen::new().generate(&sys).unwrap();
+    assert_eq!(code, include_str!("codegen/sample.ts"));
+
+    let code = TsGen::new().with_runtime_import("./strict").generate(&sys).unwrap();
+    assert!(!code.contains("class StrictWriter"));
+    assert!(code.contains("} from \"./strict\";\n"));
+}
+
+impl StrictSerialize for sample::Account {}
+impl StrictSerialize for sample::Choice {}
+impl StrictSerialize for sample::Event {}
+
+fn has_tool(name: &str) -> bool {
+    Command::new(name).arg("--version").output().is_ok_and(|output| output.status.success())
+}
+
+/// Runs the generated TypeScript codecs under node against the data encoded by the Rust types
+/// generated for the same library. Requires `node` and `tsc`, thus runs only with `--ignored`.
+#[test]
+#[ignore = "requires node and tsc"]
+fn typescript_execution() {
+    for tool in ["node", "tsc"] {
+        assert!(has_tool(tool), "TypeScript execution test requires `{tool}` to be installed");
+    }
+
+    let name = |s: &'static str| sample::Name(s.into());
+    let account = sample::Account {
+        name: name("Alice"),
+        htlc_id: 7,
+        r#type: sample::Kind::Custom,
+        self_: [0xAB; 64],
+        amounts: Confined::from_iter_checked([5, 1]),
+        digests: Confined::from_checked(vec![sample::Digest([1; 32]), sample::Digest([2; 32])]),
+        memo: Some(9),
+        title: Confined::from_checked("Ünï".to_owned()),
+        meta: Confined::from_iter_checked([(name("Bob"), Confined::from_checked(vec![1, 2]))]),
+        pair: (300, -5),
+        big: u256::from(1u64) << 200,
+    };
+    let account = account.to_strict_serialized::<{ usize::MAX }>().unwrap().to_hex();
+    // The same account with the set of amounts in the wrong order
+    let unordered = account.replacen("020100000005000000", "020500000001000000", 1);
+    assert_ne!(unordered, account);
+    let choice = sample::Choice::One(name("Alice"));
+    let settled = sample::Event::Settled {
+        at: 5,
+        kind: sample::Kind::HtlcTimeout,
+    };
+    let created = sample::Event::Created;
+
+    let checks = [
+        (
+            "Account",
+            account,
+            "v.name === \"Alice\" && v.htlcID === 7n && v.type === \"custom\" && v.amounts.join() \
+             === \"1,5\" && v.digests[1][0] === 2 && v.memo === 9 && v.title === \"Ünï\" && \
+             v.meta[0][0] === \"Bob\" && v.pair[0] === 300 && v.pair[1] === -5 && v.big === 1n << \
+             200n",
+        ),
+        (
+            "Choice",
+            choice.to_strict_serialized::<{ usize::MAX }>().unwrap().to_hex(),
+            "v.type === \"one\" && v.value === \"Alice\"",
+        ),
+        (
+            "Event",
+            settled.to_strict_serialized::<{ usize::MAX }>().unwrap().to_hex(),
+            "v.type === \"settled\" && v.value.at === 5n && v.value.kind === \"htlcTimeout\"",
+        ),
+        (
+            "Event",
+            created.to_strict_serialized::<{ usize::MAX }>().unwrap().to_hex(),
+            "v.type === \"created\"",
+        ),
+    ];
+    let mut script = String::from(
+        r#"import * as s from "./sample";
+
+function bytes(hex: string): Uint8Array {
+  return Uint8Array.from(hex.match(/../g) ?? [], (byte) => parseInt(byte, 16));
+}
+
+function hex(data: Uint8Array): string {
+  return Array.from(data, (byte) => byte.toString(16).padStart(2, "0")).join("");
+}
+
+function check<T>(name: string, dec: s.Decoder<T>, enc: s.Encoder<T>, data: string,
+                  test: (v: T) => boolean): void {
+  const v = s.strictDeserialize(dec, bytes(data));
+  if (!test(v)) throw new Error(`${name}: unexpected value decoded from ${data}`);
+  const encoded = hex(s.strictSerialize(enc, v));
+  if (encoded !== data) throw new Error(`${name}: ${data} was re-encoded as ${encoded}`);
+}
+
+function reject<T>(name: string, dec: s.Decoder<T>, data: string): void {
+  try {
+    s.strictDeserialize(dec, bytes(data));
+  } catch (err) {
+    if (err instanceof s.StrictError) return;
+    throw err;
+  }
+  throw new Error(`${name}: invalid data ${data} were accepted`);
+}
+
+"#,
+    );
+    for (ty, data, test) in checks {
+        script.push_str(&format!(
+            "check(\"{ty}\", s.decode{ty}, s.encode{ty}, \"{data}\", (v: s.{ty}) => {test});\n"
+        ));
+    }
+    script.push_str(&format!("reject(\"Account\", s.decodeAccount, \"{unordered}\");\n"));
+
+    let dir = std::env::temp_dir().join(format!("strict-types-ts-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("sample.ts"), TsGen::new().generate(&sample_sys()).unwrap()).unwrap();
+    fs::write(dir.join("test.ts"), script).unwrap();
+    let run = |cmd: &mut Command| {
+        let output = cmd.current_dir(&dir).output().unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "{stdout}{stderr}");
+    };
+    run(Command::new("tsc").args([
+        "--strict", "--target", "es2020", "--module", "commonjs", "--outDir", "out", "test.ts",
+    ]));
+    run(Command::new("node").arg("out/test.js"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn strict_types_codegen() {
+    let code = RustGen::new().generate(&strict_types_stl()).unwrap();
+    assert_eq!(code, include_str!("codegen/strict_types_lib.rs"));
+}
+
+#[test]
+fn strict_types_roundtrip() {
+    use strict_types_lib as stl;
+
+    let lib = LibBuilder::new(libname!(stl::LIB_NAME_STRICT_TYPES), [std_stl().to_dependency()])
+        .transpile::<stl::Ident>()
+        .transpile::<stl::TypeName>()
+        .transpile::<stl::FieldName>()
+        .transpile::<stl::VariantName>()
+        .transpile::<stl::LibName>()
+        .transpile::<stl::SymbolRef>()
+        .transpile::<stl::TypeLib>()
+        .transpile::<stl::TypeSysId>()
+        .transpile::<stl::TypeSymbol>()
+        .transpile::<stl::SymbolicSys>()
+        .transpile::<stl::MemoryLayout>()
+        .compile()
+        .unwrap();
+    let original = strict_types_stl();
+    assert_eq!(lib.id(), original.id());
+    assert_eq!(lib, original);
+}
@@ -0,0 +1,105 @@
+// Rust types for the strict type library `Sample`.
+// Generated by strict_types; do not edit manually.
+
+use std::collections::BTreeSet;
+
+use amplify::confinement::{Confined, SmallBlob, SmallOrdMap, SmallVec};
+use amplify::num::{u24, u256};
+use strict_encoding::{strict_dumb, RString, StrictDecode, StrictDumb, StrictEncode, StrictType};
+use strict_encoding::stl::{AlphaCapsLodash, AlphaNumLodash};
+
+pub const LIB_NAME_SAMPLE: &str = "Sample";
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SAMPLE)]
+pub struct Account {
+    pub name: Name,
+    #[strict_type(rename = "htlcID")]
+    pub htlc_id: u64,
+    #[strict_type(rename = "type")]
+    pub r#type: Kind,
+    #[strict_type(rename = "self")]
+    pub self_: [u8; 64],
+    pub amounts: Confined<BTreeSet<u32>, 1, 255>,
+    pub digests: Confined<Vec<Digest>, 2, 255>,
+    pub memo: Option<u16>,
+    pub title: Confined<String, 2, 32>,
+    pub meta: SmallOrdMap<Name, SmallBlob>,
+    pub pair: (u16, i8),
+    pub big: u256,
+}
+
+impl StrictDumb for Account {
+    fn strict_dumb() -> Self {
+        Self {
+            name: strict_dumb!(),
+            htlc_id: strict_dumb!(),
+            r#type: strict_dumb!(),
+            self_: [strict_dumb!(); 64],
+            amounts: Confined::from_iter_checked([strict_dumb!()]),
+            digests: Confined::from_iter_checked(std::iter::repeat_with(|| strict_dumb!()).take(2)),
+            memo: strict_dumb!(),
+            title: Confined::from_checked(" ".repeat(2)),
+            meta: strict_dumb!(),
+            pair: strict_dumb!(),
+            big: strict_dumb!(),
+        }
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SAMPLE, tags = custom)]
+pub enum Choice {
+    #[strict_type(tag = 0)]
+    Many {
+        items: SmallVec<Name>,
+    },
+    #[strict_type(tag = 1)]
+    One(Name),
+}
+
+impl StrictDumb for Choice {
+    fn strict_dumb() -> Self {
+        Self::Many {
+            items: strict_dumb!(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SAMPLE)]
+pub struct Digest(pub [u8; 32]);
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SAMPLE, tags = custom)]
+pub enum Event {
+    #[strict_type(tag = 0)]
+    Transfer(Digest, u24),
+    #[strict_type(tag = 1)]
+    Settled {
+        at: u64,
+        kind: Kind,
+    },
+    #[strict_type(tag = 2, dumb)]
+    Created,
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SAMPLE, tags = repr, into_u8, try_from_u8)]
+#[repr(u8)]
+pub enum Kind {
+    #[strict_type(dumb)]
+    Plain = 0,
+    HtlcTimeout = 1,
+    Custom = 7,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SAMPLE)]
+pub struct Name(pub RString<AlphaCapsLodash, AlphaNumLodash, 1, 32>);
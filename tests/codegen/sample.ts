@@ -0,0 +1,3610 @@
+// TypeScript types for the strict type system `sts:~kckngjA-dhnlZX2-JqCOQDe-WmsNRdS-iUYrVhW-IqLOujY#ritual-convert-lucky`.
+// Generated by strict_types; do not edit manually.
+
+export class StrictError extends Error {}
+
+export type Encoder<T> = (w: StrictWriter, v: T) => void;
+export type Decoder<T> = (r: StrictReader) => T;
+export type Comparator<T> = (a: T, b: T) => number;
+
+const utf8Encoder = new TextEncoder();
+const utf8Decoder = new TextDecoder("utf-8", { fatal: true, ignoreBOM: true });
+
+function fail(msg: string): never {
+  throw new StrictError(msg);
+}
+
+function hasKey(obj: object, key: PropertyKey): boolean {
+  return Object.prototype.hasOwnProperty.call(obj, key);
+}
+
+function latin1(data: Uint8Array): string {
+  return Array.from(data, (byte) => String.fromCharCode(byte)).join("");
+}
+
+function checkLen(len: number, min: number, max: number): void {
+  if (len < min || len > max) {
+    fail(`collection length ${len} is out of bounds ${min}..=${max}`);
+  }
+}
+
+function checkChars(s: string, first: string, rest: string): void {
+  [...s].forEach((c, pos) => {
+    if (!(pos === 0 ? first : rest).includes(c)) {
+      fail(`character '${c}' is not allowed at position ${pos}`);
+    }
+  });
+}
+
+export class StrictWriter {
+  private buf: number[] = [];
+
+  toBytes(): Uint8Array {
+    return Uint8Array.from(this.buf);
+  }
+
+  raw(data: Uint8Array): void {
+    data.forEach((byte) => this.buf.push(byte));
+  }
+
+  unit(v: null): void {
+    if (v !== null) fail("unit value must be null");
+  }
+
+  int(v: number | bigint, len: number, signed: boolean, nonZero = false): void {
+    if (typeof v === "number" && !Number.isSafeInteger(v)) fail(`${v} is not an integer`);
+    const bits = BigInt(len * 8);
+    const min = signed ? -(1n << (bits - 1n)) : 0n;
+    const max = (signed ? 1n << (bits - 1n) : 1n << bits) - 1n;
+    let n = BigInt(v);
+    if (n < min || n > max) fail(`integer ${v} doesn't fit into ${len} byte(s)`);
+    if (nonZero && n === 0n) fail("zero value for a non-zero integer");
+    if (n < 0n) n += 1n << bits;
+    for (let i = 0; i < len; i++) {
+      this.buf.push(Number(n & 0xffn));
+      n >>= 8n;
+    }
+  }
+
+  f32(v: number): void {
+    const view = new DataView(new ArrayBuffer(4));
+    view.setFloat32(0, v, true);
+    this.raw(new Uint8Array(view.buffer));
+  }
+
+  f64(v: number): void {
+    const view = new DataView(new ArrayBuffer(8));
+    view.setFloat64(0, v, true);
+    this.raw(new Uint8Array(view.buffer));
+  }
+
+  char(v: string): void {
+    if ([...v].length !== 1) fail(`'${v}' is not a single character`);
+    this.raw(utf8Encoder.encode(v));
+  }
+
+  len(len: number, min: number, max: number, width: number): void {
+    checkLen(len, min, max);
+    this.int(len, width, false);
+  }
+
+  enum<T extends string>(v: T, tags: Record<T, number>): void {
+    if (!hasKey(tags, v)) fail(`unknown variant '${v}'`);
+    this.int(tags[v], 1, false);
+  }
+
+  option<T>(v: T | null, enc: Encoder<T>): void {
+    this.int(v === null ? 0 : 1, 1, false);
+    if (v !== null) enc(this, v);
+  }
+
+  bytes(v: Uint8Array, len: number): void {
+    if (v.length !== len) fail(`byte array must have length ${len} and not ${v.length}`);
+    this.raw(v);
+  }
+
+  blob(v: Uint8Array, min: number, max: number, width: number): void {
+    this.len(v.length, min, max, width);
+    this.raw(v);
+  }
+
+  string(v: string, min: number, max: number, width: number): void {
+    this.blob(utf8Encoder.encode(v), min, max, width);
+  }
+
+  ascii(v: string, min: number, max: number, width: number, chars: string): void {
+    checkChars(v, chars, chars);
+    this.blob(utf8Encoder.encode(v), min, max, width);
+  }
+
+  rstring(v: string, min: number, max: number, width: number, first: string, rest: string): void {
+    checkChars(v, first, rest);
+    this.blob(utf8Encoder.encode(v), min, max, width);
+  }
+
+  array<T>(v: T[], len: number, enc: Encoder<T>): void {
+    if (v.length !== len) fail(`array must have length ${len} and not ${v.length}`);
+    v.forEach((item) => enc(this, item));
+  }
+
+  list<T>(v: T[], min: number, max: number, width: number, enc: Encoder<T>): void {
+    this.len(v.length, min, max, width);
+    v.forEach((item) => enc(this, item));
+  }
+
+  set<T>(
+    v: T[],
+    min: number,
+    max: number,
+    width: number,
+    enc: Encoder<T>,
+    cmp: Comparator<T>,
+  ): void {
+    const sorted = [...v].sort(cmp);
+    sorted.forEach((item, pos) => {
+      if (pos > 0 && cmp(sorted[pos - 1], item) === 0) fail("repeated set element");
+    });
+    this.list(sorted, min, max, width, enc);
+  }
+
+  map<K, V>(
+    v: Array<[K, V]>,
+    min: number,
+    max: number,
+    width: number,
+    encKey: Encoder<K>,
+    encVal: Encoder<V>,
+    cmpKey: Comparator<K>,
+  ): void {
+    const sorted = [...v].sort((a, b) => cmpKey(a[0], b[0]));
+    sorted.forEach(([key], pos) => {
+      if (pos > 0 && cmpKey(sorted[pos - 1][0], key) === 0) fail("repeated map key");
+    });
+    this.len(sorted.length, min, max, width);
+    sorted.forEach(([key, val]) => {
+      encKey(this, key);
+      encVal(this, val);
+    });
+  }
+}
+
+export class StrictReader {
+  private readonly data: Uint8Array;
+  private pos = 0;
+
+  constructor(data: Uint8Array) {
+    this.data = data;
+  }
+
+  raw(len: number): Uint8Array {
+    if (this.pos + len > this.data.length) fail("unexpected end of data");
+    const slice = this.data.slice(this.pos, this.pos + len);
+    this.pos += len;
+    return slice;
+  }
+
+  end(): void {
+    if (this.pos !== this.data.length) fail("data are not entirely consumed");
+  }
+
+  unit(): null {
+    return null;
+  }
+
+  big(len: number, signed: boolean, nonZero = false): bigint {
+    const bytes = this.raw(len);
+    let n = 0n;
+    for (let i = len - 1; i >= 0; i--) n = (n << 8n) | BigInt(bytes[i]);
+    if (signed && bytes[len - 1] & 0x80) n -= 1n << BigInt(len * 8);
+    if (nonZero && n === 0n) fail("zero value for a non-zero integer");
+    return n;
+  }
+
+  int(len: number, signed: boolean, nonZero = false): number {
+    return Number(this.big(len, signed, nonZero));
+  }
+
+  f32(): number {
+    return new DataView(this.raw(4).buffer).getFloat32(0, true);
+  }
+
+  f64(): number {
+    return new DataView(this.raw(8).buffer).getFloat64(0, true);
+  }
+
+  char(): string {
+    const [first] = this.raw(1);
+    const ones = Math.clz32(~(first << 24));
+    const rest = this.raw(ones >= 2 && ones <= 4 ? ones - 1 : 0);
+    const s = this.utf8(Uint8Array.of(first, ...rest));
+    if ([...s].length !== 1) fail("invalid unicode character");
+    return s;
+  }
+
+  len(min: number, max: number, width: number): number {
+    const len = this.int(width, false);
+    checkLen(len, min, max);
+    return len;
+  }
+
+  enum<T extends string>(tags: Record<T, number>): T {
+    const tag = this.int(1, false);
+    const entry = (Object.entries(tags) as Array<[T, number]>).find(([, t]) => t === tag);
+    return entry ? entry[0] : fail(`unknown tag ${tag}`);
+  }
+
+  option<T>(dec: Decoder<T>): T | null {
+    const tag = this.int(1, false);
+    if (tag > 1) fail(`unknown option tag ${tag}`);
+    return tag === 0 ? null : dec(this);
+  }
+
+  bytes(len: number): Uint8Array {
+    return this.raw(len);
+  }
+
+  blob(min: number, max: number, width: number): Uint8Array {
+    return this.raw(this.len(min, max, width));
+  }
+
+  string(min: number, max: number, width: number): string {
+    return this.utf8(this.blob(min, max, width));
+  }
+
+  ascii(min: number, max: number, width: number, chars: string): string {
+    const s = latin1(this.blob(min, max, width));
+    checkChars(s, chars, chars);
+    return s;
+  }
+
+  rstring(min: number, max: number, width: number, first: string, rest: string): string {
+    const s = latin1(this.blob(min, max, width));
+    checkChars(s, first, rest);
+    return s;
+  }
+
+  array<T>(len: number, dec: Decoder<T>): T[] {
+    return Array.from({ length: len }, () => dec(this));
+  }
+
+  list<T>(min: number, max: number, width: number, dec: Decoder<T>): T[] {
+    return this.array(this.len(min, max, width), dec);
+  }
+
+  set<T>(min: number, max: number, width: number, dec: Decoder<T>, cmp: Comparator<T>): T[] {
+    const set = this.list(min, max, width, dec);
+    set.forEach((item, pos) => {
+      if (pos > 0 && cmp(set[pos - 1], item) >= 0) fail("set elements are repeated or unordered");
+    });
+    return set;
+  }
+
+  map<K, V>(
+    min: number,
+    max: number,
+    width: number,
+    decKey: Decoder<K>,
+    decVal: Decoder<V>,
+    cmpKey: Comparator<K>,
+  ): Array<[K, V]> {
+    const map = this.array(this.len(min, max, width), (r): [K, V] => [decKey(r), decVal(r)]);
+    map.forEach(([key], pos) => {
+      if (pos > 0 && cmpKey(map[pos - 1][0], key) >= 0) fail("map keys are repeated or unordered");
+    });
+    return map;
+  }
+
+  private utf8(data: Uint8Array): string {
+    try {
+      return utf8Decoder.decode(data);
+    } catch {
+      return fail("invalid UTF-8 string");
+    }
+  }
+}
+
+export function cmpNum(a: number | bigint, b: number | bigint): number {
+  return a < b ? -1 : a > b ? 1 : 0;
+}
+
+export function cmpBytes(a: Uint8Array, b: Uint8Array): number {
+  for (let i = 0; i < Math.min(a.length, b.length); i++) {
+    if (a[i] !== b[i]) return a[i] - b[i];
+  }
+  return a.length - b.length;
+}
+
+export function cmpStr(a: string, b: string): number {
+  return cmpBytes(utf8Encoder.encode(a), utf8Encoder.encode(b));
+}
+
+export function cmpTags<T extends string>(a: T, b: T, tags: Record<T, number>): number {
+  return tags[a] - tags[b];
+}
+
+export function cmpOption<T>(a: T | null, b: T | null, cmp: Comparator<T>): number {
+  if (a === null || b === null) return (a === null ? 0 : 1) - (b === null ? 0 : 1);
+  return cmp(a, b);
+}
+
+export function cmpList<T>(a: T[], b: T[], cmp: Comparator<T>): number {
+  for (let i = 0; i < Math.min(a.length, b.length); i++) {
+    const ord = cmp(a[i], b[i]);
+    if (ord !== 0) return ord;
+  }
+  return a.length - b.length;
+}
+
+export function cmpMap<K, V>(
+  a: Array<[K, V]>,
+  b: Array<[K, V]>,
+  cmpKey: Comparator<K>,
+  cmpVal: Comparator<V>,
+): number {
+  return cmpList(a, b, (x, y) => cmpKey(x[0], y[0]) || cmpVal(x[1], y[1]));
+}
+
+export function strictSerialize<T>(enc: Encoder<T>, v: T): Uint8Array {
+  const w = new StrictWriter();
+  enc(w, v);
+  return w.toBytes();
+}
+
+export function strictDeserialize<T>(dec: Decoder<T>, data: Uint8Array): T {
+  const r = new StrictReader(data);
+  const v = dec(r);
+  r.end();
+  return v;
+}
+
+/** Type `Sample.Account` with semantic id `semid:lxyUrf8K-jsnvYC1-eLA~aZa-sdoLu3b-nThad5i-_WSRn30#opinion-east-senator`. */
+export interface Account {
+  name: Name;
+  htlcID: bigint;
+  type: Kind;
+  self: Uint8Array;
+  amounts: Array<number>;
+  digests: Array<Digest>;
+  memo: number | null;
+  title: string;
+  meta: Array<[Name, Uint8Array]>;
+  pair: [number, number];
+  big: bigint;
+}
+
+export function encodeAccount(w: StrictWriter, v: Account): void {
+  encodeName(w, v.name);
+  w.int(v.htlcID, 8, false);
+  encodeKind(w, v.type);
+  w.bytes(v.self, 64);
+  w.set(v.amounts, 1, 255, 1, (w, v) => w.int(v, 4, false), cmpNum);
+  w.list(v.digests, 2, 255, 1, encodeDigest);
+  w.option(v.memo, (w, v) => w.int(v, 2, false));
+  w.string(v.title, 2, 32, 1);
+  w.map(v.meta, 0, 65535, 2, encodeName, (w, v) => w.blob(v, 0, 65535, 2), compareName);
+  w.int(v.pair[0], 2, false);
+  w.int(v.pair[1], 1, true);
+  w.int(v.big, 32, false);
+}
+
+export function decodeAccount(r: StrictReader): Account {
+  return {
+    name: decodeName(r),
+    htlcID: r.big(8, false),
+    type: decodeKind(r),
+    self: r.bytes(64),
+    amounts: r.set(1, 255, 1, (r): number => r.int(4, false), cmpNum),
+    digests: r.list(2, 255, 1, decodeDigest),
+    memo: r.option((r): number => r.int(2, false)),
+    title: r.string(2, 32, 1),
+    meta: r.map(0, 65535, 2, decodeName, (r): Uint8Array => r.blob(0, 65535, 2), compareName),
+    pair: [r.int(2, false), r.int(1, true)],
+    big: r.big(32, false),
+  };
+}
+
+export function compareAccount(a: Account, b: Account): number {
+  return (
+    compareName(a.name, b.name) ||
+    cmpNum(a.htlcID, b.htlcID) ||
+    compareKind(a.type, b.type) ||
+    cmpBytes(a.self, b.self) ||
+    cmpList(a.amounts, b.amounts, cmpNum) ||
+    cmpList(a.digests, b.digests, compareDigest) ||
+    cmpOption(a.memo, b.memo, cmpNum) ||
+    cmpStr(a.title, b.title) ||
+    cmpMap(a.meta, b.meta, compareName, cmpBytes) ||
+    (cmpNum(a.pair[0], b.pair[0]) || cmpNum(a.pair[1], b.pair[1])) ||
+    cmpNum(a.big, b.big)
+  );
+}
+
+/** Type `Sample.Choice` with semantic id `semid:zv0OGnv~-8DUIXHN-HHcghXd-~0LdDuz-dszDTLO-wm1ynn4#avatar-member-culture`. */
+export type Choice =
+  | { type: "many"; value: { items: Array<Name> } }
+  | { type: "one"; value: Name };
+
+const ChoiceTags: Record<Choice["type"], number> = {
+  many: 0,
+  one: 1,
+};
+
+export function encodeChoice(w: StrictWriter, v: Choice): void {
+  w.enum(v.type, ChoiceTags);
+  switch (v.type) {
+    case "many":
+      w.list(v.value.items, 0, 65535, 2, encodeName);
+      break;
+    case "one":
+      encodeName(w, v.value);
+      break;
+  }
+}
+
+export function decodeChoice(r: StrictReader): Choice {
+  const type = r.enum(ChoiceTags);
+  switch (type) {
+    case "many":
+      return { type, value: { items: r.list(0, 65535, 2, decodeName) } };
+    case "one":
+      return { type, value: decodeName(r) };
+  }
+}
+
+export function compareChoice(a: Choice, b: Choice): number {
+  if (a.type === "many" && b.type === "many") {
+    return cmpList(a.value.items, b.value.items, compareName);
+  }
+  if (a.type === "one" && b.type === "one") {
+    return compareName(a.value, b.value);
+  }
+  return cmpTags(a.type, b.type, ChoiceTags);
+}
+
+/** Type `Sample.Digest` with semantic id `semid:mU~~A9zm-HF9jhnt-ekEiILh-U0EBm3n-gAdVn8M-y1SWiQw#silk-denver-nobody`. */
+export type Digest = Uint8Array;
+
+export function encodeDigest(w: StrictWriter, v: Digest): void {
+  w.bytes(v, 32);
+}
+
+export function decodeDigest(r: StrictReader): Digest {
+  return r.bytes(32);
+}
+
+export function compareDigest(a: Digest, b: Digest): number {
+  return cmpBytes(a, b);
+}
+
+/** Type `Sample.Event` with semantic id `semid:oWHYeXNW-Oeb2tUN-PED7ho~-iwj3XKK-IaWoWe4-s~bfD74#correct-brandy-record`. */
+export type Event =
+  | { type: "transfer"; value: [Digest, number] }
+  | { type: "settled"; value: { at: bigint; kind: Kind } }
+  | { type: "created" };
+
+const EventTags: Record<Event["type"], number> = {
+  transfer: 0,
+  settled: 1,
+  created: 2,
+};
+
+export function encodeEvent(w: StrictWriter, v: Event): void {
+  w.enum(v.type, EventTags);
+  switch (v.type) {
+    case "transfer":
+      encodeDigest(w, v.value[0]);
+      w.int(v.value[1], 3, false);
+      break;
+    case "settled":
+      w.int(v.value.at, 8, false);
+      encodeKind(w, v.value.kind);
+      break;
+  }
+}
+
+export function decodeEvent(r: StrictReader): Event {
+  const type = r.enum(EventTags);
+  switch (type) {
+    case "transfer":
+      return { type, value: [decodeDigest(r), r.int(3, false)] };
+    case "settled":
+      return { type, value: { at: r.big(8, false), kind: decodeKind(r) } };
+    case "created":
+      return { type };
+  }
+}
+
+export function compareEvent(a: Event, b: Event): number {
+  if (a.type === "transfer" && b.type === "transfer") {
+    return (compareDigest(a.value[0], b.value[0]) || cmpNum(a.value[1], b.value[1]));
+  }
+  if (a.type === "settled" && b.type === "settled") {
+    return (cmpNum(a.value.at, b.value.at) || compareKind(a.value.kind, b.value.kind));
+  }
+  return cmpTags(a.type, b.type, EventTags);
+}
+
+/** Type `Sample.Kind` with semantic id `semid:K8WwYSGd-z1xzvf5-HWSCbrc-HaXPpNs-6Zpzv1G-0m1dHeQ#exhibit-initial-echo`. */
+export type Kind =
+  | "plain"
+  | "htlcTimeout"
+  | "custom";
+
+const KindTags: Record<Kind, number> = {
+  plain: 0,
+  htlcTimeout: 1,
+  custom: 7,
+};
+
+export function encodeKind(w: StrictWriter, v: Kind): void {
+  w.enum(v, KindTags);
+}
+
+export function decodeKind(r: StrictReader): Kind {
+  return r.enum(KindTags);
+}
+
+export function compareKind(a: Kind, b: Kind): number {
+  return cmpTags(a, b, KindTags);
+}
+
+/** Type `Sample.Name` with semantic id `semid:LJxvH4Fn-bWa6I3L-up13Cf9-Kff0Wmz-pUvDnAT-8q9qWoA#beast-chris-october`. */
+export type Name = string;
+
+export function encodeName(w: StrictWriter, v: Name): void {
+  w.rstring(v, 1, 32, 1, "ABCDEFGHIJKLMNOPQRSTUVWXYZ_", "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz");
+}
+
+export function decodeName(r: StrictReader): Name {
+  return r.rstring(1, 32, 1, "ABCDEFGHIJKLMNOPQRSTUVWXYZ_", "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz");
+}
+
+export function compareName(a: Name, b: Name): number {
+  return cmpStr(a, b);
+}
+
+/** Type `Std.Alpha` with semantic id `semid:giOA9HXw-7bS13FF-5kd5zkK-2i27N1L-EwGaFGq-AWMClsI#citizen-bicycle-stretch`. */
+export type Alpha =
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaTags: Record<Alpha, number> = {
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlpha(w: StrictWriter, v: Alpha): void {
+  w.enum(v, AlphaTags);
+}
+
+export function decodeAlpha(r: StrictReader): Alpha {
+  return r.enum(AlphaTags);
+}
+
+export function compareAlpha(a: Alpha, b: Alpha): number {
+  return cmpTags(a, b, AlphaTags);
+}
+
+/** Type `Std.AlphaCaps` with semantic id `semid:J4TRjGsO-xjyvXxj-~NL6np1-nzJ1dCa-nz47T6a-ch1MNpg#picnic-soprano-aurora`. */
+export type AlphaCaps =
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z";
+
+const AlphaCapsTags: Record<AlphaCaps, number> = {
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+};
+
+export function encodeAlphaCaps(w: StrictWriter, v: AlphaCaps): void {
+  w.enum(v, AlphaCapsTags);
+}
+
+export function decodeAlphaCaps(r: StrictReader): AlphaCaps {
+  return r.enum(AlphaCapsTags);
+}
+
+export function compareAlphaCaps(a: AlphaCaps, b: AlphaCaps): number {
+  return cmpTags(a, b, AlphaCapsTags);
+}
+
+/** Type `Std.AlphaCapsDash` with semantic id `semid:kPRYVtkS-1gQ5npz-1CsxNOU-l36T6Rq-pam1kOl-L5me5gk#pilot-finland-lotus`. */
+export type AlphaCapsDash =
+  | "dash"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z";
+
+const AlphaCapsDashTags: Record<AlphaCapsDash, number> = {
+  dash: 45,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+};
+
+export function encodeAlphaCapsDash(w: StrictWriter, v: AlphaCapsDash): void {
+  w.enum(v, AlphaCapsDashTags);
+}
+
+export function decodeAlphaCapsDash(r: StrictReader): AlphaCapsDash {
+  return r.enum(AlphaCapsDashTags);
+}
+
+export function compareAlphaCapsDash(a: AlphaCapsDash, b: AlphaCapsDash): number {
+  return cmpTags(a, b, AlphaCapsDashTags);
+}
+
+/** Type `Std.AlphaCapsDot` with semantic id `semid:8nxR8vZm-I6WUpLf-IqHA5f8-yOmcZb5-MUpKN0E-nDByxo0#ferrari-scroll-rival`. */
+export type AlphaCapsDot =
+  | "dot"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z";
+
+const AlphaCapsDotTags: Record<AlphaCapsDot, number> = {
+  dot: 46,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+};
+
+export function encodeAlphaCapsDot(w: StrictWriter, v: AlphaCapsDot): void {
+  w.enum(v, AlphaCapsDotTags);
+}
+
+export function decodeAlphaCapsDot(r: StrictReader): AlphaCapsDot {
+  return r.enum(AlphaCapsDotTags);
+}
+
+export function compareAlphaCapsDot(a: AlphaCapsDot, b: AlphaCapsDot): number {
+  return cmpTags(a, b, AlphaCapsDotTags);
+}
+
+/** Type `Std.AlphaCapsLodash` with semantic id `semid:uYN_50Cu-khPa6zR-kkv2lXi-C4ooNJP-BS4s8x0-x52H_a8#duet-hammer-labor`. */
+export type AlphaCapsLodash =
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "lodash";
+
+const AlphaCapsLodashTags: Record<AlphaCapsLodash, number> = {
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  lodash: 95,
+};
+
+export function encodeAlphaCapsLodash(w: StrictWriter, v: AlphaCapsLodash): void {
+  w.enum(v, AlphaCapsLodashTags);
+}
+
+export function decodeAlphaCapsLodash(r: StrictReader): AlphaCapsLodash {
+  return r.enum(AlphaCapsLodashTags);
+}
+
+export function compareAlphaCapsLodash(a: AlphaCapsLodash, b: AlphaCapsLodash): number {
+  return cmpTags(a, b, AlphaCapsLodashTags);
+}
+
+/** Type `Std.AlphaCapsNum` with semantic id `semid:ekeGpQ9P-taHlCgO-nuwDeOk-uuEfEDi-K4DM2MW-uTmlUWw#aladdin-zebra-marble`. */
+export type AlphaCapsNum =
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z";
+
+const AlphaCapsNumTags: Record<AlphaCapsNum, number> = {
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+};
+
+export function encodeAlphaCapsNum(w: StrictWriter, v: AlphaCapsNum): void {
+  w.enum(v, AlphaCapsNumTags);
+}
+
+export function decodeAlphaCapsNum(r: StrictReader): AlphaCapsNum {
+  return r.enum(AlphaCapsNumTags);
+}
+
+export function compareAlphaCapsNum(a: AlphaCapsNum, b: AlphaCapsNum): number {
+  return cmpTags(a, b, AlphaCapsNumTags);
+}
+
+/** Type `Std.AlphaDash` with semantic id `semid:DBd3IiiL-N8_ZpNI-ntN7djQ-1Xi8Zzy-TOxWRQL-Dde1~wc#modem-parker-content`. */
+export type AlphaDash =
+  | "dash"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaDashTags: Record<AlphaDash, number> = {
+  dash: 45,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaDash(w: StrictWriter, v: AlphaDash): void {
+  w.enum(v, AlphaDashTags);
+}
+
+export function decodeAlphaDash(r: StrictReader): AlphaDash {
+  return r.enum(AlphaDashTags);
+}
+
+export function compareAlphaDash(a: AlphaDash, b: AlphaDash): number {
+  return cmpTags(a, b, AlphaDashTags);
+}
+
+/** Type `Std.AlphaDot` with semantic id `semid:mdI0P9Gd-m3dfFPT-G5fOdJ7-TG6s4LP-OiKQmNT-AM6yaxk#story-sensor-voice`. */
+export type AlphaDot =
+  | "dot"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaDotTags: Record<AlphaDot, number> = {
+  dot: 46,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaDot(w: StrictWriter, v: AlphaDot): void {
+  w.enum(v, AlphaDotTags);
+}
+
+export function decodeAlphaDot(r: StrictReader): AlphaDot {
+  return r.enum(AlphaDotTags);
+}
+
+export function compareAlphaDot(a: AlphaDot, b: AlphaDot): number {
+  return cmpTags(a, b, AlphaDotTags);
+}
+
+/** Type `Std.AlphaLodash` with semantic id `semid:gxvLDDKG-CPP5zRZ-jPBao5q-Uqwxx5p-hBCvp2G-S8n0oPc#halt-alamo-mimic`. */
+export type AlphaLodash =
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "lodash"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaLodashTags: Record<AlphaLodash, number> = {
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  lodash: 95,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaLodash(w: StrictWriter, v: AlphaLodash): void {
+  w.enum(v, AlphaLodashTags);
+}
+
+export function decodeAlphaLodash(r: StrictReader): AlphaLodash {
+  return r.enum(AlphaLodashTags);
+}
+
+export function compareAlphaLodash(a: AlphaLodash, b: AlphaLodash): number {
+  return cmpTags(a, b, AlphaLodashTags);
+}
+
+/** Type `Std.AlphaNum` with semantic id `semid:X14m5cUF-PBtFRFF-bxqBlPa-AqB5H7M-RFtcaT6-2RbhU1U#window-tractor-alamo`. */
+export type AlphaNum =
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaNumTags: Record<AlphaNum, number> = {
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaNum(w: StrictWriter, v: AlphaNum): void {
+  w.enum(v, AlphaNumTags);
+}
+
+export function decodeAlphaNum(r: StrictReader): AlphaNum {
+  return r.enum(AlphaNumTags);
+}
+
+export function compareAlphaNum(a: AlphaNum, b: AlphaNum): number {
+  return cmpTags(a, b, AlphaNumTags);
+}
+
+/** Type `Std.AlphaNumDash` with semantic id `semid:eGl4ZrJr-ul5Q~_m-jEZHsFx-PN_FJAq-HNAMca1-raY6pDo#sponsor-snake-nice`. */
+export type AlphaNumDash =
+  | "dash"
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaNumDashTags: Record<AlphaNumDash, number> = {
+  dash: 45,
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaNumDash(w: StrictWriter, v: AlphaNumDash): void {
+  w.enum(v, AlphaNumDashTags);
+}
+
+export function decodeAlphaNumDash(r: StrictReader): AlphaNumDash {
+  return r.enum(AlphaNumDashTags);
+}
+
+export function compareAlphaNumDash(a: AlphaNumDash, b: AlphaNumDash): number {
+  return cmpTags(a, b, AlphaNumDashTags);
+}
+
+/** Type `Std.AlphaNumDot` with semantic id `semid:G5pbkdQ~-maPI0BO-kRfCZuG-Hcto~OJ-Inhiao1-h7r6f1E#bambino-gorilla-brave`. */
+export type AlphaNumDot =
+  | "dot"
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaNumDotTags: Record<AlphaNumDot, number> = {
+  dot: 46,
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaNumDot(w: StrictWriter, v: AlphaNumDot): void {
+  w.enum(v, AlphaNumDotTags);
+}
+
+export function decodeAlphaNumDot(r: StrictReader): AlphaNumDot {
+  return r.enum(AlphaNumDotTags);
+}
+
+export function compareAlphaNumDot(a: AlphaNumDot, b: AlphaNumDot): number {
+  return cmpTags(a, b, AlphaNumDotTags);
+}
+
+/** Type `Std.AlphaNumLodash` with semantic id `semid:lcO9yU0C-YPlxahE-89kktXU-4jmI4zB-DAFyjba-bW7uZ7Q#percent-bingo-caesar`. */
+export type AlphaNumLodash =
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "lodash"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaNumLodashTags: Record<AlphaNumLodash, number> = {
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  lodash: 95,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaNumLodash(w: StrictWriter, v: AlphaNumLodash): void {
+  w.enum(v, AlphaNumLodashTags);
+}
+
+export function decodeAlphaNumLodash(r: StrictReader): AlphaNumLodash {
+  return r.enum(AlphaNumLodashTags);
+}
+
+export function compareAlphaNumLodash(a: AlphaNumLodash, b: AlphaNumLodash): number {
+  return cmpTags(a, b, AlphaNumLodashTags);
+}
+
+/** Type `Std.AlphaSmall` with semantic id `semid:_RcIBN2u-BHn41a9-0qzvSAu-bqQXLZq-bk3BxUa-23~EDKE#magnum-martin-soviet`. */
+export type AlphaSmall =
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaSmallTags: Record<AlphaSmall, number> = {
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaSmall(w: StrictWriter, v: AlphaSmall): void {
+  w.enum(v, AlphaSmallTags);
+}
+
+export function decodeAlphaSmall(r: StrictReader): AlphaSmall {
+  return r.enum(AlphaSmallTags);
+}
+
+export function compareAlphaSmall(a: AlphaSmall, b: AlphaSmall): number {
+  return cmpTags(a, b, AlphaSmallTags);
+}
+
+/** Type `Std.AlphaSmallDash` with semantic id `semid:PnYxTYh9-eYfPPo2-attXtEt-Wie2k62-xVBne~M-GKGHZ8w#sheriff-monaco-watch`. */
+export type AlphaSmallDash =
+  | "dash"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaSmallDashTags: Record<AlphaSmallDash, number> = {
+  dash: 45,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaSmallDash(w: StrictWriter, v: AlphaSmallDash): void {
+  w.enum(v, AlphaSmallDashTags);
+}
+
+export function decodeAlphaSmallDash(r: StrictReader): AlphaSmallDash {
+  return r.enum(AlphaSmallDashTags);
+}
+
+export function compareAlphaSmallDash(a: AlphaSmallDash, b: AlphaSmallDash): number {
+  return cmpTags(a, b, AlphaSmallDashTags);
+}
+
+/** Type `Std.AlphaSmallDot` with semantic id `semid:Sl82FH_a-E38TuMu-wQ248Sn-Ixvm7PR-XzEP8mK-T9IjGVo#ford-radius-imitate`. */
+export type AlphaSmallDot =
+  | "dot"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaSmallDotTags: Record<AlphaSmallDot, number> = {
+  dot: 46,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaSmallDot(w: StrictWriter, v: AlphaSmallDot): void {
+  w.enum(v, AlphaSmallDotTags);
+}
+
+export function decodeAlphaSmallDot(r: StrictReader): AlphaSmallDot {
+  return r.enum(AlphaSmallDotTags);
+}
+
+export function compareAlphaSmallDot(a: AlphaSmallDot, b: AlphaSmallDot): number {
+  return cmpTags(a, b, AlphaSmallDotTags);
+}
+
+/** Type `Std.AlphaSmallLodash` with semantic id `semid:0T0Mwyob-rXqcrHq-J_Doovr-tvTglR0-NvIC~TI-ZIesst4#pioneer-eagle-spell`. */
+export type AlphaSmallLodash =
+  | "lodash"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z";
+
+const AlphaSmallLodashTags: Record<AlphaSmallLodash, number> = {
+  lodash: 95,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+};
+
+export function encodeAlphaSmallLodash(w: StrictWriter, v: AlphaSmallLodash): void {
+  w.enum(v, AlphaSmallLodashTags);
+}
+
+export function decodeAlphaSmallLodash(r: StrictReader): AlphaSmallLodash {
+  return r.enum(AlphaSmallLodashTags);
+}
+
+export function compareAlphaSmallLodash(a: AlphaSmallLodash, b: AlphaSmallLodash): number {
+  return cmpTags(a, b, AlphaSmallLodashTags);
+}
+
+/** Type `Std.Ascii` with semantic id `semid:sBLgUzNL-Pu6_Ulf-_mugjwJ-pvP875B-TmFy~iO-S~F0fSA#palma-program-parole`. */
+export type Ascii =
+  | "nul"
+  | "soh"
+  | "stx"
+  | "etx"
+  | "eot"
+  | "enq"
+  | "ack"
+  | "bel"
+  | "bs"
+  | "ht"
+  | "lf"
+  | "vt"
+  | "ff"
+  | "cr"
+  | "so"
+  | "si"
+  | "dle"
+  | "dc1"
+  | "dc2"
+  | "dc3"
+  | "dc4"
+  | "nack"
+  | "syn"
+  | "etb"
+  | "can"
+  | "em"
+  | "sub"
+  | "esc"
+  | "fs"
+  | "gs"
+  | "rs"
+  | "us"
+  | "space"
+  | "excl"
+  | "quotes"
+  | "hash"
+  | "dollar"
+  | "percent"
+  | "ampersand"
+  | "apostrophe"
+  | "bracketL"
+  | "bracketR"
+  | "asterisk"
+  | "plus"
+  | "comma"
+  | "minus"
+  | "dot"
+  | "slash"
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "colon"
+  | "semiColon"
+  | "less"
+  | "equal"
+  | "greater"
+  | "question"
+  | "at"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "sqBracketL"
+  | "backSlash"
+  | "sqBracketR"
+  | "caret"
+  | "lodash"
+  | "backtick"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z"
+  | "cBracketL"
+  | "pipe"
+  | "cBracketR"
+  | "tilde"
+  | "del";
+
+const AsciiTags: Record<Ascii, number> = {
+  nul: 0,
+  soh: 1,
+  stx: 2,
+  etx: 3,
+  eot: 4,
+  enq: 5,
+  ack: 6,
+  bel: 7,
+  bs: 8,
+  ht: 9,
+  lf: 10,
+  vt: 11,
+  ff: 12,
+  cr: 13,
+  so: 14,
+  si: 15,
+  dle: 16,
+  dc1: 17,
+  dc2: 18,
+  dc3: 19,
+  dc4: 20,
+  nack: 21,
+  syn: 22,
+  etb: 23,
+  can: 24,
+  em: 25,
+  sub: 26,
+  esc: 27,
+  fs: 28,
+  gs: 29,
+  rs: 30,
+  us: 31,
+  space: 32,
+  excl: 33,
+  quotes: 34,
+  hash: 35,
+  dollar: 36,
+  percent: 37,
+  ampersand: 38,
+  apostrophe: 39,
+  bracketL: 40,
+  bracketR: 41,
+  asterisk: 42,
+  plus: 43,
+  comma: 44,
+  minus: 45,
+  dot: 46,
+  slash: 47,
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  colon: 58,
+  semiColon: 59,
+  less: 60,
+  equal: 61,
+  greater: 62,
+  question: 63,
+  at: 64,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  sqBracketL: 91,
+  backSlash: 92,
+  sqBracketR: 93,
+  caret: 94,
+  lodash: 95,
+  backtick: 96,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+  cBracketL: 123,
+  pipe: 124,
+  cBracketR: 125,
+  tilde: 126,
+  del: 127,
+};
+
+export function encodeAscii(w: StrictWriter, v: Ascii): void {
+  w.enum(v, AsciiTags);
+}
+
+export function decodeAscii(r: StrictReader): Ascii {
+  return r.enum(AsciiTags);
+}
+
+export function compareAscii(a: Ascii, b: Ascii): number {
+  return cmpTags(a, b, AsciiTags);
+}
+
+/** Type `Std.AsciiPrintable` with semantic id `semid:SL4jFyro-hEWa54M-0oAY~Cf-oOMXvqi-yM854Kj-iHXnlrg#ultra-sunset-format`. */
+export type AsciiPrintable =
+  | "space"
+  | "excl"
+  | "quotes"
+  | "hash"
+  | "dollar"
+  | "percent"
+  | "ampersand"
+  | "apostrophe"
+  | "bracketL"
+  | "bracketR"
+  | "asterisk"
+  | "plus"
+  | "comma"
+  | "minus"
+  | "dot"
+  | "slash"
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "colon"
+  | "semiColon"
+  | "less"
+  | "equal"
+  | "greater"
+  | "question"
+  | "at"
+  | "_A"
+  | "_B"
+  | "_C"
+  | "_D"
+  | "_E"
+  | "_F"
+  | "_G"
+  | "_H"
+  | "_I"
+  | "_J"
+  | "_K"
+  | "_L"
+  | "_M"
+  | "_N"
+  | "_O"
+  | "_P"
+  | "_Q"
+  | "_R"
+  | "_S"
+  | "_T"
+  | "_U"
+  | "_V"
+  | "_W"
+  | "_X"
+  | "_Y"
+  | "_Z"
+  | "sqBracketL"
+  | "backSlash"
+  | "sqBracketR"
+  | "caret"
+  | "lodash"
+  | "backtick"
+  | "a"
+  | "b"
+  | "c"
+  | "d"
+  | "e"
+  | "f"
+  | "g"
+  | "h"
+  | "i"
+  | "j"
+  | "k"
+  | "l"
+  | "m"
+  | "n"
+  | "o"
+  | "p"
+  | "q"
+  | "r"
+  | "s"
+  | "t"
+  | "u"
+  | "v"
+  | "w"
+  | "x"
+  | "y"
+  | "z"
+  | "cBracketL"
+  | "pipe"
+  | "cBracketR"
+  | "tilde";
+
+const AsciiPrintableTags: Record<AsciiPrintable, number> = {
+  space: 32,
+  excl: 33,
+  quotes: 34,
+  hash: 35,
+  dollar: 36,
+  percent: 37,
+  ampersand: 38,
+  apostrophe: 39,
+  bracketL: 40,
+  bracketR: 41,
+  asterisk: 42,
+  plus: 43,
+  comma: 44,
+  minus: 45,
+  dot: 46,
+  slash: 47,
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  colon: 58,
+  semiColon: 59,
+  less: 60,
+  equal: 61,
+  greater: 62,
+  question: 63,
+  at: 64,
+  _A: 65,
+  _B: 66,
+  _C: 67,
+  _D: 68,
+  _E: 69,
+  _F: 70,
+  _G: 71,
+  _H: 72,
+  _I: 73,
+  _J: 74,
+  _K: 75,
+  _L: 76,
+  _M: 77,
+  _N: 78,
+  _O: 79,
+  _P: 80,
+  _Q: 81,
+  _R: 82,
+  _S: 83,
+  _T: 84,
+  _U: 85,
+  _V: 86,
+  _W: 87,
+  _X: 88,
+  _Y: 89,
+  _Z: 90,
+  sqBracketL: 91,
+  backSlash: 92,
+  sqBracketR: 93,
+  caret: 94,
+  lodash: 95,
+  backtick: 96,
+  a: 97,
+  b: 98,
+  c: 99,
+  d: 100,
+  e: 101,
+  f: 102,
+  g: 103,
+  h: 104,
+  i: 105,
+  j: 106,
+  k: 107,
+  l: 108,
+  m: 109,
+  n: 110,
+  o: 111,
+  p: 112,
+  q: 113,
+  r: 114,
+  s: 115,
+  t: 116,
+  u: 117,
+  v: 118,
+  w: 119,
+  x: 120,
+  y: 121,
+  z: 122,
+  cBracketL: 123,
+  pipe: 124,
+  cBracketR: 125,
+  tilde: 126,
+};
+
+export function encodeAsciiPrintable(w: StrictWriter, v: AsciiPrintable): void {
+  w.enum(v, AsciiPrintableTags);
+}
+
+export function decodeAsciiPrintable(r: StrictReader): AsciiPrintable {
+  return r.enum(AsciiPrintableTags);
+}
+
+export function compareAsciiPrintable(a: AsciiPrintable, b: AsciiPrintable): number {
+  return cmpTags(a, b, AsciiPrintableTags);
+}
+
+/** Type `Std.Bool` with semantic id `semid:YYYi0Xuu-8GYC3_d-1yYDgs2-tuuugJD-YB191E7-7EuT9k0#oxygen-complex-duet`. */
+export type Bool =
+  | "false"
+  | "true";
+
+const BoolTags: Record<Bool, number> = {
+  false: 0,
+  true: 1,
+};
+
+export function encodeBool(w: StrictWriter, v: Bool): void {
+  w.enum(v, BoolTags);
+}
+
+export function decodeBool(r: StrictReader): Bool {
+  return r.enum(BoolTags);
+}
+
+export function compareBool(a: Bool, b: Bool): number {
+  return cmpTags(a, b, BoolTags);
+}
+
+/** Type `Std.Dec` with semantic id `semid:vCiRscZq-xfXmEFm-jIHfaqC-MzoUNb0-0YIswsD-~AF9lUU#emotion-sweet-rabbit`. */
+export type Dec =
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine";
+
+const DecTags: Record<Dec, number> = {
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+};
+
+export function encodeDec(w: StrictWriter, v: Dec): void {
+  w.enum(v, DecTags);
+}
+
+export function decodeDec(r: StrictReader): Dec {
+  return r.enum(DecTags);
+}
+
+export function compareDec(a: Dec, b: Dec): number {
+  return cmpTags(a, b, DecTags);
+}
+
+/** Type `Std.DecDot` with semantic id `semid:5XbFX40A-QmIWjEI-_EpVX5R-dZ~p2An-HIP1y0h-jGiVpE8#cement-capital-belgium`. */
+export type DecDot =
+  | "dot"
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine";
+
+const DecDotTags: Record<DecDot, number> = {
+  dot: 46,
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+};
+
+export function encodeDecDot(w: StrictWriter, v: DecDot): void {
+  w.enum(v, DecDotTags);
+}
+
+export function decodeDecDot(r: StrictReader): DecDot {
+  return r.enum(DecDotTags);
+}
+
+export function compareDecDot(a: DecDot, b: DecDot): number {
+  return cmpTags(a, b, DecDotTags);
+}
+
+/** Type `Std.HexDecCaps` with semantic id `semid:7t7D66oI-_wEPL5b-heg4Dmt-h~5gdxp-zAfjF6I-mV0FZ2s#canada-major-convert`. */
+export type HexDecCaps =
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "ten"
+  | "eleven"
+  | "twelve"
+  | "thirteen"
+  | "fourteen"
+  | "fifteen";
+
+const HexDecCapsTags: Record<HexDecCaps, number> = {
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  ten: 65,
+  eleven: 66,
+  twelve: 67,
+  thirteen: 68,
+  fourteen: 69,
+  fifteen: 70,
+};
+
+export function encodeHexDecCaps(w: StrictWriter, v: HexDecCaps): void {
+  w.enum(v, HexDecCapsTags);
+}
+
+export function decodeHexDecCaps(r: StrictReader): HexDecCaps {
+  return r.enum(HexDecCapsTags);
+}
+
+export function compareHexDecCaps(a: HexDecCaps, b: HexDecCaps): number {
+  return cmpTags(a, b, HexDecCapsTags);
+}
+
+/** Type `Std.HexDecSmall` with semantic id `semid:pi44ShNR-hBg7naa-5eWsG6I-x1xoAxO-d05EUjq-BJrj1_E#crater-plasma-diagram`. */
+export type HexDecSmall =
+  | "zero"
+  | "one"
+  | "two"
+  | "three"
+  | "four"
+  | "five"
+  | "six"
+  | "seven"
+  | "eight"
+  | "nine"
+  | "ten"
+  | "eleven"
+  | "twelve"
+  | "thirteen"
+  | "fourteen"
+  | "fifteen";
+
+const HexDecSmallTags: Record<HexDecSmall, number> = {
+  zero: 48,
+  one: 49,
+  two: 50,
+  three: 51,
+  four: 52,
+  five: 53,
+  six: 54,
+  seven: 55,
+  eight: 56,
+  nine: 57,
+  ten: 97,
+  eleven: 98,
+  twelve: 99,
+  thirteen: 100,
+  fourteen: 101,
+  fifteen: 102,
+};
+
+export function encodeHexDecSmall(w: StrictWriter, v: HexDecSmall): void {
+  w.enum(v, HexDecSmallTags);
+}
+
+export function decodeHexDecSmall(r: StrictReader): HexDecSmall {
+  return r.enum(HexDecSmallTags);
+}
+
+export function compareHexDecSmall(a: HexDecSmall, b: HexDecSmall): number {
+  return cmpTags(a, b, HexDecSmallTags);
+}
+
+/** Type `Std.U1` with semantic id `semid:LKzY8xRa-GHDg2ax-eM00XwM-vbzSJ3f-2d5ma0H-Q1MmcQ4#concept-inside-samuel`. */
+export type U1 =
+  | "_0"
+  | "_1";
+
+const U1Tags: Record<U1, number> = {
+  _0: 0,
+  _1: 1,
+};
+
+export function encodeU1(w: StrictWriter, v: U1): void {
+  w.enum(v, U1Tags);
+}
+
+export function decodeU1(r: StrictReader): U1 {
+  return r.enum(U1Tags);
+}
+
+export function compareU1(a: U1, b: U1): number {
+  return cmpTags(a, b, U1Tags);
+}
+
+/** Type `Std.U2` with semantic id `semid:56TCokKN-7CCsCZP-iC1Akca-CQhPKxU-BV5KA6p-YWPSTs8#sonata-nickel-travel`. */
+export type U2 =
+  | "_0"
+  | "_1"
+  | "_2"
+  | "_3";
+
+const U2Tags: Record<U2, number> = {
+  _0: 0,
+  _1: 1,
+  _2: 2,
+  _3: 3,
+};
+
+export function encodeU2(w: StrictWriter, v: U2): void {
+  w.enum(v, U2Tags);
+}
+
+export function decodeU2(r: StrictReader): U2 {
+  return r.enum(U2Tags);
+}
+
+export function compareU2(a: U2, b: U2): number {
+  return cmpTags(a, b, U2Tags);
+}
+
+/** Type `Std.U3` with semantic id `semid:VZDfUGDA-sYmvPZQ-o2UwNLG-pvlsU8H-KJhUGU2-hTiMWZw#burma-travel-diet`. */
+export type U3 =
+  | "_0"
+  | "_1"
+  | "_2"
+  | "_3"
+  | "_4"
+  | "_5"
+  | "_6"
+  | "_7";
+
+const U3Tags: Record<U3, number> = {
+  _0: 0,
+  _1: 1,
+  _2: 2,
+  _3: 3,
+  _4: 4,
+  _5: 5,
+  _6: 6,
+  _7: 7,
+};
+
+export function encodeU3(w: StrictWriter, v: U3): void {
+  w.enum(v, U3Tags);
+}
+
+export function decodeU3(r: StrictReader): U3 {
+  return r.enum(U3Tags);
+}
+
+export function compareU3(a: U3, b: U3): number {
+  return cmpTags(a, b, U3Tags);
+}
+
+/** Type `Std.U4` with semantic id `semid:nhTM5rGF-zKR3ukR-jS~osWH-Gpfgwyt-67B32qU-UiqTGcc#halt-crack-kayak`. */
+export type U4 =
+  | "_0"
+  | "_1"
+  | "_2"
+  | "_3"
+  | "_4"
+  | "_5"
+  | "_6"
+  | "_7"
+  | "_8"
+  | "_9"
+  | "_10"
+  | "_11"
+  | "_12"
+  | "_13"
+  | "_14"
+  | "_15";
+
+const U4Tags: Record<U4, number> = {
+  _0: 0,
+  _1: 1,
+  _2: 2,
+  _3: 3,
+  _4: 4,
+  _5: 5,
+  _6: 6,
+  _7: 7,
+  _8: 8,
+  _9: 9,
+  _10: 10,
+  _11: 11,
+  _12: 12,
+  _13: 13,
+  _14: 14,
+  _15: 15,
+};
+
+export function encodeU4(w: StrictWriter, v: U4): void {
+  w.enum(v, U4Tags);
+}
+
+export function decodeU4(r: StrictReader): U4 {
+  return r.enum(U4Tags);
+}
+
+export function compareU4(a: U4, b: U4): number {
+  return cmpTags(a, b, U4Tags);
+}
+
+/** Type `Std.U5` with semantic id `semid:Zlhw2I5c-0QmQ6Uw-XWD3cn6-NTnLVCW-KDDYJnf-rjoDC5I#orbit-graph-sonic`. */
+export type U5 =
+  | "_0"
+  | "_1"
+  | "_2"
+  | "_3"
+  | "_4"
+  | "_5"
+  | "_6"
+  | "_7"
+  | "_8"
+  | "_9"
+  | "_10"
+  | "_11"
+  | "_12"
+  | "_13"
+  | "_14"
+  | "_15"
+  | "_16"
+  | "_17"
+  | "_18"
+  | "_19"
+  | "_20"
+  | "_21"
+  | "_22"
+  | "_23"
+  | "_24"
+  | "_25"
+  | "_26"
+  | "_27"
+  | "_28"
+  | "_29"
+  | "_30"
+  | "_31";
+
+const U5Tags: Record<U5, number> = {
+  _0: 0,
+  _1: 1,
+  _2: 2,
+  _3: 3,
+  _4: 4,
+  _5: 5,
+  _6: 6,
+  _7: 7,
+  _8: 8,
+  _9: 9,
+  _10: 10,
+  _11: 11,
+  _12: 12,
+  _13: 13,
+  _14: 14,
+  _15: 15,
+  _16: 16,
+  _17: 17,
+  _18: 18,
+  _19: 19,
+  _20: 20,
+  _21: 21,
+  _22: 22,
+  _23: 23,
+  _24: 24,
+  _25: 25,
+  _26: 26,
+  _27: 27,
+  _28: 28,
+  _29: 29,
+  _30: 30,
+  _31: 31,
+};
+
+export function encodeU5(w: StrictWriter, v: U5): void {
+  w.enum(v, U5Tags);
+}
+
+export function decodeU5(r: StrictReader): U5 {
+  return r.enum(U5Tags);
+}
+
+export function compareU5(a: U5, b: U5): number {
+  return cmpTags(a, b, U5Tags);
+}
+
+/** Type `Std.U6` with semantic id `semid:ILZ9XVhm-FZJ3KKB-zvbNYTf-vrp5Uch-CUNZXQU-oREET4g#jupiter-brenda-harlem`. */
+export type U6 =
+  | "_0"
+  | "_1"
+  | "_2"
+  | "_3"
+  | "_4"
+  | "_5"
+  | "_6"
+  | "_7"
+  | "_8"
+  | "_9"
+  | "_10"
+  | "_11"
+  | "_12"
+  | "_13"
+  | "_14"
+  | "_15"
+  | "_16"
+  | "_17"
+  | "_18"
+  | "_19"
+  | "_20"
+  | "_21"
+  | "_22"
+  | "_23"
+  | "_24"
+  | "_25"
+  | "_26"
+  | "_27"
+  | "_28"
+  | "_29"
+  | "_30"
+  | "_31"
+  | "_32"
+  | "_33"
+  | "_34"
+  | "_35"
+  | "_36"
+  | "_37"
+  | "_38"
+  | "_39"
+  | "_40"
+  | "_41"
+  | "_42"
+  | "_43"
+  | "_44"
+  | "_45"
+  | "_46"
+  | "_47"
+  | "_48"
+  | "_49"
+  | "_50"
+  | "_51"
+  | "_52"
+  | "_53"
+  | "_54"
+  | "_55"
+  | "_56"
+  | "_57"
+  | "_58"
+  | "_59"
+  | "_60"
+  | "_61"
+  | "_62"
+  | "_63";
+
+const U6Tags: Record<U6, number> = {
+  _0: 0,
+  _1: 1,
+  _2: 2,
+  _3: 3,
+  _4: 4,
+  _5: 5,
+  _6: 6,
+  _7: 7,
+  _8: 8,
+  _9: 9,
+  _10: 10,
+  _11: 11,
+  _12: 12,
+  _13: 13,
+  _14: 14,
+  _15: 15,
+  _16: 16,
+  _17: 17,
+  _18: 18,
+  _19: 19,
+  _20: 20,
+  _21: 21,
+  _22: 22,
+  _23: 23,
+  _24: 24,
+  _25: 25,
+  _26: 26,
+  _27: 27,
+  _28: 28,
+  _29: 29,
+  _30: 30,
+  _31: 31,
+  _32: 32,
+  _33: 33,
+  _34: 34,
+  _35: 35,
+  _36: 36,
+  _37: 37,
+  _38: 38,
+  _39: 39,
+  _40: 40,
+  _41: 41,
+  _42: 42,
+  _43: 43,
+  _44: 44,
+  _45: 45,
+  _46: 46,
+  _47: 47,
+  _48: 48,
+  _49: 49,
+  _50: 50,
+  _51: 51,
+  _52: 52,
+  _53: 53,
+  _54: 54,
+  _55: 55,
+  _56: 56,
+  _57: 57,
+  _58: 58,
+  _59: 59,
+  _60: 60,
+  _61: 61,
+  _62: 62,
+  _63: 63,
+};
+
+export function encodeU6(w: StrictWriter, v: U6): void {
+  w.enum(v, U6Tags);
+}
+
+export function decodeU6(r: StrictReader): U6 {
+  return r.enum(U6Tags);
+}
+
+export function compareU6(a: U6, b: U6): number {
+  return cmpTags(a, b, U6Tags);
+}
+
+/** Type `Std.U7` with semantic id `semid:Lvkj4x6J-5mc_gf4-aexZg8I-C9H24RH-iURVwQV-QpQp1ls#arena-pixel-quest`. */
+export type U7 =
+  | "_0"
+  | "_1"
+  | "_2"
+  | "_3"
+  | "_4"
+  | "_5"
+  | "_6"
+  | "_7"
+  | "_8"
+  | "_9"
+  | "_10"
+  | "_11"
+  | "_12"
+  | "_13"
+  | "_14"
+  | "_15"
+  | "_16"
+  | "_17"
+  | "_18"
+  | "_19"
+  | "_20"
+  | "_21"
+  | "_22"
+  | "_23"
+  | "_24"
+  | "_25"
+  | "_26"
+  | "_27"
+  | "_28"
+  | "_29"
+  | "_30"
+  | "_31"
+  | "_32"
+  | "_33"
+  | "_34"
+  | "_35"
+  | "_36"
+  | "_37"
+  | "_38"
+  | "_39"
+  | "_40"
+  | "_41"
+  | "_42"
+  | "_43"
+  | "_44"
+  | "_45"
+  | "_46"
+  | "_47"
+  | "_48"
+  | "_49"
+  | "_50"
+  | "_51"
+  | "_52"
+  | "_53"
+  | "_54"
+  | "_55"
+  | "_56"
+  | "_57"
+  | "_58"
+  | "_59"
+  | "_60"
+  | "_61"
+  | "_62"
+  | "_63"
+  | "_64"
+  | "_65"
+  | "_66"
+  | "_67"
+  | "_68"
+  | "_69"
+  | "_70"
+  | "_71"
+  | "_72"
+  | "_73"
+  | "_74"
+  | "_75"
+  | "_76"
+  | "_77"
+  | "_78"
+  | "_79"
+  | "_80"
+  | "_81"
+  | "_82"
+  | "_83"
+  | "_84"
+  | "_85"
+  | "_86"
+  | "_87"
+  | "_88"
+  | "_89"
+  | "_90"
+  | "_91"
+  | "_92"
+  | "_93"
+  | "_94"
+  | "_95"
+  | "_96"
+  | "_97"
+  | "_98"
+  | "_99"
+  | "_100"
+  | "_101"
+  | "_102"
+  | "_103"
+  | "_104"
+  | "_105"
+  | "_106"
+  | "_107"
+  | "_108"
+  | "_109"
+  | "_110"
+  | "_111"
+  | "_112"
+  | "_113"
+  | "_114"
+  | "_115"
+  | "_116"
+  | "_117"
+  | "_118"
+  | "_119"
+  | "_120"
+  | "_121"
+  | "_122"
+  | "_123"
+  | "_124"
+  | "_125"
+  | "_126"
+  | "_127";
+
+const U7Tags: Record<U7, number> = {
+  _0: 0,
+  _1: 1,
+  _2: 2,
+  _3: 3,
+  _4: 4,
+  _5: 5,
+  _6: 6,
+  _7: 7,
+  _8: 8,
+  _9: 9,
+  _10: 10,
+  _11: 11,
+  _12: 12,
+  _13: 13,
+  _14: 14,
+  _15: 15,
+  _16: 16,
+  _17: 17,
+  _18: 18,
+  _19: 19,
+  _20: 20,
+  _21: 21,
+  _22: 22,
+  _23: 23,
+  _24: 24,
+  _25: 25,
+  _26: 26,
+  _27: 27,
+  _28: 28,
+  _29: 29,
+  _30: 30,
+  _31: 31,
+  _32: 32,
+  _33: 33,
+  _34: 34,
+  _35: 35,
+  _36: 36,
+  _37: 37,
+  _38: 38,
+  _39: 39,
+  _40: 40,
+  _41: 41,
+  _42: 42,
+  _43: 43,
+  _44: 44,
+  _45: 45,
+  _46: 46,
+  _47: 47,
+  _48: 48,
+  _49: 49,
+  _50: 50,
+  _51: 51,
+  _52: 52,
+  _53: 53,
+  _54: 54,
+  _55: 55,
+  _56: 56,
+  _57: 57,
+  _58: 58,
+  _59: 59,
+  _60: 60,
+  _61: 61,
+  _62: 62,
+  _63: 63,
+  _64: 64,
+  _65: 65,
+  _66: 66,
+  _67: 67,
+  _68: 68,
+  _69: 69,
+  _70: 70,
+  _71: 71,
+  _72: 72,
+  _73: 73,
+  _74: 74,
+  _75: 75,
+  _76: 76,
+  _77: 77,
+  _78: 78,
+  _79: 79,
+  _80: 80,
+  _81: 81,
+  _82: 82,
+  _83: 83,
+  _84: 84,
+  _85: 85,
+  _86: 86,
+  _87: 87,
+  _88: 88,
+  _89: 89,
+  _90: 90,
+  _91: 91,
+  _92: 92,
+  _93: 93,
+  _94: 94,
+  _95: 95,
+  _96: 96,
+  _97: 97,
+  _98: 98,
+  _99: 99,
+  _100: 100,
+  _101: 101,
+  _102: 102,
+  _103: 103,
+  _104: 104,
+  _105: 105,
+  _106: 106,
+  _107: 107,
+  _108: 108,
+  _109: 109,
+  _110: 110,
+  _111: 111,
+  _112: 112,
+  _113: 113,
+  _114: 114,
+  _115: 115,
+  _116: 116,
+  _117: 117,
+  _118: 118,
+  _119: 119,
+  _120: 120,
+  _121: 121,
+  _122: 122,
+  _123: 123,
+  _124: 124,
+  _125: 125,
+  _126: 126,
+  _127: 127,
+};
+
+export function encodeU7(w: StrictWriter, v: U7): void {
+  w.enum(v, U7Tags);
+}
+
+export function decodeU7(r: StrictReader): U7 {
+  return r.enum(U7Tags);
+}
+
+export function compareU7(a: U7, b: U7): number {
+  return cmpTags(a, b, U7Tags);
+}
@@ -0,0 +1,642 @@
+// Rust types for the strict type library `StrictTypes`.
+// Generated by strict_types; do not edit manually.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use amplify::confinement::{
+    Confined, LargeVec, MediumOrdMap, MediumOrdSet, SmallOrdMap, SmallOrdSet, TinyOrdMap,
+    TinyOrdSet, TinyVec,
+};
+use strict_encoding::{strict_dumb, RString, StrictDecode, StrictDumb, StrictEncode, StrictType};
+use strict_encoding::stl::{AlphaCapsLodash, AlphaLodash, AlphaNumLodash, AlphaSmallLodash};
+
+pub const LIB_NAME_STRICT_TYPES: &str = "StrictTypes";
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Dependency {
+    pub id: TypeLibId,
+    pub name: LibName,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct EnumVariants(pub Confined<BTreeSet<Variant>, 1, 255>);
+
+impl StrictDumb for EnumVariants {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct ExternRef {
+    pub lib_id: TypeLibId,
+    pub sem_id: SemId,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldInlineRef {
+    pub name: FieldName,
+    pub ty: InlineRef,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldInlineRef1 {
+    pub name: FieldName,
+    pub ty: InlineRef1,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldInlineRef2 {
+    pub name: FieldName,
+    pub ty: InlineRef2,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldLibRef {
+    pub name: FieldName,
+    pub ty: LibRef,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldName(pub RString<AlphaSmallLodash, AlphaNumLodash, 1, 100>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct FieldSemId {
+    pub name: FieldName,
+    pub ty: SemId,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Ident(pub RString<AlphaLodash, AlphaNumLodash, 1, 100>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum InlineRef {
+    #[strict_type(tag = 0)]
+    Inline(TyInlineRef1),
+    #[strict_type(tag = 1)]
+    Named(SemId),
+    #[strict_type(tag = 2)]
+    Extern(ExternRef),
+}
+
+impl StrictDumb for InlineRef {
+    fn strict_dumb() -> Self {
+        Self::Inline(strict_dumb!())
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum InlineRef1 {
+    #[strict_type(tag = 0)]
+    Inline(TyInlineRef2),
+    #[strict_type(tag = 1)]
+    Named(SemId),
+    #[strict_type(tag = 2)]
+    Extern(ExternRef),
+}
+
+impl StrictDumb for InlineRef1 {
+    fn strict_dumb() -> Self {
+        Self::Inline(strict_dumb!())
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum InlineRef2 {
+    #[strict_type(tag = 0)]
+    Named(SemId),
+    #[strict_type(tag = 1)]
+    Extern(ExternRef),
+}
+
+impl StrictDumb for InlineRef2 {
+    fn strict_dumb() -> Self {
+        Self::Named(strict_dumb!())
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum ItemCase {
+    #[strict_type(tag = 0)]
+    UnnamedField(u8),
+    #[strict_type(tag = 1)]
+    NamedField(u8, FieldName),
+    #[strict_type(tag = 2)]
+    UnionVariant(u8, VariantName),
+    #[strict_type(tag = 16, dumb)]
+    ArrayItem,
+    #[strict_type(tag = 17)]
+    ListItem,
+    #[strict_type(tag = 18)]
+    SetItem,
+    #[strict_type(tag = 19)]
+    MapKey,
+    #[strict_type(tag = 20)]
+    MapValue,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct LibName(pub RString<AlphaCapsLodash, AlphaNumLodash, 1, 100>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum LibRef {
+    #[strict_type(tag = 0)]
+    Inline(TyInlineRef),
+    #[strict_type(tag = 1)]
+    Named(SemId),
+    #[strict_type(tag = 2)]
+    Extern(ExternRef),
+}
+
+impl StrictDumb for LibRef {
+    fn strict_dumb() -> Self {
+        Self::Inline(strict_dumb!())
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct MemoryLayout {
+    pub items: LargeVec<TypeInfo>,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsInlineRef(pub Confined<Vec<FieldInlineRef>, 1, 255>);
+
+impl StrictDumb for NamedFieldsInlineRef {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsInlineRef1(pub Confined<Vec<FieldInlineRef1>, 1, 255>);
+
+impl StrictDumb for NamedFieldsInlineRef1 {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsInlineRef2(pub Confined<Vec<FieldInlineRef2>, 1, 255>);
+
+impl StrictDumb for NamedFieldsInlineRef2 {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsLibRef(pub Confined<Vec<FieldLibRef>, 1, 255>);
+
+impl StrictDumb for NamedFieldsLibRef {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct NamedFieldsSemId(pub Confined<Vec<FieldSemId>, 1, 255>);
+
+impl StrictDumb for NamedFieldsSemId {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum NestedCase {
+    #[strict_type(tag = 0)]
+    NewType(Option<TypeFqn>),
+    #[strict_type(tag = 1, dumb)]
+    Option,
+    #[strict_type(tag = 16)]
+    ByteStr,
+    #[strict_type(tag = 17)]
+    AsciiStr(Option<TypeFqn>),
+    #[strict_type(tag = 18)]
+    UniStr,
+    #[strict_type(tag = 19)]
+    RStr(Option<TypeFqn>, Option<TypeFqn>, Sizing),
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Primitive(pub u8);
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct SemId(pub [u8; 32]);
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Sizing {
+    pub min: u64,
+    pub max: u64,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct SymbolRef {
+    pub lib_name: LibName,
+    pub ty_name: TypeName,
+    pub lib_id: TypeLibId,
+    pub sem_id: SemId,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct SymbolicSys {
+    pub symbols: Symbols,
+    pub types: TypeSystem,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Symbols {
+    pub libs: SmallOrdSet<Dependency>,
+    pub symbols: MediumOrdSet<TypeSymbol>,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TyInlineRef {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsInlineRef),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsInlineRef),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsInlineRef),
+    #[strict_type(tag = 7)]
+    Array(InlineRef, u16),
+    #[strict_type(tag = 8)]
+    List(InlineRef, Sizing),
+    #[strict_type(tag = 9)]
+    Set(InlineRef, Sizing),
+    #[strict_type(tag = 10)]
+    Map(InlineRef, InlineRef, Sizing),
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TyInlineRef1 {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsInlineRef1),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsInlineRef1),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsInlineRef1),
+    #[strict_type(tag = 7)]
+    Array(InlineRef1, u16),
+    #[strict_type(tag = 8)]
+    List(InlineRef1, Sizing),
+    #[strict_type(tag = 9)]
+    Set(InlineRef1, Sizing),
+    #[strict_type(tag = 10)]
+    Map(InlineRef1, InlineRef1, Sizing),
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TyInlineRef2 {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsInlineRef2),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsInlineRef2),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsInlineRef2),
+    #[strict_type(tag = 7)]
+    Array(InlineRef2, u16),
+    #[strict_type(tag = 8)]
+    List(InlineRef2, Sizing),
+    #[strict_type(tag = 9)]
+    Set(InlineRef2, Sizing),
+    #[strict_type(tag = 10)]
+    Map(InlineRef2, InlineRef2, Sizing),
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TyLibRef {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsLibRef),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsLibRef),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsLibRef),
+    #[strict_type(tag = 7)]
+    Array(LibRef, u16),
+    #[strict_type(tag = 8)]
+    List(LibRef, Sizing),
+    #[strict_type(tag = 9)]
+    Set(LibRef, Sizing),
+    #[strict_type(tag = 10)]
+    Map(LibRef, LibRef, Sizing),
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES, tags = custom)]
+pub enum TySemId {
+    #[strict_type(tag = 0)]
+    Primitive(Primitive),
+    #[strict_type(tag = 1, dumb)]
+    Unicode,
+    #[strict_type(tag = 3)]
+    Enum(EnumVariants),
+    #[strict_type(tag = 4)]
+    Union(UnionVariantsSemId),
+    #[strict_type(tag = 5)]
+    Tuple(UnnamedFieldsSemId),
+    #[strict_type(tag = 6)]
+    Struct(NamedFieldsSemId),
+    #[strict_type(tag = 7)]
+    Array(SemId, u16),
+    #[strict_type(tag = 8)]
+    List(SemId, Sizing),
+    #[strict_type(tag = 9)]
+    Set(SemId, Sizing),
+    #[strict_type(tag = 10)]
+    Map(SemId, SemId, Sizing),
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeFqn {
+    pub lib: LibName,
+    pub name: TypeName,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeInfo {
+    pub depth: u32,
+    pub ty: TySemId,
+    pub fqn: Option<TypeFqn>,
+    pub item: Option<ItemCase>,
+    pub nested: TinyVec<NestedCase>,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeLib {
+    pub name: LibName,
+    pub dependencies: TinyOrdSet<Dependency>,
+    pub extern_types: TinyOrdMap<LibName, SmallOrdMap<SemId, TypeName>>,
+    pub types: Confined<BTreeMap<TypeName, TyLibRef>, 1, 65535>,
+}
+
+impl StrictDumb for TypeLib {
+    fn strict_dumb() -> Self {
+        Self {
+            name: strict_dumb!(),
+            dependencies: strict_dumb!(),
+            extern_types: strict_dumb!(),
+            types: Confined::from_iter_checked([(strict_dumb!(), strict_dumb!())]),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeLibId(pub [u8; 32]);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeName(pub RString<AlphaCapsLodash, AlphaNumLodash, 1, 100>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeSymbol {
+    pub id: SemId,
+    pub fqn: Option<TypeFqn>,
+}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeSysId(pub [u8; 32]);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct TypeSystem(pub MediumOrdMap<SemId, TySemId>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsInlineRef(pub TinyOrdMap<u8, VariantInfoInlineRef>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsInlineRef1(pub TinyOrdMap<u8, VariantInfoInlineRef1>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsInlineRef2(pub TinyOrdMap<u8, VariantInfoInlineRef2>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsLibRef(pub TinyOrdMap<u8, VariantInfoLibRef>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnionVariantsSemId(pub TinyOrdMap<u8, VariantInfoSemId>);
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsInlineRef(pub Confined<Vec<InlineRef>, 1, 255>);
+
+impl StrictDumb for UnnamedFieldsInlineRef {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsInlineRef1(pub Confined<Vec<InlineRef1>, 1, 255>);
+
+impl StrictDumb for UnnamedFieldsInlineRef1 {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsInlineRef2(pub Confined<Vec<InlineRef2>, 1, 255>);
+
+impl StrictDumb for UnnamedFieldsInlineRef2 {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsLibRef(pub Confined<Vec<LibRef>, 1, 255>);
+
+impl StrictDumb for UnnamedFieldsLibRef {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct UnnamedFieldsSemId(pub Confined<Vec<SemId>, 1, 255>);
+
+impl StrictDumb for UnnamedFieldsSemId {
+    fn strict_dumb() -> Self {
+        Self(Confined::from_iter_checked([strict_dumb!()]))
+    }
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct Variant {
+    pub name: VariantName,
+    pub tag: u8,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoInlineRef {
+    pub name: VariantName,
+    pub ty: InlineRef,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoInlineRef1 {
+    pub name: VariantName,
+    pub ty: InlineRef1,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoInlineRef2 {
+    pub name: VariantName,
+    pub ty: InlineRef2,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoLibRef {
+    pub name: VariantName,
+    pub ty: LibRef,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantInfoSemId {
+    pub name: VariantName,
+    pub ty: SemId,
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_STRICT_TYPES)]
+pub struct VariantName(pub RString<AlphaSmallLodash, AlphaNumLodash, 1, 100>);
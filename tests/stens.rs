@@ -0,0 +1,184 @@
+// Strict encoding schema library, implementing validation and parsing
+// strict encoded data against a schema.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2022-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@ubideco.org>
+//
+// Copyright 2022-2024 UBIDECO Institute
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::{fs, str};
+
+const SOURCE: &str = include_str!("data/Events.sty");
+
+fn stens(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_stens")).args(args).output().expect("unable to run stens")
+}
+
+fn stdout(output: &Output) -> &str { str::from_utf8(&output.stdout).unwrap() }
+
+fn stderr(output: &Output) -> &str { str::from_utf8(&output.stderr).unwrap() }
+
+fn workdir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("Events.sty"), SOURCE).unwrap();
+    dir
+}
+
+#[test]
+fn usage_errors() {
+    let output = stens(&[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).starts_with("Error: no command given\n"));
+
+    let output = stens(&["frobnicate"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).starts_with("Error: unknown command `frobnicate`\n"));
+
+    let output = stens(&["decode", "Events.Kind"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).starts_with("Error: command `decode` requires argument `DATA`\n"));
+
+    let output = stens(&["layout", "Events.Kind", "--frob"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).starts_with("Error: unknown option `--frob`\n"));
+
+    let output = stens(&["--help"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Usage: stens <COMMAND>"));
+}
+
+#[test]
+fn compile_and_verify() {
+    let dir = workdir("compile");
+    let src = dir.join("Events.sty");
+    let stl = dir.join("Events.stl");
+    let sty = dir.join("Events.compiled.sty");
+
+    let output = stens(&["compile", src.to_str().unwrap(), "-o", stl.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let output = stens(&["compile", stl.to_str().unwrap(), "-f", "sty"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    fs::write(&sty, &output.stdout).unwrap();
+
+    let output = stens(&["id", stl.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let id = stdout(&output).trim().to_owned();
+    assert!(id.starts_with("stl:"));
+    assert!(stdout(&output).contains('#'));
+
+    // Id recorded in the source header is verified
+    let output = stens(&["id", sty.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output).trim(), id);
+
+    let output = stens(&["id", src.to_str().unwrap(), &id]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let output = stens(&["id", src.to_str().unwrap(), &format!("urn:sten:lib:{id}")]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let sem_id = "semid:9SJxLn7R-yLTOnlf-4jgnS8k-VKKldNt-v3sxyZi-6ptTfyc";
+    let output = stens(&["id", src.to_str().unwrap(), &format!("urn:sten:id:{sem_id}")]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("is a type and not a library id"));
+
+    let other = "stl:gonrTQ8L-cFSvdEs-F6MHXnS-MDplxjy-8_lZ5j5-_lY8MWo#delete-roman-hair";
+    let output = stens(&["id", src.to_str().unwrap(), other]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("doesn't match the expected id"));
+
+    let output = stens(&["inspect", stl.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), fs::read_to_string(&sty).unwrap());
+}
+
+#[test]
+fn encode_decode() {
+    let dir = workdir("values");
+    let lib = dir.join("Events.sty");
+    let lib = lib.to_str().unwrap();
+
+    let output = stens(&["encode", "Events.Event", "(at 42, kind custom).settled", "-l", lib]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "012a0000000000000007\n");
+
+    let output = stens(&["decode", "Events.Event", "012a0000000000000007", "-l", lib]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "(at 42, kind custom).settled\n");
+
+    let bin = dir.join("event.bin");
+    let output = stens(&[
+        "encode",
+        "Events.Event",
+        "(1, 2).transfer",
+        "-l",
+        lib,
+        "-o",
+        bin.to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read(&bin).unwrap(), [0, 1, 0, 2, 0, 0]);
+    let output = stens(&["decode", "Events.Event", bin.to_str().unwrap(), "-l", lib]);
+    assert_eq!(stdout(&output), "(1, 2).transfer\n");
+
+    let output = stens(&["decode", "Events.Event", "0100", "-l", lib]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("Error: unable to decode `Events.Event`"));
+
+    let output = stens(&["decode", "Events.Absent", "00", "-l", lib]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn dump() {
+    let dir = workdir("dump");
+    let lib = dir.join("Events.sty");
+    let lib = lib.to_str().unwrap();
+
+    let output = stens(&["dump", "Events.Event", "012a0000000000000007", "-l", lib]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "\
+00000000  01                                               tag     = settled
+00000001  2a 00 00 00 00 00 00 00                          U64     .at = 42
+00000009  07                                               tag     .kind = custom
+"
+    );
+
+    let output = stens(&["dump", "Events.Event", "0100", "-l", lib]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).starts_with("00000000  01  "));
+    assert!(stderr(&output).starts_with("Error: data don't match `Events.Event`"));
+}
+
+#[test]
+fn layout() {
+    let dir = workdir("layout");
+    let lib = dir.join("Events.sty");
+    let output = stens(&["layout", "Events.Event", "-l", lib.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("union Event, size 1..10\n"));
+    assert!(stdout(&output).contains("enum kind, Kind, plain 0, custom 7, size 1\n"));
+
+    let output = stens(&["layout", "Events.Event#alpha-beta-gamma", "-l", lib.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("Error: checkword `alpha-beta-gamma` doesn't match"));
+    let output = stens(&["layout", "Events.Event#alpha", "-l", lib.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+}